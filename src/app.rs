@@ -1,9 +1,38 @@
 use anyhow::{Context, Result};
-use chrono::{Local, NaiveDate};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Weekday};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::models::{Frequency, HabitStatus, Week};
-use crate::storage::Storage;
+use crate::exports::{
+    DayExport, EntryExport, HabitTotalsExport, StatusExport, WeekExport, EXPORT_SCHEMA,
+};
+use crate::keymap::{keymap_path, KeyMap};
+use crate::models::{Frequency, HabitStatus, Template, Week};
+use crate::storage::{
+    read_data_file, round_rate, validate_log_date, Change, Clock, DataLock, HabitData, ImportDiff,
+    NoteDraft, Storage,
+};
+use crate::theme::{theme_path, Theme, HABIT_COLORS};
+
+/// Interval used when a habit is first switched to every-N-days
+const DEFAULT_INTERVAL_DAYS: u32 = 3;
+
+/// Days used when a habit is first switched to specific weekdays
+const DEFAULT_SPECIFIC_DAYS: [Weekday; 3] = [Weekday::Mon, Weekday::Wed, Weekday::Fri];
+
+/// Most actions kept for undo; older ones are dropped
+pub const UNDO_LIMIT: usize = 50;
+
+/// Weeks of history (ending with the viewed week) in the trend CSV export
+pub const TREND_WEEKS: usize = 12;
+
+/// Months of history (ending with the selected day's month) in the monthly trend CSV export
+pub const TREND_MONTHS: usize = 12;
+
+/// Longest custom export range, in days
+pub const MAX_EXPORT_DAYS: i64 = 366;
 
 /// Different screens/views in the application
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,6 +49,310 @@ pub enum AppView {
     NoteInput,
     /// Export confirmation view
     ExportConfirmation,
+    /// Editable grid of all habits across the week
+    Grid,
+    /// Confirmation prompt before clearing the current week
+    ResetWeekConfirmation,
+    /// Searchable list of actions
+    CommandPalette,
+    /// Reverse-chronological feed of the week's activity
+    Activity,
+    /// Reflection note editor for the current week
+    WeekNoteInput,
+    /// Read-only preview of the export before it's written
+    ExportPreview,
+    /// Typing the first and last day the export should cover
+    ExportRange,
+    /// Typing a day to jump straight to
+    GoToDate,
+    /// Guided walk through the week's unmarked past days
+    Review,
+    /// Everything about one habit on a single screen
+    HabitDetail,
+    /// What an import would change, waiting for confirmation
+    ImportPreview,
+    /// Another running copy holds the data file's lock
+    LockWarning,
+    /// Every note written for the selected habit and day, oldest first
+    NotesHistory,
+    /// Find notes containing some text, across every habit and day
+    Search,
+    /// One habit's statuses across a whole year, a column per week
+    Heatmap,
+}
+
+/// One entry in the week's activity feed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityItem {
+    /// Day the habit was logged
+    pub date: NaiveDate,
+    /// Name of the habit
+    pub habit_name: String,
+    /// Recorded status (never Unmarked)
+    pub status: HabitStatus,
+    /// Latest note attached to the log, if any
+    pub note: Option<String>,
+}
+
+/// Format a report was exported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportKind {
+    /// Markdown report file
+    Markdown,
+    /// Plain-text report file, free of markdown syntax
+    PlainText,
+    /// Self-contained HTML report with a colored status table
+    Html,
+    /// Structured JSON for other tools (see the `exports` module)
+    Json,
+    /// One CSV row per day and habit, for spreadsheets
+    Csv,
+    /// Per-week, per-habit completion CSV covering the last `TREND_WEEKS` weeks
+    TrendCsv,
+    /// Per-month, per-habit completion CSV covering the last `TREND_MONTHS` months
+    MonthlyTrendCsv,
+}
+
+impl ExportKind {
+    /// Every export format, in cycling order
+    pub const ALL: [ExportKind; 7] = [
+        ExportKind::Markdown,
+        ExportKind::PlainText,
+        ExportKind::Html,
+        ExportKind::Json,
+        ExportKind::Csv,
+        ExportKind::TrendCsv,
+        ExportKind::MonthlyTrendCsv,
+    ];
+
+    /// Whether a file name looks like one of our exports, e.g. `habit-report-2025-10-13.md`
+    /// or `habit-report-2025-10-01-to-2025-10-31.md`
+    pub fn is_export_file_name(name: &str) -> bool {
+        let is_date = |text: &str| NaiveDate::parse_from_str(text, "%Y-%m-%d").is_ok();
+        Self::ALL.iter().any(|kind| {
+            name.strip_prefix(kind.file_prefix())
+                .and_then(|rest| rest.strip_prefix('-'))
+                .and_then(|rest| rest.strip_suffix(kind.extension()))
+                .and_then(|rest| rest.strip_suffix('.'))
+                .is_some_and(|dates| match dates.split_once("-to-") {
+                    Some((start, end)) => is_date(start) && is_date(end),
+                    None => is_date(dates),
+                })
+        })
+    }
+
+    /// Get a human-readable description
+    pub fn description(&self) -> &'static str {
+        match self {
+            ExportKind::Markdown => "markdown report",
+            ExportKind::PlainText => "plain-text report",
+            ExportKind::Html => "HTML report",
+            ExportKind::Json => "JSON export",
+            ExportKind::Csv => "CSV export",
+            ExportKind::TrendCsv => "trend CSV",
+            ExportKind::MonthlyTrendCsv => "monthly trend CSV",
+        }
+    }
+
+    /// File extension used when writing this kind of export
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportKind::Markdown => "md",
+            ExportKind::PlainText => "txt",
+            ExportKind::Html => "html",
+            ExportKind::Json => "json",
+            ExportKind::Csv | ExportKind::TrendCsv | ExportKind::MonthlyTrendCsv => "csv",
+        }
+    }
+
+    /// Start of the file name, before the week's date
+    pub fn file_prefix(&self) -> &'static str {
+        match self {
+            ExportKind::TrendCsv => "habit-trends",
+            ExportKind::MonthlyTrendCsv => "habit-monthly-trends",
+            _ => "habit-report",
+        }
+    }
+
+    /// Whether this format can cover a custom range (the trend CSVs always count back from the week)
+    pub fn takes_range(&self) -> bool {
+        !matches!(self, ExportKind::TrendCsv | ExportKind::MonthlyTrendCsv)
+    }
+
+    /// The export format after this one
+    pub fn next(&self) -> Self {
+        match self {
+            ExportKind::Markdown => ExportKind::PlainText,
+            ExportKind::PlainText => ExportKind::Html,
+            ExportKind::Html => ExportKind::Json,
+            ExportKind::Json => ExportKind::Csv,
+            ExportKind::Csv => ExportKind::TrendCsv,
+            ExportKind::TrendCsv => ExportKind::MonthlyTrendCsv,
+            ExportKind::MonthlyTrendCsv => ExportKind::Markdown,
+        }
+    }
+}
+
+/// Per-habit totals for the exported days
+struct HabitWeekTotals {
+    habit_id: Uuid,
+    name: String,
+    done: usize,
+    partial: usize,
+    skipped: usize,
+    unmarked: usize,
+    trackable: usize,
+}
+
+/// One habit's entry on an exported day
+struct ReportEntry {
+    habit_id: Uuid,
+    habit_name: String,
+    status: HabitStatus,
+    note: Option<String>,
+}
+
+/// Everything an export needs, gathered once and rendered per format
+struct WeekReport {
+    /// The viewed week, e.g. "Oct 13 - Oct 19, 2025", or the custom range
+    period: String,
+    /// Whether the report covers exactly one week (rather than a custom range)
+    weekly: bool,
+    generated: String,
+    reflection: Option<String>,
+    totals: Vec<HabitWeekTotals>,
+    days: Vec<(NaiveDate, Vec<ReportEntry>)>,
+}
+
+/// Result of the most recent export, shown on the confirmation screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastExport {
+    /// File written, if the export went to disk
+    pub path: Option<std::path::PathBuf>,
+    /// Format of the export
+    pub kind: ExportKind,
+    /// Message describing where the export went
+    pub message: String,
+}
+
+impl LastExport {
+    /// Describe an export of the given kind, written to `path` if it went to disk
+    /// `weekly` is false for an export of a custom range.
+    pub fn new(kind: ExportKind, path: Option<std::path::PathBuf>, weekly: bool) -> Self {
+        let what = if weekly { format!("weekly {}", kind.description()) } else { kind.description().to_string() };
+        let message = match &path {
+            Some(_) => format!("Your {} has been exported to:", what),
+            None => format!("Your {} is ready.", what),
+        };
+        Self { path, kind, message }
+    }
+}
+
+/// Progress through the week's unmarked (day, habit) pairs in review mode
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReviewMode {
+    /// Pairs still unmarked when the review started, earliest day first
+    pub pairs: Vec<(NaiveDate, Uuid)>,
+    /// Index of the pair being reviewed
+    pub position: usize,
+}
+
+/// One habit's settings and all-time stats, gathered for the detail screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HabitDetail {
+    /// Name as displayed, including any label
+    pub name: String,
+    /// Optional description
+    pub description: Option<String>,
+    /// Frequency, as described in habit management
+    pub frequency: String,
+    /// Date the habit was added
+    pub created_at: NaiveDate,
+    /// Tags, as entered
+    pub tags: Vec<String>,
+    /// Consecutive Done days up to today
+    pub current_streak: usize,
+    /// Longest run of consecutive Done days
+    pub best_streak: usize,
+    /// Percentage of trackable days done, from creation (or the first log) through today
+    pub completion_rate: usize,
+    /// Most recent day marked Done
+    pub last_done: Option<NaiveDate>,
+}
+
+/// Span of days the stats view aggregates over
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StatsRange {
+    /// The viewed week
+    #[default]
+    Week,
+    /// The calendar month containing the selected day
+    Month,
+    /// From the first to the last day with any activity
+    AllTime,
+}
+
+impl StatsRange {
+    /// The range after this one
+    pub fn next(&self) -> Self {
+        match self {
+            StatsRange::Week => StatsRange::Month,
+            StatsRange::Month => StatsRange::AllTime,
+            StatsRange::AllTime => StatsRange::Week,
+        }
+    }
+}
+
+/// First and last day of the calendar month containing `date`
+pub fn month_bounds(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let start = date.with_day(1).unwrap();
+    let next_month = if date.month() == 12 {
+        NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
+    };
+    (start, next_month.unwrap().pred_opt().unwrap())
+}
+
+/// A span of days for display, e.g. "Oct 01, 2025 to Oct 31, 2025"
+pub fn range_label(start: NaiveDate, end: NaiveDate) -> String {
+    format!("{} to {}", start.format("%b %d, %Y"), end.format("%b %d, %Y"))
+}
+
+/// Read a first and last day from text like "2025-10-01 to 2025-10-31" (the "to" is optional)
+/// Returns None unless there are exactly two dates, in order.
+pub fn parse_date_range(text: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let dates = text.split_whitespace()
+        .filter(|word| !word.eq_ignore_ascii_case("to"))
+        .map(|word| NaiveDate::parse_from_str(word, "%Y-%m-%d").ok())
+        .collect::<Option<Vec<_>>>()?;
+    match dates[..] {
+        [start, end] if start <= end => Some((start, end)),
+        _ => None,
+    }
+}
+
+/// Read a day to jump to: a date as YYYY-MM-DD, or days from `today` like "-7" or "+3"
+pub fn parse_date_input(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let text = text.trim();
+    if text.starts_with(['-', '+']) {
+        let days: i64 = text.parse().ok()?;
+        return today.checked_add_signed(chrono::Duration::try_days(days)?);
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()
+}
+
+/// Why a day's habit list is empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyDay {
+    /// No habits exist at all
+    NoHabits,
+    /// Habits exist, but none carry the tag being filtered on
+    NoneTagged,
+    /// Habits exist, but none are scheduled for this day
+    NoneScheduled,
+    /// Only unmarked habits are shown, and every habit is marked
+    AllMarked,
 }
 
 /// Habit management mode
@@ -31,6 +364,112 @@ pub enum HabitMgmtMode {
     AddHabit,
     /// Editing an existing habit
     EditHabit,
+    /// Editing the selected habit's label
+    EditLabel,
+    /// Editing the selected habit's comma-separated tags
+    EditTags,
+    /// Editing the selected habit's description
+    EditDescription,
+    /// Editing the text the selected habit's new notes start from
+    EditNoteTemplate,
+    /// Editing the selected habit's reminder time (HH:MM)
+    EditReminder,
+    /// Editing the selected habit's Done-per-week goal
+    EditGoal,
+    /// Typing the tag whose habits should be archived
+    ArchiveTag,
+    /// Confirming the archive of every habit with the typed tag
+    ConfirmArchiveTag,
+    /// Typing the file to write a full backup to
+    BackupPath,
+    /// Typing the backup file to restore from
+    RestorePath,
+    /// Typing a text file of habit names, one per line, to add
+    ImportListPath,
+    /// Picking the habit that `merge_source` should be merged into
+    MergeHabit,
+}
+
+/// Quote a CSV field if it contains a comma, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Escape the characters HTML gives meaning to, so text shows up as written
+fn html_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Delete all but the `keep` most recently written export files in `dir`
+/// Anything not named like one of our exports is left alone. Returns how many were removed.
+pub fn prune_exports(dir: &Path, keep: usize) -> Result<usize> {
+    let mut exports = Vec::new();
+    for entry in std::fs::read_dir(dir).context("Failed to read export directory")? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.file_type()?.is_file() && ExportKind::is_export_file_name(&name) {
+            exports.push((entry.metadata()?.modified()?, name, entry.path()));
+        }
+    }
+
+    // Newest first; names break ties so the order is stable
+    exports.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+    let mut removed = 0;
+    for (_, _, path) in exports.into_iter().skip(keep) {
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove old export {}", path.display()))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Default directory for exported reports (~/Documents/healing-habits-exports)
+fn default_export_dir() -> std::path::PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| std::env::current_dir().unwrap())
+        .join("Documents")
+        .join("healing-habits-exports")
+}
+
+/// Scroll position of a list, kept by the last draw so scrolling stays steady between frames
+/// and paging knows how many rows fit.
+#[derive(Debug, Default)]
+pub struct ListScroll {
+    offset: Cell<usize>,
+    rows: Cell<usize>,
+}
+
+impl ListScroll {
+    /// Index of the first item shown
+    pub fn offset(&self) -> usize {
+        self.offset.get()
+    }
+
+    /// Items that fit on screen at once (at least 1)
+    pub fn rows(&self) -> usize {
+        self.rows.get().max(1)
+    }
+
+    /// Record where the list was drawn from and how many rows it had
+    pub fn remember(&self, offset: usize, rows: usize) {
+        self.offset.set(offset);
+        self.rows.set(rows);
+    }
 }
 
 /// Application state
@@ -39,52 +478,192 @@ pub struct App {
     pub storage: Storage,
     /// Current week being viewed
     pub current_week: Week,
-    /// Selected day index (0 = Monday, 6 = Sunday)
+    /// Selected day index (0 = the week's first day, 6 = its last)
     pub selected_day_idx: usize,
     /// Selected habit index in the current day
     pub selected_habit_idx: usize,
+    /// Scroll position of the day view's habit list
+    pub habit_list_scroll: ListScroll,
     /// Current view
     pub view: AppView,
     /// Whether the app should quit
     pub should_quit: bool,
     /// Input buffer for note editing and habit management
     pub input_buffer: String,
+    /// Note editor cursor, as a count of characters before the end of `input_buffer`
+    /// (0 keeps the cursor at the end, however the buffer is replaced)
+    pub input_cursor_from_end: usize,
+    /// Selected entry while the note snippet picker is open
+    pub snippet_picker: Option<usize>,
     /// Habit management mode
     pub habit_mgmt_mode: HabitMgmtMode,
     /// Selected habit index in habit management view
     pub habit_mgmt_selected_idx: usize,
-    /// Last export file path
-    pub last_export_path: Option<std::path::PathBuf>,
+    /// Scroll position of the habit management list
+    pub habit_mgmt_scroll: ListScroll,
+    /// Habit being merged into another, while its target is picked
+    pub merge_source: Option<Uuid>,
+    /// Most recent export, shown on the confirmation screen
+    pub last_export: Option<LastExport>,
+    /// Directory export files are written to
+    pub export_dir: std::path::PathBuf,
+    /// Scroll offset (in lines) of the export preview
+    pub preview_scroll: u16,
+    /// Scroll offset (in lines) of the notes history
+    pub notes_scroll: u16,
+    /// Format used by the next export
+    pub export_kind: ExportKind,
+    /// First and last day to export instead of the viewed week
+    pub export_range: Option<(NaiveDate, NaiveDate)>,
+    /// The export as previewed, rebuilt when its format or range changes
+    pub export_preview: String,
     /// Staged status change (habit_id, date, new_status) that hasn't been saved yet
     pub staged_status: Option<(Uuid, NaiveDate, HabitStatus)>,
+    /// Selected habit row in the grid view
+    pub grid_row: usize,
+    /// Selected day column in the grid view (0 = the week's first day, 6 = its last)
+    pub grid_col: usize,
+    /// View to return to when note editing finishes
+    pub note_return_view: AppView,
+    /// One-off message shown in the footer until the next key press
+    pub flash_message: Option<String>,
+    /// Set when a habit was just marked Done and the bell should ring; see `take_bell`
+    bell_pending: bool,
+    /// Habit and day just marked Skipped, waiting for `prompt_skip_reason` to ask why
+    skip_reason_pending: Option<(Uuid, NaiveDate)>,
+    /// Habit and day the note editor is asking a skip reason for
+    pub skip_reason_for: Option<(Uuid, NaiveDate)>,
+    /// Selected entry in the filtered command palette list
+    pub palette_selected_idx: usize,
+    /// Notes matching the last search run, as (habit, day, note); None until Enter is pressed
+    pub search_results: Option<Vec<(Uuid, NaiveDate, String)>>,
+    /// Selected entry in the search results
+    pub search_selected_idx: usize,
+    /// Habit shown on the heatmap, as an index into the habit list
+    pub heatmap_habit_idx: usize,
+    /// Year shown on the heatmap
+    pub heatmap_year: i32,
+    /// Show every habit on every day, ignoring frequency scheduling
+    pub show_all_habits: bool,
+    /// Hide habits already marked for the selected day, leaving what's still to do
+    pub only_unmarked: bool,
+    /// List only As-needed habits on the main view
+    pub as_needed_panel: bool,
+    /// Only show habits carrying this tag on the main view and in stats
+    pub tag_filter: Option<String>,
+    /// State of the guided review of unmarked days
+    pub review: ReviewMode,
+    /// Habit shown on the detail screen
+    pub detail_habit: Option<Uuid>,
+    /// View to go back to when the detail screen closes
+    pub detail_return: AppView,
+    /// Week strip shows the selected habit's status instead of each day's aggregate
+    pub strip_follows_habit: bool,
+    /// Names of habits whose reminder has passed with today still unmarked, from `check_reminders`
+    pub reminders_due: Vec<String>,
+    /// Data waiting to be merged in, with what it would change
+    pub pending_import: Option<(HabitData, ImportDiff)>,
+    /// Our lock on the data file, released when the app is dropped
+    pub data_lock: Option<DataLock>,
+    /// PID and lock file of another running copy using the same data file
+    pub lock_warning: Option<(u32, PathBuf)>,
+    /// Import asked for while the lock warning is up, held until it's answered
+    pub deferred_import: Option<PathBuf>,
+    /// Span of days the stats view covers
+    pub stats_range: StatsRange,
+    /// Actions that can be undone, most recent last, with a short description
+    pub undo_stack: Vec<(&'static str, Change)>,
+    /// Undone actions that can be redone, most recently undone last
+    pub redo_stack: Vec<(&'static str, Change)>,
+    /// An action is being recorded, so nested actions join it instead of stacking
+    recording: bool,
+    /// Colors the views are drawn with, from theme.json next to the data file
+    pub theme: Theme,
+    /// Key bindings for the main, grid and stats views, from keys.json next to the data file
+    pub keymap: KeyMap,
 }
 
 impl App {
     /// Create a new App instance
     pub fn new(data_path: impl Into<std::path::PathBuf>) -> Result<Self> {
-        let mut storage = Storage::new(data_path);
+        Self::with_template(data_path, Template::default())
+    }
+
+    /// Create a new App instance, filling a brand-new data file with `template`'s habits
+    pub fn with_template(data_path: impl Into<std::path::PathBuf>, template: Template) -> Result<Self> {
+        let data_path = data_path.into();
+        let theme = Theme::load(&theme_path(&data_path));
+        let (keymap, keymap_warning) = KeyMap::load(&keymap_path(&data_path));
+        let mut storage = Storage::with_template(data_path, template);
         storage.load()?;
+        let vim_warning = keymap.vim_conflicts().filter(|_| storage.settings().vim_keys);
+        let warnings: Vec<String> = [storage.take_load_warning(), keymap_warning, vim_warning]
+            .into_iter()
+            .flatten()
+            .collect();
+        let warning = (!warnings.is_empty()).then(|| warnings.join(" - "));
 
-        // Start at current week and find today's index
-        let current_week = Week::current();
-        let today = Local::now().date_naive();
-        let selected_day_idx = current_week.days()
-            .iter()
-            .position(|&d| d == today)
-            .unwrap_or(0);
+        // Start where the last session left off, or at today
+        let today = storage.today();
+        let (current_week, selected_day_idx) = storage.last_view().unwrap_or_else(|| {
+            let week = storage.week_of(today);
+            (week, week.days().iter().position(|&d| d == today).unwrap_or(0))
+        });
 
         Ok(Self {
             storage,
             current_week,
             selected_day_idx,
             selected_habit_idx: 0,
+            habit_list_scroll: ListScroll::default(),
             view: AppView::Main,
             should_quit: false,
             input_buffer: String::new(),
+            input_cursor_from_end: 0,
+            snippet_picker: None,
             habit_mgmt_mode: HabitMgmtMode::List,
+            merge_source: None,
             habit_mgmt_selected_idx: 0,
-            last_export_path: None,
+            habit_mgmt_scroll: ListScroll::default(),
+            last_export: None,
+            export_dir: default_export_dir(),
+            preview_scroll: 0,
+            notes_scroll: 0,
+            export_kind: ExportKind::Markdown,
+            export_range: None,
+            export_preview: String::new(),
             staged_status: None,
+            grid_row: 0,
+            grid_col: selected_day_idx,
+            note_return_view: AppView::Main,
+            flash_message: warning,
+            bell_pending: false,
+            skip_reason_pending: None,
+            skip_reason_for: None,
+            palette_selected_idx: 0,
+            search_results: None,
+            search_selected_idx: 0,
+            heatmap_habit_idx: 0,
+            heatmap_year: 0,
+            show_all_habits: false,
+            as_needed_panel: false,
+            only_unmarked: false,
+            tag_filter: None,
+            review: ReviewMode::default(),
+            detail_habit: None,
+            detail_return: AppView::Main,
+            pending_import: None,
+            data_lock: None,
+            lock_warning: None,
+            deferred_import: None,
+            strip_follows_habit: false,
+            reminders_due: Vec::new(),
+            stats_range: StatsRange::Week,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            recording: false,
+            theme,
+            keymap,
         })
     }
 
@@ -98,22 +677,133 @@ impl App {
         self.storage.habits()
     }
 
-    /// Get habits that should be shown for a given date, respecting frequency
-    pub fn habits_for_date(&self, date: NaiveDate) -> Vec<&crate::models::Habit> {
+    /// Get habits in the active tag filter (every habit when no tag is chosen)
+    pub fn filtered_habits(&self) -> Vec<&crate::models::Habit> {
         self.storage.habits().into_iter()
-            .filter(|habit| self.should_show_habit(habit, date))
+            .filter(|habit| self.tag_filter.as_deref().is_none_or(|tag| habit.has_tag(tag)))
+            .collect()
+    }
+
+    /// Every tag in use, sorted, with differently-cased duplicates merged
+    pub fn all_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.storage.habits().iter().flat_map(|habit| &habit.tags) {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.clone());
+            }
+        }
+        tags.sort_by_key(|tag| tag.to_lowercase());
+        tags
+    }
+
+    /// Step the tag filter to the next tag in use, then back to all habits
+    pub fn cycle_tag_filter(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        let tags = self.all_tags();
+        let next = match &self.tag_filter {
+            None => 0,
+            Some(current) => tags.iter().position(|t| t.eq_ignore_ascii_case(current)).map_or(0, |idx| idx + 1),
+        };
+        self.tag_filter = tags.get(next).cloned();
+        self.selected_habit_idx = 0;
+        Ok(())
+    }
+
+    /// Get habits that should be shown for a given date, respecting frequency,
+    /// the tag filter and the unmarked-only filter
+    pub fn habits_for_date(&self, date: NaiveDate) -> Vec<&crate::models::Habit> {
+        self.scheduled_habits(date).into_iter()
+            .filter(|habit| !self.only_unmarked || self.is_left_to_do(habit.id, date))
             .collect()
     }
 
+    /// Get habits in the tag filter scheduled for a date (or all of them when showing all)
+    fn scheduled_habits(&self, date: NaiveDate) -> Vec<&crate::models::Habit> {
+        self.filtered_habits().into_iter()
+            .filter(|habit| !self.as_needed_panel || habit.frequency == Frequency::AsNeeded)
+            .filter(|habit| self.show_all_habits || self.should_show_habit(habit, date))
+            .collect()
+    }
+
+    /// Whether a habit is still unmarked on a day
+    /// A habit being cycled stays listed until the change is saved.
+    fn is_left_to_do(&self, habit_id: Uuid, date: NaiveDate) -> bool {
+        let staged = self.staged_status.is_some_and(|(id, day, _)| id == habit_id && day == date);
+        let saved = self.storage.get_log(habit_id, date).map_or(HabitStatus::Unmarked, |log| log.status);
+        staged || saved == HabitStatus::Unmarked
+    }
+
+    /// Toggle hiding habits already marked for the selected day
+    pub fn toggle_only_unmarked(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.only_unmarked = !self.only_unmarked;
+        self.clamp_habit_selection();
+        Ok(())
+    }
+
+    /// Keep the selected habit inside the (possibly shrunken) list for the selected day
+    fn clamp_habit_selection(&mut self) {
+        let count = self.habits_for_date(self.selected_date()).len();
+        self.selected_habit_idx = self.selected_habit_idx.min(count.saturating_sub(1));
+    }
+
+    /// Explain why no habits are shown for a date, or None if some are
+    pub fn empty_day_reason(&self, date: NaiveDate) -> Option<EmptyDay> {
+        if self.storage.habits().is_empty() {
+            Some(EmptyDay::NoHabits)
+        } else if self.filtered_habits().is_empty() {
+            Some(EmptyDay::NoneTagged)
+        } else if self.scheduled_habits(date).is_empty() {
+            Some(EmptyDay::NoneScheduled)
+        } else if self.habits_for_date(date).is_empty() {
+            Some(EmptyDay::AllMarked)
+        } else {
+            None
+        }
+    }
+
+    /// Toggle showing unscheduled habits on every day
+    pub fn toggle_show_all_habits(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.show_all_habits = !self.show_all_habits;
+        self.selected_habit_idx = 0;
+        Ok(())
+    }
+
+    /// Toggle listing only As-needed habits (their own panel in strict frequency mode)
+    pub fn toggle_as_needed_panel(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.as_needed_panel = !self.as_needed_panel;
+        self.selected_habit_idx = 0;
+        Ok(())
+    }
+
+    /// Toggle strict frequency mode
+    pub fn toggle_strict_frequency(&mut self) -> Result<()> {
+        let mut settings = self.storage.settings().clone();
+        settings.strict_frequency = !settings.strict_frequency;
+        self.storage.update_settings(settings)?;
+        self.clamp_habit_selection();
+        Ok(())
+    }
+
+    /// Day Weekly habits are listed on in strict frequency mode
+    pub fn weekly_day(&self) -> Weekday {
+        let settings = self.storage.settings();
+        settings.weekly_day.unwrap_or_else(|| settings.week_start.weekday())
+    }
+
     /// Check if a habit should be shown on a given date based on its frequency
-    fn should_show_habit(&self, habit: &crate::models::Habit, _date: NaiveDate) -> bool {
-        // All habits show every day regardless of frequency
-        // Frequency is informational only (tells you how often to do it)
-        match habit.frequency {
-            Frequency::Daily => true,
-            Frequency::Weekly => true,
-            Frequency::AsNeeded => true,
+    /// Weekly and As-needed habits show every day unless strict frequency mode is on.
+    fn should_show_habit(&self, habit: &crate::models::Habit, date: NaiveDate) -> bool {
+        if self.storage.settings().strict_frequency {
+            match habit.frequency {
+                Frequency::Weekly if date.weekday() != self.weekly_day() => return false,
+                Frequency::AsNeeded if !self.as_needed_panel => return false,
+                _ => {}
+            }
         }
+        habit.is_scheduled_on(date)
     }
 
     /// Get the currently selected habit (from the filtered list for the selected date)
@@ -130,7 +820,7 @@ impl App {
         if self.selected_day_idx > 0 {
             self.selected_day_idx -= 1;
         } else {
-            // Wrap to previous week's Sunday
+            // Wrap to the last day of the previous week
             self.current_week = self.current_week.prev();
             self.selected_day_idx = 6;
         }
@@ -143,11 +833,16 @@ impl App {
     pub fn next_day(&mut self) -> Result<()> {
         // Commit any staged changes before navigating
         self.commit_staged_status()?;
+        // Stop at the last day a log can be kept for
+        if let Err(e) = validate_log_date(self.selected_date() + chrono::Duration::days(1), self.today()) {
+            self.flash(e.to_string());
+            return Ok(());
+        }
 
         if self.selected_day_idx < 6 {
             self.selected_day_idx += 1;
         } else {
-            // Wrap to next week's Monday
+            // Wrap to the first day of next week
             self.current_week = self.current_week.next();
             self.selected_day_idx = 0;
         }
@@ -156,6 +851,40 @@ impl App {
         Ok(())
     }
 
+    /// Select a day of the viewed week (e.g. clicked in the week strip)
+    pub fn select_day(&mut self, day_idx: usize) -> Result<()> {
+        self.commit_staged_status()?;
+        if day_idx < 7 && day_idx != self.selected_day_idx {
+            self.selected_day_idx = day_idx;
+            self.selected_habit_idx = 0;
+        }
+        Ok(())
+    }
+
+    /// Select a habit in the day's list (e.g. clicked)
+    pub fn select_habit(&mut self, habit_idx: usize) -> Result<()> {
+        self.commit_staged_status()?;
+        if habit_idx < self.habits_for_date(self.selected_date()).len() {
+            self.selected_habit_idx = habit_idx;
+        }
+        Ok(())
+    }
+
+    /// Move the selection up a screenful of the day's habits (stopping at the first)
+    pub fn page_up_habits(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.selected_habit_idx = self.selected_habit_idx.saturating_sub(self.habit_list_scroll.rows());
+        Ok(())
+    }
+
+    /// Move the selection down a screenful of the day's habits (stopping at the last)
+    pub fn page_down_habits(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        let last = self.habits_for_date(self.selected_date()).len().saturating_sub(1);
+        self.selected_habit_idx = (self.selected_habit_idx + self.habit_list_scroll.rows()).min(last);
+        Ok(())
+    }
+
     /// Navigate to the previous habit
     pub fn prev_habit(&mut self) -> Result<()> {
         // Commit any staged changes before navigating
@@ -191,108 +920,420 @@ impl App {
     }
 
     /// Navigate to the previous week
-    pub fn prev_week(&mut self) {
+    /// Keeps the selected weekday; like day navigation, commits staged changes first
+    pub fn prev_week(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
         self.current_week = self.current_week.prev();
+        self.selected_habit_idx = 0;
+        Ok(())
     }
 
     /// Navigate to the next week
-    pub fn next_week(&mut self) {
+    /// Keeps the selected weekday; like day navigation, commits staged changes first
+    pub fn next_week(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        if let Err(e) = validate_log_date(self.selected_date() + chrono::Duration::days(7), self.today()) {
+            self.flash(e.to_string());
+            return Ok(());
+        }
         self.current_week = self.current_week.next();
+        self.selected_habit_idx = 0;
+        Ok(())
+    }
+
+    /// Use `clock` for "now" from here on, for the app and its storage alike
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.storage.set_clock(clock);
+    }
+
+    /// Current local date and time, according to the app's clock
+    pub fn now(&self) -> NaiveDateTime {
+        self.storage.now()
+    }
+
+    /// The clock's current time in the local time zone, for stamping notes
+    pub fn now_local(&self) -> DateTime<Local> {
+        let now = self.now();
+        Local.from_local_datetime(&now).earliest()
+            .unwrap_or_else(|| Local.from_utc_datetime(&now))
+    }
+
+    /// Today's date, according to the app's clock
+    pub fn today(&self) -> NaiveDate {
+        self.now().date()
+    }
+
+    /// Position of today in the viewed week, if the week includes it
+    pub fn today_index(&self) -> Option<usize> {
+        let today = self.today();
+        self.current_week.days().iter().position(|&d| d == today)
     }
 
     /// Go to the current week and today
     pub fn go_to_today(&mut self) {
-        self.current_week = Week::current();
-        let today = Local::now().date_naive();
+        self.current_week = self.storage.week_of(self.today());
+        self.selected_day_idx = self.today_index().unwrap_or(0);
+    }
+
+    /// Show the week containing a date and select that day
+    pub fn jump_to_date(&mut self, date: NaiveDate) -> Result<()> {
+        validate_log_date(date, self.today())?;
+        self.current_week = self.storage.week_of(date);
         self.selected_day_idx = self.current_week.days()
             .iter()
-            .position(|&d| d == today)
+            .position(|&d| d == date)
             .unwrap_or(0);
+        self.selected_habit_idx = 0;
+        Ok(())
     }
 
-    /// Toggle the status of the selected habit for the selected date (stages change, doesn't save)
-    pub fn toggle_habit_status(&mut self) {
-        if let Some(habit) = self.selected_habit() {
-            let date = self.selected_date();
-            let current_status = self.get_habit_status(habit.id, date);
-            let new_status = current_status.cycle();
+    /// Start typing a day to jump to
+    pub fn start_go_to_date(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.clear_input();
+        self.view = AppView::GoToDate;
+        Ok(())
+    }
 
-            // Stage the change instead of saving immediately
-            self.staged_status = Some((habit.id, date, new_status));
+    /// Jump to the typed day, keeping the prompt open if it can't be read
+    pub fn save_go_to_date(&mut self) {
+        let Some(date) = parse_date_input(&self.input_buffer, self.today()) else {
+            self.flash("Enter a date as YYYY-MM-DD, or days from today like -7");
+            return;
+        };
+        if let Err(e) = self.jump_to_date(date) {
+            self.flash(e.to_string());
+            return;
         }
+        self.clear_input();
+        self.view = AppView::Main;
     }
 
-    /// Commit any staged status changes to storage
-    pub fn commit_staged_status(&mut self) -> Result<()> {
-        if let Some((habit_id, date, status)) = self.staged_status.take() {
-            // Get old status before updating
-            let old_status = self.storage.get_log(habit_id, date)
-                .map(|log| log.status)
-                .unwrap_or(HabitStatus::Unmarked);
-
-            self.storage.update_log_status(habit_id, date, status)?;
+    /// Leave the date prompt where we were
+    pub fn cancel_go_to_date(&mut self) {
+        self.clear_input();
+        self.view = AppView::Main;
+    }
 
-            // For Weekly habits, handle propagation
-            if let Some(habit) = self.storage.get_habit(habit_id) {
-                if habit.frequency == Frequency::Weekly {
-                    if status == HabitStatus::Done {
-                        // Marking as Done: auto-fill previous days as Skipped
-                        self.propagate_weekly_habit_status(habit_id, date)?;
-                    } else if old_status == HabitStatus::Done {
-                        // Changed from Done to something else: clear auto-propagation
-                        self.clear_weekly_habit_propagation(habit_id, date)?;
-                    }
-                }
-            }
+    /// Jump to the earliest day with any activity
+    pub fn jump_to_first_activity(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        match self.storage.earliest_log_date() {
+            Some(date) => self.jump_to_date(date)?,
+            None => self.flash("No activity recorded yet"),
         }
         Ok(())
     }
 
-    /// Propagate Weekly habit status across the week
-    /// When marked Done on a day, mark previous days as Skipped (if unmarked)
-    /// Future days remain unmarked (no entry needed)
-    fn propagate_weekly_habit_status(&mut self, habit_id: Uuid, done_date: NaiveDate) -> Result<()> {
-        let week = Week::containing(done_date);
-        let days = week.days();
-
-        for &day in &days {
-            if day < done_date {
-                // Previous days in the week: mark as Skipped (if unmarked)
-                let current_status = self.storage.get_log(habit_id, day)
-                    .map(|log| log.status)
-                    .unwrap_or(HabitStatus::Unmarked);
-
-                if current_status == HabitStatus::Unmarked {
-                    self.storage.update_log_status(habit_id, day, HabitStatus::Skipped)?;
-                }
-            }
-            // Future days (day > done_date) remain unmarked
-            // day == done_date is already marked as Done by the initial update
+    /// Jump to the most recent day with any activity
+    pub fn jump_to_last_activity(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        match self.storage.most_recent_log_date() {
+            Some(date) => self.jump_to_date(date)?,
+            None => self.flash("No activity recorded yet"),
         }
-
         Ok(())
     }
 
-    /// Clear Weekly habit auto-propagation
-    /// When changing from Done to Skipped/Unmarked, leave previous entries as-is
-    /// (Don't clear them since we can't tell if they were manual or auto-filled)
-    fn clear_weekly_habit_propagation(&mut self, _habit_id: Uuid, _changed_date: NaiveDate) -> Result<()> {
-        // No-op: leave previous entries unchanged
-        // If Monday was Skipped (either manually or auto), it stays Skipped
-        Ok(())
+    /// Toggle the status of the selected habit for the selected date (stages change, doesn't save)
+    pub fn toggle_habit_status(&mut self) {
+        self.stage_next_status(HabitStatus::cycle);
     }
 
-    /// Cancel any staged status changes without saving
-    pub fn cancel_staged_status(&mut self) {
-        self.staged_status = None;
+    /// Step the selected habit's status backwards (staged, not saved yet)
+    pub fn toggle_habit_status_back(&mut self) {
+        self.stage_next_status(HabitStatus::cycle_back);
     }
 
-    /// Get the status for a habit on a specific date (checks staged changes first)
-    pub fn get_habit_status(&self, habit_id: Uuid, date: NaiveDate) -> HabitStatus {
-        // Check if there's a staged change for this habit and date
-        if let Some((staged_id, staged_date, staged_status)) = self.staged_status {
-            if staged_id == habit_id && staged_date == date {
-                return staged_status;
+    /// Stage the status produced by `next` for the selected habit and date
+    fn stage_next_status(&mut self, next: fn(&HabitStatus) -> HabitStatus) {
+        if let Some(habit_id) = self.selected_habit().map(|h| h.id) {
+            let date = self.selected_date();
+            if let Err(e) = validate_log_date(date, self.today()) {
+                self.flash(e.to_string());
+                return;
+            }
+            let current_status = self.get_habit_status(habit_id, date);
+            let new_status = next(&current_status);
+
+            // Stage the change instead of saving immediately
+            self.staged_status = Some((habit_id, date, new_status));
+            if self.needs_skip_note(habit_id, date, new_status) {
+                self.start_note_input();
+            }
+        }
+    }
+
+    /// Set the selected habit's status for the selected date and save it right away
+    pub fn set_habit_status(&mut self, status: HabitStatus) -> Result<()> {
+        self.commit_staged_status()?;
+        if let Some(habit_id) = self.selected_habit().map(|h| h.id) {
+            let date = self.selected_date();
+            if let Err(e) = validate_log_date(date, self.today()) {
+                self.flash(e.to_string());
+                return Ok(());
+            }
+            self.staged_status = Some((habit_id, date, status));
+            if self.needs_skip_note(habit_id, date, status) {
+                // Wait for the required note before saving
+                self.start_note_input();
+            } else {
+                self.commit_staged_status()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Set every habit listed for the selected day to a status as one undoable change
+    /// Habits marked N/A that day keep it, and Skipped leaves alone any habit still needing a note.
+    pub fn mark_all(&mut self, status: HabitStatus) -> Result<()> {
+        self.commit_staged_status()?;
+        let date = self.selected_date();
+        if let Err(e) = validate_log_date(date, self.today()) {
+            self.flash(e.to_string());
+            return Ok(());
+        }
+        let habit_ids: Vec<Uuid> = self.habits_for_date(date).iter()
+            .filter(|habit| self.get_habit_status(habit.id, date) != HabitStatus::NotApplicable)
+            .map(|habit| habit.id)
+            .collect();
+        let needing_note = habit_ids.iter()
+            .filter(|&&id| self.needs_skip_note(id, date, status))
+            .count();
+
+        self.record("mark all", |app| {
+            for habit_id in habit_ids {
+                app.staged_status = Some((habit_id, date, status));
+                app.apply_staged_status()?;
+            }
+            Ok(())
+        })?;
+
+        if needing_note > 0 {
+            self.flash(format!("{} habit(s) need a note before they can be skipped", needing_note));
+        }
+        Ok(())
+    }
+
+    /// Start a vacation on the selected day, or cancel the one it's already in
+    pub fn toggle_vacation(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        let date = self.selected_date();
+        self.record("vacation", |app| {
+            match app.storage.remove_vacation(date)? {
+                Some((start, end)) => app.flash(format!(
+                    "Removed vacation {} to {}", start.format("%b %d"), end.format("%b %d")
+                )),
+                None => {
+                    app.storage.add_vacation(date, date)?;
+                    app.flash(format!("Vacation starts {}", date.format("%b %d")));
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// End the most recent vacation on the selected day
+    pub fn end_vacation_here(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        let date = self.selected_date();
+        self.record("vacation", |app| {
+            match app.storage.end_vacation(date)? {
+                Some((start, end)) => app.flash(format!(
+                    "Vacation {} to {}", start.format("%b %d"), end.format("%b %d")
+                )),
+                None => app.flash("No vacation starts on or before this day"),
+            }
+            Ok(())
+        })
+    }
+
+    /// Check whether saving this status is blocked until a note is written
+    pub fn needs_skip_note(&self, habit_id: Uuid, date: NaiveDate, status: HabitStatus) -> bool {
+        status == HabitStatus::Skipped
+            && self.storage.settings().require_skip_note
+            && self.storage.get_log(habit_id, date)
+                .and_then(|log| log.latest_note())
+                .is_none()
+    }
+
+    /// Toggle whether a note is required before saving a Skipped status
+    pub fn toggle_require_skip_note(&mut self) -> Result<()> {
+        let mut settings = self.storage.settings().clone();
+        settings.require_skip_note = !settings.require_skip_note;
+        self.storage.update_settings(settings)
+    }
+
+    /// Switch weeks between starting on Monday and on Sunday, keeping the selected day
+    pub fn toggle_week_start(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        let date = self.selected_date();
+        let mut settings = self.storage.settings().clone();
+        settings.week_start = settings.week_start.toggle();
+        self.storage.update_settings(settings)?;
+        self.current_week = self.storage.week_of(date);
+        self.selected_day_idx = self.current_week.days()
+            .iter()
+            .position(|&d| d == date)
+            .unwrap_or(0);
+        Ok(())
+    }
+
+    /// Toggle whether Skipped days keep a streak going instead of breaking it
+    pub fn toggle_skips_keep_streak(&mut self) -> Result<()> {
+        let mut settings = self.storage.settings().clone();
+        settings.skips_keep_streak = !settings.skips_keep_streak;
+        self.storage.update_settings(settings)
+    }
+
+    /// Toggle vim-style h/j/k/l movement on the main view
+    /// Turning it on warns about any custom keys it takes over.
+    pub fn toggle_vim_keys(&mut self) -> Result<()> {
+        let mut settings = self.storage.settings().clone();
+        settings.vim_keys = !settings.vim_keys;
+        if let Some(conflicts) = self.keymap.vim_conflicts().filter(|_| settings.vim_keys) {
+            self.flash(conflicts);
+        }
+        self.storage.update_settings(settings)
+    }
+
+    /// Toggle the week-strip legend under the main view
+    pub fn toggle_legend(&mut self) -> Result<()> {
+        let mut settings = self.storage.settings().clone();
+        settings.hide_legend = !settings.hide_legend;
+        self.storage.update_settings(settings)
+    }
+
+    /// Commit any staged status changes to storage
+    /// Marking one habit Done queues the bell if it's turned on, and marking one Skipped from
+    /// the main view queues the skip-reason prompt if that is; bulk changes do neither.
+    pub fn commit_staged_status(&mut self) -> Result<()> {
+        let changing = self.staged_status.filter(|&(habit_id, date, status)| {
+            self.storage.get_log(habit_id, date).map_or(HabitStatus::Unmarked, |log| log.status) != status
+        });
+        self.record("status change", Self::apply_staged_status)?;
+        if let Some((habit_id, date, status)) = changing {
+            let saved = self.storage.get_log(habit_id, date).is_some_and(|log| log.status == status);
+            let settings = self.storage.settings();
+            if saved && status == HabitStatus::Done {
+                self.bell_pending |= settings.bell_on_complete;
+            }
+            if saved && status == HabitStatus::Skipped && settings.ask_skip_reason && self.view == AppView::Main {
+                self.skip_reason_pending = Some((habit_id, date));
+            }
+        }
+        Ok(())
+    }
+
+    /// Open the note editor to ask why, if a habit was just marked Skipped on the main view
+    /// Called once a key press is handled, so the prompt follows whatever else the key did.
+    pub fn prompt_skip_reason(&mut self) {
+        let Some(target) = self.skip_reason_pending.take() else {
+            return;
+        };
+        if self.view == AppView::Main {
+            self.skip_reason_for = Some(target);
+            self.start_note_input();
+        }
+    }
+
+    /// Toggle asking for a reason after a habit is marked Skipped
+    pub fn toggle_ask_skip_reason(&mut self) -> Result<()> {
+        let mut settings = self.storage.settings().clone();
+        settings.ask_skip_reason = !settings.ask_skip_reason;
+        self.storage.update_settings(settings)
+    }
+
+    /// Whether the bell should ring for what just happened, clearing the request
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.bell_pending)
+    }
+
+    /// Toggle the bell when a habit is marked Done
+    pub fn toggle_bell_on_complete(&mut self) -> Result<()> {
+        let mut settings = self.storage.settings().clone();
+        settings.bell_on_complete = !settings.bell_on_complete;
+        self.storage.update_settings(settings)
+    }
+
+    /// Write the staged status (and any weekly auto-fill) to storage
+    fn apply_staged_status(&mut self) -> Result<()> {
+        if let Some((habit_id, date, status)) = self.staged_status.take() {
+            // Reject a Skipped without a note when the policy requires one (status reverts)
+            if self.needs_skip_note(habit_id, date, status) {
+                return Ok(());
+            }
+
+            // Get old status before updating
+            let old_status = self.storage.get_log(habit_id, date)
+                .map(|log| log.status)
+                .unwrap_or(HabitStatus::Unmarked);
+
+            self.storage.update_log_status(habit_id, date, status)?;
+
+            // For Weekly habits, handle propagation
+            if let Some(habit) = self.storage.get_habit(habit_id) {
+                if habit.frequency == Frequency::Weekly && habit.auto_fill_week {
+                    if status == HabitStatus::Done {
+                        // Marking as Done: auto-fill previous days as Skipped
+                        self.propagate_weekly_habit_status(habit_id, date)?;
+                    } else if old_status == HabitStatus::Done {
+                        // Changed from Done to something else: clear auto-propagation
+                        self.clear_weekly_habit_propagation(habit_id, date)?;
+                    }
+                }
+            }
+            // The saved habit may have dropped out of the unmarked-only list
+            self.clamp_habit_selection();
+        }
+        Ok(())
+    }
+
+    /// Propagate Weekly habit status across the week
+    /// When marked Done on a day, mark previous days as Skipped (if unmarked)
+    /// Future days remain unmarked (no entry needed)
+    fn propagate_weekly_habit_status(&mut self, habit_id: Uuid, done_date: NaiveDate) -> Result<()> {
+        let week = self.storage.week_of(done_date);
+        let days = week.days();
+
+        for &day in &days {
+            if day < done_date {
+                // Previous days in the week: mark as Skipped (if unmarked)
+                let current_status = self.storage.get_log(habit_id, day)
+                    .map(|log| log.status)
+                    .unwrap_or(HabitStatus::Unmarked);
+
+                if current_status == HabitStatus::Unmarked {
+                    self.storage.update_log_status(habit_id, day, HabitStatus::Skipped)?;
+                }
+            }
+            // Future days (day > done_date) remain unmarked
+            // day == done_date is already marked as Done by the initial update
+        }
+
+        Ok(())
+    }
+
+    /// Clear Weekly habit auto-propagation
+    /// When changing from Done to Skipped/Unmarked, leave previous entries as-is
+    /// (Don't clear them since we can't tell if they were manual or auto-filled)
+    fn clear_weekly_habit_propagation(&mut self, _habit_id: Uuid, _changed_date: NaiveDate) -> Result<()> {
+        // No-op: leave previous entries unchanged
+        // If Monday was Skipped (either manually or auto), it stays Skipped
+        Ok(())
+    }
+
+    /// Cancel any staged status changes without saving
+    pub fn cancel_staged_status(&mut self) {
+        self.staged_status = None;
+    }
+
+    /// Get the status for a habit on a specific date (checks staged changes first)
+    pub fn get_habit_status(&self, habit_id: Uuid, date: NaiveDate) -> HabitStatus {
+        // Check if there's a staged change for this habit and date
+        if let Some((staged_id, staged_date, staged_status)) = self.staged_status {
+            if staged_id == habit_id && staged_date == date {
+                return staged_status;
             }
         }
 
@@ -302,363 +1343,4548 @@ impl App {
             .unwrap_or(HabitStatus::Unmarked)
     }
 
-    /// Get the note for the selected habit and date
+    /// Get the (habit, date) a note applies to: the staged change if any, else the selection
+    pub fn note_target(&self) -> Option<(Uuid, NaiveDate)> {
+        if self.skip_reason_for.is_some() {
+            return self.skip_reason_for;
+        }
+        if let Some((habit_id, date, _)) = self.staged_status {
+            return Some((habit_id, date));
+        }
+        self.selected_habit().map(|habit| (habit.id, self.selected_date()))
+    }
+
+    /// The template a new note starts from, unless it's a skip reason
+    fn note_template(&self) -> Option<String> {
+        if self.skip_reason_for.is_some() {
+            return None;
+        }
+        let (habit_id, _) = self.note_target()?;
+        self.storage.get_habit(habit_id)?.note_template.clone()
+    }
+
+    /// Get the most recent note for the selected habit and date
     pub fn get_current_note(&self) -> Option<String> {
-        if let Some(habit) = self.selected_habit() {
-            let date = self.selected_date();
-            self.storage.get_log(habit.id, date)
-                .and_then(|log| log.note.clone())
+        let (habit_id, date) = self.note_target()?;
+        self.storage.get_log(habit_id, date)
+            .and_then(|log| log.latest_note())
+            .map(str::to_string)
+    }
+
+    /// Every note for the selected habit and date, oldest first
+    pub fn current_notes(&self) -> Vec<(DateTime<Local>, String)> {
+        self.note_target()
+            .and_then(|(habit_id, date)| self.storage.get_log(habit_id, date))
+            .map(|log| log.notes.clone())
+            .unwrap_or_default()
+    }
+
+    /// Add a note, stamped with the current time, to the selected habit and date
+    pub fn add_current_note(&mut self, note: String) -> Result<()> {
+        let at = self.now_local();
+        if let Some((habit_id, date)) = self.note_target() {
+            self.storage.add_log_note(habit_id, date, at, note)?;
+        }
+        Ok(())
+    }
+
+    /// Open the scrollable list of every note for the selected habit and date
+    pub fn open_notes_history(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        if self.selected_habit().is_some() {
+            self.notes_scroll = 0;
+            self.view = AppView::NotesHistory;
+        }
+        Ok(())
+    }
+
+    /// Scroll the notes history up one line
+    pub fn notes_scroll_up(&mut self) {
+        self.notes_scroll = self.notes_scroll.saturating_sub(1);
+    }
+
+    /// Scroll the notes history down one line
+    pub fn notes_scroll_down(&mut self) {
+        let line_count: usize = self.current_notes().iter()
+            .map(|(_, text)| text.lines().count() + 2)
+            .sum();
+        if (self.notes_scroll as usize) + 1 < line_count {
+            self.notes_scroll += 1;
+        }
+    }
+
+    /// Get the current week's logged activity, newest day first, then by habit order
+    pub fn week_activity(&self) -> Vec<ActivityItem> {
+        let mut items = Vec::new();
+        for &date in self.current_week.days().iter().rev() {
+            for habit in self.habits() {
+                if let Some(log) = self.storage.get_log(habit.id, date) {
+                    if log.status == HabitStatus::Unmarked {
+                        continue;
+                    }
+                    items.push(ActivityItem {
+                        date,
+                        habit_name: habit.display_name(),
+                        status: log.status,
+                        note: log.latest_note().map(str::to_string),
+                    });
+                }
+            }
+        }
+        items
+    }
+
+    /// Get a 7-character strip of a habit's statuses for the week ending on `end`, oldest first
+    /// ✓ = Done, ◐ = Partial, ✗ = Skipped, ∅ = N/A, · = Unmarked
+    pub fn recent_status_strip(&self, habit_id: Uuid, end: NaiveDate) -> String {
+        (0..7).rev()
+            .map(|offset| end - chrono::Duration::days(offset))
+            .map(|date| match self.get_habit_status(habit_id, date) {
+                HabitStatus::Done => '✓',
+                HabitStatus::Partial => '◐',
+                HabitStatus::Skipped => '✗',
+                HabitStatus::NotApplicable => '∅',
+                HabitStatus::Unmarked => '·',
+            })
+            .collect()
+    }
+
+    /// Number of days in a week that count toward a habit's completion rate
+    /// Stats, exports and the needs-attention hint all use this as "out of how many".
+    pub fn trackable_days(&self, habit_id: Uuid, week: Week) -> usize {
+        self.storage.trackable_days(habit_id, week.start, week.end())
+    }
+
+    /// Switch the stats view to the next range (week, month, all time)
+    pub fn cycle_stats_range(&mut self) {
+        self.stats_range = self.stats_range.next();
+    }
+
+    /// First and last day the stats view covers, or None for all time with no activity yet
+    pub fn stats_bounds(&self) -> Option<(NaiveDate, NaiveDate)> {
+        match self.stats_range {
+            StatsRange::Week => Some((self.current_week.start, self.current_week.end())),
+            StatsRange::Month => Some(month_bounds(self.selected_date())),
+            StatsRange::AllTime => self.storage.earliest_log_date()
+                .zip(self.storage.most_recent_log_date()),
+        }
+    }
+
+    /// Heading for the stats view's range, e.g. "Monthly Stats - October 2025"
+    pub fn stats_title(&self) -> String {
+        let title = match (self.stats_range, self.stats_bounds()) {
+            (StatsRange::Week, _) => format!("Weekly Stats - {}", self.current_week.format()),
+            (StatsRange::Month, Some((start, _))) => format!("Monthly Stats - {}", start.format("%B %Y")),
+            (StatsRange::AllTime, Some((start, end))) => format!(
+                "All-time Stats - {} to {}",
+                start.format("%b %d, %Y"),
+                end.format("%b %d, %Y")
+            ),
+            (_, None) => "All-time Stats".to_string(),
+        };
+        match &self.tag_filter {
+            Some(tag) => format!("{} - #{}", title, tag),
+            None => title,
+        }
+    }
+
+    /// Get the habit with the lowest completion rate this week, if any is below 100%
+    pub fn needs_attention(&self) -> Option<&crate::models::Habit> {
+        let ranked = self.storage.ranked_habits_by_completion(self.current_week.start, self.current_week.end());
+        let (habit_id, rate) = ranked.first()?;
+        if *rate >= 100 {
+            return None;
+        }
+        self.storage.get_habit(*habit_id)
+    }
+
+    /// Get the day status symbol for a specific day
+    /// ✓ = All habits done, ✗ = Some skipped, ~ = Partial, space = Unmarked/future
+    /// Habits marked N/A that day are left out, and so are As-needed habits, which are optional,
+    /// unless they are all that's listed (the As-needed panel).
+    pub fn get_day_status(&self, day_idx: usize) -> char {
+        let date = self.current_week.day(day_idx).unwrap();
+        let mut habits = self.scheduled_habits(date);
+        if habits.iter().any(|habit| habit.frequency != Frequency::AsNeeded) {
+            habits.retain(|habit| habit.frequency != Frequency::AsNeeded);
+        }
+
+        if habits.is_empty() {
+            return ' ';
+        }
+
+        let mut done_count = 0;
+        let mut skipped_count = 0;
+        let mut unmarked_count = 0;
+        let mut applicable = habits.len();
+        let vacation = self.storage.is_vacation(date);
+
+        for habit in &habits {
+            match self.get_habit_status(habit.id, date) {
+                // Vacation days only count what actually got done
+                HabitStatus::Skipped | HabitStatus::Unmarked if vacation => applicable -= 1,
+                HabitStatus::Done => done_count += 1,
+                HabitStatus::Partial => {}
+                HabitStatus::Skipped => skipped_count += 1,
+                HabitStatus::NotApplicable => applicable -= 1,
+                HabitStatus::Unmarked => unmarked_count += 1,
+            }
+        }
+
+        if vacation && applicable == 0 {
+            '·'
+        // If all unmarked or future date, show space
+        } else if unmarked_count == applicable || date > self.today() {
+            ' '
+        } else if done_count == applicable {
+            '✓'
+        } else if skipped_count > 0 {
+            '✗'
         } else {
-            None
+            '~'
+        }
+    }
+
+    /// Get the selected habit's status symbol for a day in the week strip
+    pub fn get_habit_day_status(&self, habit_id: Uuid, day_idx: usize) -> char {
+        let date = self.current_week.day(day_idx).unwrap();
+        let Some(habit) = self.storage.get_habit(habit_id) else {
+            return ' ';
+        };
+
+        match self.get_habit_status(habit_id, date) {
+            HabitStatus::Done => '✓',
+            HabitStatus::Partial => '◐',
+            HabitStatus::Skipped => '✗',
+            HabitStatus::NotApplicable => '∅',
+            HabitStatus::Unmarked if self.storage.is_vacation(date) => '·',
+            HabitStatus::Unmarked if !habit.is_scheduled_on(date) => '-',
+            HabitStatus::Unmarked => ' ',
+        }
+    }
+
+    /// Symbol for a day in the week strip, following the selected habit if enabled
+    pub fn week_strip_status(&self, day_idx: usize) -> char {
+        match self.selected_habit().filter(|_| self.strip_follows_habit) {
+            Some(habit) => self.get_habit_day_status(habit.id, day_idx),
+            None => self.get_day_status(day_idx),
+        }
+    }
+
+    /// Progress toward a habit's weekly goal in the viewed week, as (Done so far, goal)
+    pub fn weekly_goal_progress(&self, habit_id: Uuid) -> Option<(usize, u32)> {
+        let goal = self.storage.get_habit(habit_id)?.weekly_goal?;
+        let done = self.storage.get_stats(self.current_week.start, self.current_week.end())
+            .get(&habit_id)
+            .map_or(0, |stats| stats.done);
+        Some((done, goal))
+    }
+
+    /// Whether a habit has any (non-blank) note on a day
+    pub fn habit_has_note(&self, habit_id: Uuid, date: NaiveDate) -> bool {
+        self.storage.get_log(habit_id, date).is_some_and(|log| log.latest_note().is_some())
+    }
+
+    /// Weekly habits carried over into the week containing `as_of`: not Done at all the week
+    /// before (unless it was all vacation), and not Done yet this week (up to `as_of`)
+    pub fn overdue_weekly_habits(&self, as_of: NaiveDate) -> Vec<Uuid> {
+        let week = self.storage.week_of(as_of);
+        let prior = week.prev();
+        let done_between = |habit_id, start: NaiveDate, end: NaiveDate| {
+            self.storage.statuses_in_range(habit_id, start, end).iter()
+                .any(|&(_, status)| status == HabitStatus::Done)
+        };
+        // A week spent entirely on vacation doesn't carry anything over
+        let away = prior.start.iter_days().take(7).all(|date| self.storage.is_vacation(date));
+        self.habits().into_iter()
+            .filter(|_| !away)
+            .filter(|habit| habit.frequency == Frequency::Weekly && habit.created_at <= prior.end())
+            .filter(|habit| !done_between(habit.id, prior.start, prior.end()))
+            .filter(|habit| !done_between(habit.id, week.start, as_of))
+            .map(|habit| habit.id)
+            .collect()
+    }
+
+    /// Habits whose reminder time has passed today while today is still unmarked
+    pub fn due_reminders(&self) -> Vec<&crate::models::Habit> {
+        let now = self.now();
+        let today = now.date();
+        self.habits().into_iter()
+            .filter(|habit| habit.remind_at.is_some_and(|time| time <= now.time()))
+            .filter(|habit| habit.created_at <= today && habit.is_scheduled_on(today))
+            .filter(|habit| self.get_habit_status(habit.id, today) == HabitStatus::Unmarked)
+            .collect()
+    }
+
+    /// Refresh the reminder banner from the clock
+    pub fn check_reminders(&mut self) {
+        self.reminders_due = self.due_reminders().iter().map(|h| h.display_name()).collect();
+    }
+
+    /// Toggle whether the week strip follows the selected habit
+    pub fn toggle_strip_follows_habit(&mut self) {
+        self.strip_follows_habit = !self.strip_follows_habit;
+    }
+
+    /// Whether anything (a staged status or pending storage write) hasn't reached disk
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.staged_status.is_some() || self.storage.is_dirty()
+    }
+
+    /// Show a failed save in the flash message; the change is kept and retried on the next save
+    pub fn report_save_error(&mut self) {
+        if let Some(err) = self.storage.take_save_error() {
+            self.flash(format!("Save failed — will retry ({})", err));
         }
     }
 
-    /// Update the note for the selected habit and date
-    pub fn update_current_note(&mut self, note: Option<String>) -> Result<()> {
-        if let Some(habit) = self.selected_habit() {
-            let date = self.selected_date();
-            self.storage.update_log_note(habit.id, date, note)?;
+    /// Change the current view, writing out anything pending from the one being left
+    pub fn set_view(&mut self, view: AppView) {
+        self.storage.flush_or_report();
+        self.view = view;
+    }
+
+    /// Show a one-off message in the footer
+    pub fn flash(&mut self, message: impl Into<String>) {
+        self.flash_message = Some(message.into());
+    }
+
+    /// Request the app to quit, remembering the week and day on screen for next time
+    /// Nothing is recorded while another running copy holds the data file.
+    pub fn quit(&mut self) -> Result<()> {
+        if self.view != AppView::LockWarning {
+            self.storage.set_last_view(self.current_week, self.selected_day_idx)?;
+        }
+        self.should_quit = true;
+        Ok(())
+    }
+
+    /// Enter note editing mode
+    pub fn start_note_input(&mut self) {
+        // Notes are added to the day's history, so start empty unless a draft was left for it,
+        // or from the habit's template if the day has no notes yet
+        self.clear_input();
+        if let Some(draft) = self.storage.load_note_draft()
+            .filter(|d| Some((d.habit_id, d.date)) == self.note_target())
+        {
+            self.input_buffer = draft.text;
+            self.flash("Restored your unsaved note");
+        } else if let Some(template) = self.note_template().filter(|_| self.current_notes().is_empty()) {
+            self.input_buffer = template;
+        }
+        self.input_cursor_from_end = 0;
+        self.snippet_picker = None;
+        if self.view != AppView::NoteInput {
+            self.note_return_view = self.view;
+        }
+        self.view = AppView::NoteInput;
+    }
+
+    /// Add the note (and save any staged status waiting on it) and return to the previous view
+    /// A blank note adds nothing.
+    pub fn save_note_input(&mut self) -> Result<()> {
+        let mut note = self.input_buffer.trim().to_string();
+        // A template saved untouched says nothing
+        if self.note_template().as_deref() == Some(note.as_str()) {
+            note.clear();
+        }
+        if self.skip_reason_for.is_some() && !note.is_empty() {
+            note = format!("Skipped: {}", note);
+        }
+        self.record("note edit", |app| {
+            if !note.is_empty() {
+                app.add_current_note(note)?;
+            }
+            app.commit_staged_status()
+        })?;
+        self.discard_note_draft()?;
+        self.skip_reason_for = None;
+        self.clear_input();
+        self.view = self.note_return_view;
+        Ok(())
+    }
+
+    /// Cancel note editing and return to the previous view
+    pub fn cancel_note_input(&mut self) {
+        // A status waiting on this note reverts
+        self.cancel_staged_status();
+        // Nothing useful to report if the draft can't be removed; it's replaced next time
+        let _ = self.discard_note_draft();
+        self.skip_reason_for = None;
+        self.clear_input();
+        self.snippet_picker = None;
+        self.view = self.note_return_view;
+    }
+
+    /// Enter week reflection editing mode
+    pub fn start_week_note_input(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.input_buffer = self.storage.get_week_note(&self.current_week)
+            .unwrap_or_default()
+            .to_string();
+        self.input_cursor_from_end = 0;
+        self.view = AppView::WeekNoteInput;
+        Ok(())
+    }
+
+    /// Save the week reflection and return to main view
+    pub fn save_week_note_input(&mut self) -> Result<()> {
+        let note = if self.input_buffer.trim().is_empty() {
+            None
+        } else {
+            Some(self.input_buffer.trim().to_string())
+        };
+        let week = self.current_week;
+        self.record("week note", |app| app.storage.set_week_note(&week, note))?;
+        self.clear_input();
+        self.view = AppView::Main;
+        Ok(())
+    }
+
+    /// Cancel week reflection editing and return to main view
+    pub fn cancel_week_note_input(&mut self) {
+        self.clear_input();
+        self.view = AppView::Main;
+    }
+
+    /// Empty the text input and put the cursor back at its end
+    pub fn clear_input(&mut self) {
+        self.input_buffer.clear();
+        self.input_cursor_from_end = 0;
+    }
+
+    /// Handle character input for note editing
+    pub fn input_char(&mut self, c: char) {
+        let at = self.input_cursor_byte();
+        self.input_buffer.insert(at, c);
+        self.autosave_note_draft();
+    }
+
+    /// Handle backspace in note editing (deletes the character before the cursor)
+    pub fn input_backspace(&mut self) {
+        let at = self.input_cursor_byte();
+        if let Some((start, _)) = self.input_buffer[..at].char_indices().next_back() {
+            self.input_buffer.remove(start);
+        }
+        self.autosave_note_draft();
+    }
+
+    /// Handle Delete in text input (deletes the character under the cursor)
+    pub fn input_delete(&mut self) {
+        let at = self.input_cursor_byte();
+        if at < self.input_buffer.len() {
+            self.input_buffer.remove(at);
+            // The cursor stays put, so it's one character closer to the end
+            self.input_cursor_from_end = self.input_cursor_from_end.saturating_sub(1);
+        }
+        self.autosave_note_draft();
+    }
+
+    /// Remove the saved draft if it belongs to the note being edited
+    /// A draft for another habit or day is kept until that note is opened again.
+    fn discard_note_draft(&self) -> Result<()> {
+        let matches = self.storage.load_note_draft()
+            .is_some_and(|d| Some((d.habit_id, d.date)) == self.note_target());
+        if matches {
+            self.storage.clear_note_draft()?;
+        }
+        Ok(())
+    }
+
+    /// Keep a copy of the note being typed so a crash doesn't lose it
+    /// Best effort: a failed write only means there's no draft to restore.
+    fn autosave_note_draft(&self) {
+        if self.view != AppView::NoteInput {
+            return;
+        }
+        if let Some((habit_id, date)) = self.note_target() {
+            let draft = NoteDraft { habit_id, date, text: self.input_buffer.clone() };
+            let _ = self.storage.save_note_draft(&draft);
+        }
+    }
+
+    /// Cursor position in characters from the start of the input
+    pub fn input_cursor(&self) -> usize {
+        let len = self.input_buffer.chars().count();
+        len - self.input_cursor_from_end.min(len)
+    }
+
+    /// Cursor position as a byte offset into `input_buffer`
+    fn input_cursor_byte(&self) -> usize {
+        self.input_buffer.char_indices()
+            .nth(self.input_cursor())
+            .map_or(self.input_buffer.len(), |(idx, _)| idx)
+    }
+
+    /// Move the input cursor one character left
+    pub fn input_cursor_left(&mut self) {
+        let len = self.input_buffer.chars().count();
+        self.input_cursor_from_end = (self.input_cursor_from_end + 1).min(len);
+    }
+
+    /// Move the input cursor one character right
+    pub fn input_cursor_right(&mut self) {
+        self.input_cursor_from_end = self.input_cursor_from_end.saturating_sub(1);
+    }
+
+    /// Move the input cursor to the start of the input
+    pub fn input_cursor_home(&mut self) {
+        self.input_cursor_from_end = self.input_buffer.chars().count();
+    }
+
+    /// Move the input cursor to the end of the input
+    pub fn input_cursor_end(&mut self) {
+        self.input_cursor_from_end = 0;
+    }
+
+    /// Insert text at the input cursor, leaving the cursor after it
+    pub fn insert_at_cursor(&mut self, text: &str) {
+        let at = self.input_cursor_byte();
+        self.input_buffer.insert_str(at, text);
+        self.autosave_note_draft();
+    }
+
+    // Snippet Methods
+
+    /// Save the note being written as a reusable snippet
+    pub fn save_note_as_snippet(&mut self) -> Result<()> {
+        let note = self.input_buffer.trim().to_string();
+        if note.is_empty() {
+            self.flash("Write something first to save it as a snippet");
+            return Ok(());
+        }
+        let mut settings = self.storage.settings().clone();
+        if settings.snippets.contains(&note) {
+            self.flash("That snippet is already saved");
+            return Ok(());
+        }
+        settings.snippets.push(note);
+        self.storage.update_settings(settings)?;
+        self.flash("Saved as a snippet");
+        Ok(())
+    }
+
+    /// Open the snippet picker in the note editor
+    pub fn open_snippet_picker(&mut self) {
+        if self.storage.settings().snippets.is_empty() {
+            self.flash("No snippets yet - press Ctrl+S to save this note as one");
+        } else {
+            self.snippet_picker = Some(0);
+        }
+    }
+
+    /// Select the previous snippet in the picker
+    pub fn snippet_picker_prev(&mut self) {
+        if let Some(idx) = self.snippet_picker {
+            self.snippet_picker = Some(idx.saturating_sub(1));
+        }
+    }
+
+    /// Select the next snippet in the picker
+    pub fn snippet_picker_next(&mut self) {
+        let count = self.storage.settings().snippets.len();
+        if let Some(idx) = self.snippet_picker {
+            if idx + 1 < count {
+                self.snippet_picker = Some(idx + 1);
+            }
+        }
+    }
+
+    /// Insert the selected snippet at the cursor and close the picker
+    pub fn insert_selected_snippet(&mut self) {
+        if let Some(snippet) = self.snippet_picker
+            .and_then(|idx| self.storage.settings().snippets.get(idx))
+            .cloned()
+        {
+            self.insert_at_cursor(&snippet);
+        }
+        self.snippet_picker = None;
+    }
+
+    /// Close the snippet picker without inserting anything
+    pub fn close_snippet_picker(&mut self) {
+        self.snippet_picker = None;
+    }
+
+    // Command Palette Methods
+
+    /// Open the command palette with an empty query
+    pub fn open_command_palette(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.clear_input();
+        self.palette_selected_idx = 0;
+        self.view = AppView::CommandPalette;
+        Ok(())
+    }
+
+    /// Get the commands matching the current palette query
+    pub fn palette_matches(&self) -> Vec<crate::palette::Command> {
+        crate::palette::filter_commands(&self.input_buffer)
+    }
+
+    /// Add a character to the palette query
+    pub fn palette_input_char(&mut self, c: char) {
+        self.input_char(c);
+        self.palette_selected_idx = 0;
+    }
+
+    /// Remove the last character from the palette query
+    pub fn palette_input_backspace(&mut self) {
+        self.input_backspace();
+        self.palette_selected_idx = 0;
+    }
+
+    /// Select the previous palette entry
+    pub fn palette_prev(&mut self) {
+        self.palette_selected_idx = self.palette_selected_idx.saturating_sub(1);
+    }
+
+    /// Select the next palette entry
+    pub fn palette_next(&mut self) {
+        if self.palette_selected_idx + 1 < self.palette_matches().len() {
+            self.palette_selected_idx += 1;
+        }
+    }
+
+    /// Run the selected palette entry
+    pub fn run_palette_selection(&mut self) -> Result<()> {
+        let selected = self.palette_matches().get(self.palette_selected_idx).copied();
+        self.clear_input();
+        match selected {
+            Some(cmd) => crate::palette::execute(self, cmd),
+            None => {
+                self.view = AppView::Main;
+                Ok(())
+            }
+        }
+    }
+
+    /// Close the palette without running anything
+    pub fn close_command_palette(&mut self) {
+        self.clear_input();
+        self.view = AppView::Main;
+    }
+
+    // Search Methods
+
+    /// Open note search with an empty query
+    pub fn open_search(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.clear_input();
+        self.search_results = None;
+        self.search_selected_idx = 0;
+        self.view = AppView::Search;
+        Ok(())
+    }
+
+    /// Add a character to the search query; results wait for the next Enter
+    pub fn search_input_char(&mut self, c: char) {
+        self.input_char(c);
+        self.search_results = None;
+    }
+
+    /// Remove the character before the cursor from the search query
+    pub fn search_input_backspace(&mut self) {
+        self.input_backspace();
+        self.search_results = None;
+    }
+
+    /// Run the search, or once results are showing, go to the selected one
+    pub fn confirm_search(&mut self) -> Result<()> {
+        match &self.search_results {
+            None => {
+                self.search_results = Some(self.storage.search_notes(&self.input_buffer));
+                self.search_selected_idx = 0;
+                Ok(())
+            }
+            Some(results) => {
+                let Some(&(habit_id, date, _)) = results.get(self.search_selected_idx) else {
+                    return Ok(());
+                };
+                self.close_search();
+                self.show_habit_day(habit_id, date)
+            }
+        }
+    }
+
+    /// Select the previous search result
+    pub fn search_prev(&mut self) {
+        self.search_selected_idx = self.search_selected_idx.saturating_sub(1);
+    }
+
+    /// Select the next search result
+    pub fn search_next(&mut self) {
+        let count = self.search_results.as_ref().map_or(0, Vec::len);
+        if self.search_selected_idx + 1 < count {
+            self.search_selected_idx += 1;
+        }
+    }
+
+    /// Leave search for the main view
+    pub fn close_search(&mut self) {
+        self.clear_input();
+        self.search_results = None;
+        self.view = AppView::Main;
+    }
+
+    /// Select a habit on a day in the main view, lifting any filter that would hide it
+    fn show_habit_day(&mut self, habit_id: Uuid, date: NaiveDate) -> Result<()> {
+        self.jump_to_date(date)?;
+        let listed = |app: &Self| app.habits_for_date(date).iter().position(|h| h.id == habit_id);
+        if listed(self).is_none() {
+            self.tag_filter = None;
+            self.only_unmarked = false;
+            self.show_all_habits = true;
+        }
+        self.selected_habit_idx = listed(self).unwrap_or(0);
+        Ok(())
+    }
+
+    // Heatmap Methods
+
+    /// Show the selected habit's year, starting with the year of the viewed week
+    pub fn open_heatmap(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        let habit_id = self.selected_habit().map(|h| h.id);
+        self.heatmap_habit_idx = self.habits().iter()
+            .position(|h| Some(h.id) == habit_id)
+            .unwrap_or(0);
+        self.heatmap_year = self.selected_date().year();
+        self.view = AppView::Heatmap;
+        Ok(())
+    }
+
+    /// Show the previous habit on the heatmap, wrapping around
+    pub fn heatmap_prev_habit(&mut self) {
+        let count = self.habits().len();
+        if count > 0 {
+            self.heatmap_habit_idx = (self.heatmap_habit_idx + count - 1) % count;
+        }
+    }
+
+    /// Show the next habit on the heatmap, wrapping around
+    pub fn heatmap_next_habit(&mut self) {
+        let count = self.habits().len();
+        if count > 0 {
+            self.heatmap_habit_idx = (self.heatmap_habit_idx + 1) % count;
+        }
+    }
+
+    /// Show another year on the heatmap, from 1970 (the earliest logs can be) to next year
+    pub fn heatmap_change_year(&mut self, delta: i32) {
+        let year = self.heatmap_year + delta;
+        if (1970..=self.today().year() + 1).contains(&year) {
+            self.heatmap_year = year;
+        }
+    }
+
+    /// The heatmap's weeks, one array per column in weekday order
+    /// Days outside the year, or still to come, are None.
+    pub fn heatmap_weeks(&self) -> Vec<[Option<HabitStatus>; 7]> {
+        let Some(habit) = self.habits().get(self.heatmap_habit_idx).copied() else {
+            return Vec::new();
+        };
+        let (Some(first), Some(last)) = (
+            NaiveDate::from_ymd_opt(self.heatmap_year, 1, 1),
+            NaiveDate::from_ymd_opt(self.heatmap_year, 12, 31),
+        ) else {
+            return Vec::new();
+        };
+        let statuses: HashMap<NaiveDate, HabitStatus> = self.storage
+            .statuses_in_range(habit.id, first, last)
+            .into_iter()
+            .collect();
+
+        let mut weeks = Vec::new();
+        let mut week = self.storage.week_of(first);
+        while week.start <= last {
+            weeks.push(week.days().map(|date| {
+                statuses.get(&date).copied().filter(|_| !self.is_future(date))
+            }));
+            week = week.next();
+        }
+        weeks
+    }
+
+    // Review Methods
+
+    /// Every (day, habit) pair in the current week, up to today, that's scheduled but unmarked
+    /// Ordered by day, then habit order; days before a habit was created are left out.
+    pub fn review_pairs(&self) -> Vec<(NaiveDate, Uuid)> {
+        let today = self.today();
+        let habits = self.habits();
+        self.current_week.days().into_iter()
+            .filter(|&date| date <= today)
+            .flat_map(|date| {
+                habits.iter()
+                    .filter(move |h| date >= h.created_at && h.is_scheduled_on(date))
+                    .map(move |h| (date, h.id))
+            })
+            .filter(|&(date, habit_id)| self.get_habit_status(habit_id, date) == HabitStatus::Unmarked)
+            .collect()
+    }
+
+    /// Start reviewing the week's unmarked past days
+    pub fn start_review(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        let pairs = self.review_pairs();
+        if pairs.is_empty() {
+            self.flash("Nothing to review - every past day this week is marked");
+            return Ok(());
+        }
+        self.review = ReviewMode { pairs, position: 0 };
+        self.view = AppView::Review;
+        Ok(())
+    }
+
+    /// The pair under review, passing over any that got marked in the meantime
+    /// (e.g. by a weekly habit's auto-skip)
+    pub fn review_current(&self) -> Option<(NaiveDate, Uuid)> {
+        self.review.pairs[self.review.position.min(self.review.pairs.len())..]
+            .iter()
+            .copied()
+            .find(|&(date, habit_id)| self.get_habit_status(habit_id, date) == HabitStatus::Unmarked)
+    }
+
+    /// Record a status for the pair under review and move on
+    pub fn review_mark(&mut self, status: HabitStatus) -> Result<()> {
+        let Some((date, habit_id)) = self.review_current() else {
+            return self.finish_review();
+        };
+        self.advance_review(date, habit_id);
+        self.staged_status = Some((habit_id, date, status));
+        if self.needs_skip_note(habit_id, date, status) {
+            // The note editor returns here once the note is saved
+            self.start_note_input();
+            return Ok(());
+        }
+        self.commit_staged_status()?;
+        if self.review_current().is_none() {
+            self.finish_review()?;
+        }
+        Ok(())
+    }
+
+    /// Leave the pair under review unmarked and move on
+    pub fn review_skip_pair(&mut self) -> Result<()> {
+        match self.review_current() {
+            Some((date, habit_id)) => {
+                self.advance_review(date, habit_id);
+                if self.review_current().is_none() {
+                    self.finish_review()?;
+                }
+                Ok(())
+            }
+            None => self.finish_review(),
+        }
+    }
+
+    /// Point the review just past the given pair
+    fn advance_review(&mut self, date: NaiveDate, habit_id: Uuid) {
+        if let Some(idx) = self.review.pairs.iter().position(|&p| p == (date, habit_id)) {
+            self.review.position = idx + 1;
+        }
+    }
+
+    /// Leave review mode
+    pub fn finish_review(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        let left = self.review_pairs().len();
+        self.review = ReviewMode::default();
+        self.view = AppView::Main;
+        if left == 0 {
+            self.flash("Review complete - every past day this week is marked");
+        } else {
+            self.flash(format!("Review finished - {} left unmarked", left));
+        }
+        Ok(())
+    }
+
+    // Habit Management Methods
+
+    /// Enter habit management view
+    pub fn enter_habit_management(&mut self) {
+        self.habit_mgmt_mode = HabitMgmtMode::List;
+        self.habit_mgmt_selected_idx = 0;
+        self.view = AppView::HabitManagement;
+    }
+
+    /// Gather a habit's settings and all-time stats
+    pub fn habit_detail(&self, habit_id: Uuid) -> Option<HabitDetail> {
+        let habit = self.storage.get_habit(habit_id)?;
+        let today = self.today();
+        let logs = self.storage.logs_for_habit(habit_id);
+
+        let start = logs.first().map_or(habit.created_at, |log| log.date.min(habit.created_at));
+        let done = self.storage.get_stats(start, today).get(&habit_id).map_or(0, |s| s.done);
+        let trackable = self.storage.trackable_days(habit_id, start, today);
+
+        Some(HabitDetail {
+            name: habit.display_name(),
+            description: habit.description.clone(),
+            frequency: habit.frequency.description(),
+            created_at: habit.created_at,
+            tags: habit.tags.clone(),
+            current_streak: self.storage.current_streak(habit_id, today),
+            best_streak: self.storage.longest_streak(habit_id),
+            completion_rate: round_rate(done, trackable),
+            last_done: logs.iter().rev()
+                .find(|log| log.status == HabitStatus::Done)
+                .map(|log| log.date),
+        })
+    }
+
+    /// Open the detail screen for the habit selected in the current view
+    pub fn open_habit_detail(&mut self) -> Result<()> {
+        let habit_id = match self.view {
+            AppView::HabitManagement => {
+                self.habits().get(self.habit_mgmt_selected_idx).map(|h| h.id)
+            }
+            _ => {
+                self.commit_staged_status()?;
+                self.selected_habit().map(|h| h.id)
+            }
+        };
+        if let Some(habit_id) = habit_id {
+            self.detail_habit = Some(habit_id);
+            self.detail_return = self.view;
+            self.view = AppView::HabitDetail;
+        }
+        Ok(())
+    }
+
+    /// Leave the detail screen for wherever it was opened from
+    pub fn close_habit_detail(&mut self) {
+        self.detail_habit = None;
+        self.view = self.detail_return;
+    }
+
+    // Lock Methods
+
+    /// Ask what to do because another running copy (`pid`) holds the lock at `path`
+    pub fn warn_data_locked(&mut self, pid: u32, path: PathBuf) {
+        self.lock_warning = Some((pid, path));
+        self.view = AppView::LockWarning;
+    }
+
+    /// Take the data file's lock, then deal with a file that couldn't be read
+    /// Moving an unreadable file aside waits for the lock so another copy's file is never touched.
+    pub fn claim_data_lock(&mut self, path: PathBuf) -> Result<()> {
+        self.data_lock = Some(DataLock::claim(path)?);
+        self.storage.set_aside_unreadable()?;
+        if let Some(warning) = self.storage.take_load_warning() {
+            self.flash(warning);
+        }
+        Ok(())
+    }
+
+    /// Carry on without writing anything, leaving the other copy's lock alone
+    /// A held-back import is dropped, since nothing it changed could be saved.
+    pub fn open_read_only(&mut self) {
+        self.lock_warning = None;
+        self.storage.set_read_only(true);
+        match (self.storage.unreadable(), self.deferred_import.take()) {
+            (Some(err), _) => self.flash(format!("Read-only: the data file couldn't be read ({}) and was left as it is", err)),
+            (None, Some(path)) => self.flash(format!("Read-only: changes won't be saved, so {} wasn't imported", path.display())),
+            (None, None) => self.flash("Read-only: changes won't be saved"),
+        }
+        self.view = AppView::Main;
+    }
+
+    /// Take the lock over from the other copy and carry on normally,
+    /// including with an import held back until now
+    pub fn force_take_lock(&mut self) -> Result<()> {
+        self.view = AppView::Main;
+        if let Some((_, path)) = self.lock_warning.take() {
+            self.claim_data_lock(path)?;
+        }
+        if let Some(path) = self.deferred_import.take() {
+            if let Err(err) = self.start_import(&path) {
+                self.flash(format!("Import failed: {}", err));
+            }
+        }
+        Ok(())
+    }
+
+    // Import Methods
+
+    /// Read another data file and show what merging it would change
+    /// While another copy's lock is unanswered the import waits (see `force_take_lock`).
+    pub fn start_import(&mut self, path: &Path) -> Result<()> {
+        if self.lock_warning.is_some() {
+            self.deferred_import = Some(path.to_path_buf());
+            return Ok(());
+        }
+        let imported = read_data_file(path, self.today())?;
+        let diff = self.storage.preview_import(&imported);
+        // A file with less than we have still matters if it's used to replace everything
+        if diff.is_empty() && self.storage.matches(&imported) {
+            self.flash("Nothing to import - that file matches your data");
+            return Ok(());
+        }
+        self.pending_import = Some((imported, diff));
+        self.view = AppView::ImportPreview;
+        Ok(())
+    }
+
+    /// Merge the previewed import and return to the main view
+    pub fn confirm_import(&mut self) -> Result<()> {
+        if let Some((imported, _)) = self.pending_import.take() {
+            self.storage.apply_import(imported)?;
+            self.clear_history();
+            self.flash("Import merged");
+        }
+        self.view = AppView::Main;
+        Ok(())
+    }
+
+    /// Replace all data with the previewed import and return to the main view
+    pub fn confirm_import_replace(&mut self) -> Result<()> {
+        if let Some((imported, _)) = self.pending_import.take() {
+            let date = self.selected_date();
+            self.storage.replace_data(imported)?;
+            self.clear_history();
+            // The restored settings may start weeks on a different day
+            self.current_week = self.storage.week_of(date);
+            self.selected_day_idx = self.current_week.days()
+                .iter()
+                .position(|&d| d == date)
+                .unwrap_or(0);
+            self.selected_habit_idx = 0;
+            self.habit_mgmt_selected_idx = 0;
+            self.flash("All data replaced from the import");
+        }
+        self.view = AppView::Main;
+        Ok(())
+    }
+
+    /// Drop the previewed import without changing anything
+    pub fn cancel_import(&mut self) {
+        self.pending_import = None;
+        self.view = AppView::Main;
+    }
+
+    /// Start adding a new habit
+    pub fn start_add_habit(&mut self) {
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::AddHabit;
+    }
+
+    /// Start editing the selected habit
+    pub fn start_edit_habit(&mut self) {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            self.input_buffer = habit.name.clone();
+            self.input_cursor_from_end = 0;
+            self.habit_mgmt_mode = HabitMgmtMode::EditHabit;
+        }
+    }
+
+    /// Start editing the selected habit's label
+    pub fn start_edit_label(&mut self) {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            self.input_buffer = habit.label.clone().unwrap_or_default();
+            self.input_cursor_from_end = 0;
+            self.habit_mgmt_mode = HabitMgmtMode::EditLabel;
+        }
+    }
+
+    /// Start editing the selected habit's description
+    pub fn start_edit_description(&mut self) {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            self.input_buffer = habit.description.clone().unwrap_or_default();
+            self.input_cursor_from_end = 0;
+            self.habit_mgmt_mode = HabitMgmtMode::EditDescription;
+        }
+    }
+
+    /// Save the edited description (an empty description removes it)
+    pub fn save_edited_description(&mut self) -> Result<()> {
+        if let Some(habit_id) = self.habits().get(self.habit_mgmt_selected_idx).map(|h| h.id) {
+            let description = Some(self.input_buffer.trim().to_string()).filter(|d| !d.is_empty());
+            self.record("description edit", |app| app.storage.update_habit_description(habit_id, description))?;
+        }
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::List;
+        Ok(())
+    }
+
+    /// Start editing the selected habit's note template
+    pub fn start_edit_note_template(&mut self) {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            self.input_buffer = habit.note_template.clone().unwrap_or_default();
+            self.input_cursor_from_end = 0;
+            self.habit_mgmt_mode = HabitMgmtMode::EditNoteTemplate;
+        }
+    }
+
+    /// Save the edited note template (an empty template removes it)
+    pub fn save_edited_note_template(&mut self) -> Result<()> {
+        if let Some(habit_id) = self.habits().get(self.habit_mgmt_selected_idx).map(|h| h.id) {
+            let template = Some(self.input_buffer.trim().to_string()).filter(|t| !t.is_empty());
+            self.record("note template edit", |app| app.storage.update_habit_note_template(habit_id, template))?;
+        }
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::List;
+        Ok(())
+    }
+
+    /// Start editing the selected habit's reminder time
+    pub fn start_edit_reminder(&mut self) {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            self.input_buffer = habit.remind_at
+                .map(|time| time.format("%H:%M").to_string())
+                .unwrap_or_default();
+            self.input_cursor_from_end = 0;
+            self.habit_mgmt_mode = HabitMgmtMode::EditReminder;
+        }
+    }
+
+    /// Save the edited reminder time (empty removes it); an unreadable time keeps the editor open
+    pub fn save_edited_reminder(&mut self) -> Result<()> {
+        let text = self.input_buffer.trim();
+        let remind_at = if text.is_empty() {
+            None
+        } else {
+            match chrono::NaiveTime::parse_from_str(text, "%H:%M") {
+                Ok(time) => Some(time),
+                Err(_) => {
+                    self.flash(format!("'{}' isn't a time - use HH:MM, e.g. 20:30", text));
+                    return Ok(());
+                }
+            }
+        };
+        if let Some(habit_id) = self.habits().get(self.habit_mgmt_selected_idx).map(|h| h.id) {
+            self.record("reminder edit", |app| app.storage.update_habit_reminder(habit_id, remind_at))?;
+        }
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::List;
+        Ok(())
+    }
+
+    /// Start editing the selected habit's weekly goal
+    pub fn start_edit_goal(&mut self) {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            self.input_buffer = habit.weekly_goal.map(|goal| goal.to_string()).unwrap_or_default();
+            self.input_cursor_from_end = 0;
+            self.habit_mgmt_mode = HabitMgmtMode::EditGoal;
+        }
+    }
+
+    /// Save the edited weekly goal (empty or 0 removes it); anything but 0-7 keeps the editor open
+    pub fn save_edited_goal(&mut self) -> Result<()> {
+        let text = self.input_buffer.trim();
+        let weekly_goal = if text.is_empty() {
+            None
+        } else {
+            match text.parse::<u32>() {
+                Ok(0) => None,
+                Ok(goal @ 1..=7) => Some(goal),
+                _ => {
+                    self.flash(format!("'{}' isn't a weekly goal - use a number from 1 to 7", text));
+                    return Ok(());
+                }
+            }
+        };
+        if let Some(habit_id) = self.habits().get(self.habit_mgmt_selected_idx).map(|h| h.id) {
+            self.record("goal edit", |app| app.storage.update_habit_weekly_goal(habit_id, weekly_goal))?;
+        }
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::List;
+        Ok(())
+    }
+
+    /// Start editing the selected habit's tags
+    pub fn start_edit_tags(&mut self) {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            self.input_buffer = habit.tags.join(", ");
+            self.input_cursor_from_end = 0;
+            self.habit_mgmt_mode = HabitMgmtMode::EditTags;
+        }
+    }
+
+    /// Save the edited tags, split on commas (blank and repeated tags are dropped)
+    pub fn save_edited_tags(&mut self) -> Result<()> {
+        if let Some(habit_id) = self.habits().get(self.habit_mgmt_selected_idx).map(|h| h.id) {
+            let mut tags: Vec<String> = Vec::new();
+            for tag in self.input_buffer.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+                if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    tags.push(tag.to_string());
+                }
+            }
+            self.record("tags edit", |app| app.storage.update_habit_tags(habit_id, tags))?;
+        }
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::List;
+        Ok(())
+    }
+
+    /// Start choosing a tag whose habits should be archived
+    pub fn start_archive_tag(&mut self) {
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::ArchiveTag;
+    }
+
+    /// Ask for confirmation before archiving the typed tag's habits
+    pub fn request_archive_tag(&mut self) {
+        let tag = self.input_buffer.trim().to_string();
+        if self.storage.count_tagged(&tag) == 0 {
+            self.flash(format!("No habits tagged '{}'", tag));
+            self.cancel_habit_input();
+        } else {
+            self.input_buffer = tag;
+            self.habit_mgmt_mode = HabitMgmtMode::ConfirmArchiveTag;
+        }
+    }
+
+    /// Archive every habit with the confirmed tag and report how many
+    pub fn confirm_archive_tag(&mut self) -> Result<()> {
+        let tag = self.input_buffer.trim().to_string();
+        let archived = self.record("archive by tag", |app| app.storage.archive_by_tag(&tag))?;
+        self.flash(format!(
+            "Archived {} habit{} tagged '{}'",
+            archived,
+            if archived == 1 { "" } else { "s" },
+            tag
+        ));
+        self.habit_mgmt_selected_idx = self.habit_mgmt_selected_idx
+            .min(self.habits().len().saturating_sub(1));
+        self.selected_habit_idx = 0;
+        self.cancel_habit_input();
+        Ok(())
+    }
+
+    /// Start typing where to write a full backup, suggesting a dated file in the export directory
+    pub fn start_backup(&mut self) {
+        let file_name = format!("habits-backup-{}.json", self.today().format("%Y-%m-%d"));
+        self.input_buffer = self.export_dir.join(file_name).display().to_string();
+        self.input_cursor_from_end = 0;
+        self.habit_mgmt_mode = HabitMgmtMode::BackupPath;
+    }
+
+    /// Write a full backup to the typed path
+    pub fn save_backup(&mut self) {
+        let path = PathBuf::from(self.input_buffer.trim());
+        match self.storage.export_all(&path) {
+            Ok(()) => self.flash(format!("Backed up to {}", path.display())),
+            Err(err) => self.flash(format!("Backup failed: {:#}", err)),
+        }
+        self.cancel_habit_input();
+    }
+
+    /// Start typing the backup file to restore from
+    pub fn start_restore(&mut self) {
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::RestorePath;
+    }
+
+    /// Read the typed backup and preview it, to be merged or to replace everything
+    pub fn request_restore(&mut self) {
+        let path = PathBuf::from(self.input_buffer.trim());
+        self.cancel_habit_input();
+        if let Err(err) = self.start_import(&path) {
+            self.flash(format!("Import failed: {:#}", err));
+        }
+    }
+
+    /// Start typing a text file of habit names to add
+    pub fn start_import_list(&mut self) {
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::ImportListPath;
+    }
+
+    /// Add a Daily habit for each line of the typed file, as one undoable change
+    pub fn save_import_list(&mut self) -> Result<()> {
+        let path = PathBuf::from(self.input_buffer.trim());
+        self.cancel_habit_input();
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) => {
+                self.flash(format!("Couldn't read {}: {}", path.display(), err));
+                return Ok(());
+            }
+        };
+        let added = self.record("habit import", |app| app.storage.add_habits_from_text(&text))?;
+        self.flash(format!("Added {} habit(s) from {}", added, path.display()));
+        Ok(())
+    }
+
+    /// Save new habit
+    pub fn save_new_habit(&mut self) -> Result<()> {
+        let name = self.input_buffer.trim().to_string();
+        if self.storage.is_name_taken(&name, None, None) {
+            self.flash(format!("A habit named '{}' already exists", name));
+        } else if !name.is_empty() {
+            self.record("habit add", |app| app.storage.add_habit(name))?;
+        }
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::List;
+        Ok(())
+    }
+
+    /// Save edited habit
+    pub fn save_edited_habit(&mut self) -> Result<()> {
+        if !self.input_buffer.trim().is_empty() {
+            if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+                let habit_id = habit.id;
+                let label = habit.label.clone();
+                let name = self.input_buffer.trim().to_string();
+                if self.storage.is_name_taken(&name, label.as_deref(), Some(habit_id)) {
+                    self.flash(format!("A habit named '{}' already exists", name));
+                } else {
+                    self.record("habit rename", |app| app.storage.update_habit_name(habit_id, name))?;
+                }
+            }
+        }
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::List;
+        Ok(())
+    }
+
+    /// Save the edited label (an empty label removes it)
+    pub fn save_edited_label(&mut self) -> Result<()> {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            let habit_id = habit.id;
+            let name = habit.name.clone();
+            let label = Some(self.input_buffer.trim().to_string()).filter(|l| !l.is_empty());
+            if self.storage.is_name_taken(&name, label.as_deref(), Some(habit_id)) {
+                self.flash(format!("Another '{}' already uses that label", name));
+            } else {
+                self.record("label edit", |app| app.storage.update_habit_label(habit_id, label))?;
+            }
+        }
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::List;
+        Ok(())
+    }
+
+    /// Cancel habit input
+    pub fn cancel_habit_input(&mut self) {
+        self.clear_input();
+        self.habit_mgmt_mode = HabitMgmtMode::List;
+    }
+
+    /// Start merging the selected habit into another one, picked next
+    pub fn start_merge_habit(&mut self) {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            self.merge_source = Some(habit.id);
+            self.habit_mgmt_mode = HabitMgmtMode::MergeHabit;
+        }
+    }
+
+    /// Merge the habit being merged into the selected one, which keeps its name and settings
+    pub fn merge_into_selected_habit(&mut self) -> Result<()> {
+        let Some(source) = self.merge_source else {
+            self.cancel_merge_habit();
+            return Ok(());
+        };
+        let Some(keep) = self.habits().get(self.habit_mgmt_selected_idx).map(|h| (h.id, h.display_name())) else {
+            return Ok(());
+        };
+        if keep.0 == source {
+            self.flash("Pick a different habit to merge into");
+            return Ok(());
+        }
+        let source_name = self.storage.get_habit(source).map(|h| h.display_name()).unwrap_or_default();
+        self.record("habit merge", |app| app.storage.merge_habits(keep.0, source))?;
+        if let Some(idx) = self.habits().iter().position(|h| h.id == keep.0) {
+            self.habit_mgmt_selected_idx = idx;
+        }
+        self.flash(format!("Merged {} into {}", source_name, keep.1));
+        self.cancel_merge_habit();
+        Ok(())
+    }
+
+    /// Stop merging without changing anything
+    pub fn cancel_merge_habit(&mut self) {
+        self.merge_source = None;
+        self.habit_mgmt_mode = HabitMgmtMode::List;
+    }
+
+    /// Delete the selected habit
+    pub fn delete_selected_habit(&mut self) -> Result<()> {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            let habit_id = habit.id;
+            self.record("habit delete", |app| app.storage.remove_habit(habit_id))?;
+            // Adjust selected index if necessary
+            let habit_count = self.habits().len();
+            if self.habit_mgmt_selected_idx >= habit_count && habit_count > 0 {
+                self.habit_mgmt_selected_idx = habit_count - 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Duplicate the selected habit and select the copy
+    pub fn duplicate_selected_habit(&mut self) -> Result<()> {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            let habit_id = habit.id;
+            let new_id = self.record("habit duplicate", |app| app.storage.duplicate_habit(habit_id))?;
+            if let Some(idx) = self.habits().iter().position(|h| h.id == new_id) {
+                self.habit_mgmt_selected_idx = idx;
+            }
+        }
+        Ok(())
+    }
+
+    /// Move selected habit up in the list
+    pub fn move_habit_up(&mut self) -> Result<()> {
+        if self.habit_mgmt_selected_idx > 0 {
+            let habits = self.habits();
+            if let Some(habit) = habits.get(self.habit_mgmt_selected_idx) {
+                let habit_id = habit.id;
+                let new_order = self.habit_mgmt_selected_idx - 1;
+                self.record("habit reorder", |app| app.storage.reorder_habit(habit_id, new_order))?;
+                self.habit_mgmt_selected_idx = new_order;
+            }
+        }
+        Ok(())
+    }
+
+    /// Move selected habit down in the list
+    pub fn move_habit_down(&mut self) -> Result<()> {
+        let habit_count = self.habits().len();
+        if self.habit_mgmt_selected_idx < habit_count.saturating_sub(1) {
+            let habits = self.habits();
+            if let Some(habit) = habits.get(self.habit_mgmt_selected_idx) {
+                let habit_id = habit.id;
+                let new_order = self.habit_mgmt_selected_idx + 1;
+                self.record("habit reorder", |app| app.storage.reorder_habit(habit_id, new_order))?;
+                self.habit_mgmt_selected_idx = new_order;
+            }
+        }
+        Ok(())
+    }
+
+    /// Run an action and push what it changed onto the undo stack
+    /// Starting a new action clears anything that could be redone.
+    fn record<T>(&mut self, description: &'static str, action: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.recording {
+            return action(self);
+        }
+        self.storage.begin_change();
+        self.recording = true;
+        let result = action(self);
+        self.recording = false;
+
+        let change = self.storage.finish_change();
+        if !change.is_empty() {
+            self.undo_stack.push((description, change));
+            if self.undo_stack.len() > UNDO_LIMIT {
+                self.undo_stack.remove(0);
+            }
+            self.redo_stack.clear();
+        }
+        // However many writes the action made, they reach disk together
+        self.storage.flush_or_report();
+        result
+    }
+
+    /// Forget every undoable change, once the data they were made to has been replaced
+    fn clear_history(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    /// Undo the most recent action (a staged status counts as one)
+    pub fn undo(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        match self.undo_stack.pop() {
+            Some((description, change)) => {
+                self.storage.undo_change(&change)?;
+                self.redo_stack.push((description, change));
+                self.clamp_after_history_step();
+                self.flash(format!("Undid {}", description));
+            }
+            None => self.flash("Nothing to undo"),
+        }
+        Ok(())
+    }
+
+    /// Redo the most recently undone action
+    pub fn redo(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        match self.redo_stack.pop() {
+            Some((description, change)) => {
+                self.storage.redo_change(&change)?;
+                self.undo_stack.push((description, change));
+                self.clamp_after_history_step();
+                self.flash(format!("Redid {}", description));
+            }
+            None => self.flash("Nothing to redo"),
+        }
+        Ok(())
+    }
+
+    /// Keep selections in range after undo/redo adds or removes habits
+    fn clamp_after_history_step(&mut self) {
+        self.clamp_habit_selection();
+        let habit_count = self.habits().len();
+        self.habit_mgmt_selected_idx = self.habit_mgmt_selected_idx.min(habit_count.saturating_sub(1));
+    }
+
+    /// Navigate to previous habit in management view
+    pub fn habit_mgmt_prev(&mut self) {
+        let habit_count = self.habits().len();
+        if habit_count > 0 && self.habit_mgmt_selected_idx > 0 {
+            self.habit_mgmt_selected_idx -= 1;
+        }
+    }
+
+    /// Navigate to next habit in management view
+    pub fn habit_mgmt_next(&mut self) {
+        let habit_count = self.habits().len();
+        if habit_count > 0 && self.habit_mgmt_selected_idx < habit_count - 1 {
+            self.habit_mgmt_selected_idx += 1;
+        }
+    }
+
+    /// Move the management selection up a screenful of habits
+    pub fn habit_mgmt_page_up(&mut self) {
+        self.habit_mgmt_selected_idx = self.habit_mgmt_selected_idx.saturating_sub(self.habit_mgmt_scroll.rows());
+    }
+
+    /// Move the management selection down a screenful of habits
+    pub fn habit_mgmt_page_down(&mut self) {
+        let last = self.habits().len().saturating_sub(1);
+        self.habit_mgmt_selected_idx = (self.habit_mgmt_selected_idx + self.habit_mgmt_scroll.rows()).min(last);
+    }
+
+    /// Cycle through frequencies for the selected habit
+    pub fn cycle_habit_frequency(&mut self) -> Result<()> {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            let habit_id = habit.id;
+
+            let new_frequency = match habit.frequency {
+                Frequency::Daily => Frequency::Weekly,
+                Frequency::Weekly => Frequency::EveryNDays(DEFAULT_INTERVAL_DAYS),
+                Frequency::EveryNDays(_) => Frequency::SpecificDays(DEFAULT_SPECIFIC_DAYS.to_vec()),
+                Frequency::SpecificDays(_) => Frequency::AsNeeded,
+                Frequency::AsNeeded => Frequency::Daily,
+            };
+
+            self.record("frequency change", |app| app.storage.update_habit_frequency(habit_id, new_frequency))?;
+        }
+        Ok(())
+    }
+
+    /// Ask for confirmation before clearing the current week
+    pub fn start_reset_week(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.view = AppView::ResetWeekConfirmation;
+        Ok(())
+    }
+
+    /// Clear all logs in the currently viewed week and return to main view
+    pub fn confirm_reset_week(&mut self) -> Result<usize> {
+        let week = self.current_week;
+        let removed = self.record("reset week", |app| app.storage.clear_week(&week))?;
+        self.view = AppView::Main;
+        Ok(removed)
+    }
+
+    // Grid Methods
+
+    /// Enter the grid view, starting at the selected day
+    pub fn enter_grid_view(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        let habit_count = self.habits().len();
+        self.grid_row = self.grid_row.min(habit_count.saturating_sub(1));
+        self.grid_col = self.selected_day_idx;
+        self.view = AppView::Grid;
+        Ok(())
+    }
+
+    /// Leave the grid view, saving any staged change
+    pub fn leave_grid_view(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.selected_day_idx = self.grid_col;
+        self.selected_habit_idx = 0;
+        self.view = AppView::Main;
+        Ok(())
+    }
+
+    /// Move the grid cursor up one habit
+    pub fn grid_up(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.grid_row = self.grid_row.saturating_sub(1);
+        Ok(())
+    }
+
+    /// Move the grid cursor down one habit
+    pub fn grid_down(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        let habit_count = self.habits().len();
+        if self.grid_row + 1 < habit_count {
+            self.grid_row += 1;
+        }
+        Ok(())
+    }
+
+    /// Move the grid cursor to the previous day
+    pub fn grid_left(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.grid_col = self.grid_col.saturating_sub(1);
+        Ok(())
+    }
+
+    /// Move the grid cursor to the next day
+    pub fn grid_right(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        if self.grid_col < 6 {
+            self.grid_col += 1;
+        }
+        Ok(())
+    }
+
+    /// Get the (habit, date) under the grid cursor
+    pub fn grid_cell(&self) -> Option<(Uuid, NaiveDate)> {
+        let habit = self.habits().get(self.grid_row).map(|h| h.id)?;
+        let date = self.current_week.day(self.grid_col)?;
+        Some((habit, date))
+    }
+
+    /// Check whether a date is in the future (future days can't be edited in the grid)
+    pub fn is_future(&self, date: NaiveDate) -> bool {
+        date > self.today()
+    }
+
+    /// Cycle the status of the cell under the grid cursor (stages change, doesn't save)
+    pub fn toggle_grid_cell_status(&mut self) {
+        if let Some((habit_id, date)) = self.grid_cell() {
+            if self.is_future(date) {
+                return;
+            }
+            let new_status = self.get_habit_status(habit_id, date).cycle();
+            self.staged_status = Some((habit_id, date, new_status));
+            if self.needs_skip_note(habit_id, date, new_status) {
+                self.start_note_input();
+            }
+        }
+    }
+
+    /// Toggle whether the selected Weekly habit auto-fills earlier days as Skipped
+    pub fn toggle_habit_auto_fill_week(&mut self) -> Result<()> {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            let habit_id = habit.id;
+            let auto_fill_week = !habit.auto_fill_week;
+            self.record("auto-skip change", |app| app.storage.update_habit_auto_fill_week(habit_id, auto_fill_week))?;
+        }
+        Ok(())
+    }
+
+    /// Give the selected habit the next color in the palette, or none after the last
+    pub fn cycle_habit_color(&mut self) -> Result<()> {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            let mut habit = (*habit).clone();
+            let next = match habit.color.as_deref().and_then(|c| HABIT_COLORS.iter().position(|&p| p == c)) {
+                Some(idx) => HABIT_COLORS.get(idx + 1),
+                None => HABIT_COLORS.first(),
+            };
+            habit.color = next.map(|name| name.to_string());
+            self.record("habit color", |app| app.storage.update_habit(habit))?;
+        }
+        Ok(())
+    }
+
+    /// Toggle whether the selected habit is skipped on weekends
+    pub fn toggle_habit_skip_weekends(&mut self) -> Result<()> {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            let mut habit = (*habit).clone();
+            habit.skip_weekends = !habit.skip_weekends;
+            self.record("weekends change", |app| app.storage.update_habit(habit))?;
+        }
+        Ok(())
+    }
+
+    /// Lengthen (delta > 0) or shorten (delta < 0) the selected habit's every-N-days interval
+    pub fn adjust_habit_interval(&mut self, delta: i32) -> Result<()> {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            if let Frequency::EveryNDays(n) = habit.frequency {
+                let habit_id = habit.id;
+                let new_n = n.saturating_add_signed(delta).max(2);
+                self.record("interval change", |app| app.storage.update_habit_frequency(habit_id, Frequency::EveryNDays(new_n)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Turn a weekday on or off for the selected habit, if it's scheduled on specific days
+    pub fn toggle_habit_day(&mut self, day: Weekday) -> Result<()> {
+        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
+            let habit_id = habit.id;
+            let mut frequency = habit.frequency.clone();
+            if !matches!(frequency, Frequency::SpecificDays(_)) {
+                return Ok(());
+            }
+            if frequency.toggle_day(day) {
+                self.record("days change", |app| app.storage.update_habit_frequency(habit_id, frequency))?;
+            } else {
+                self.flash("A habit needs at least one day - pick another frequency with 'f' instead");
+            }
+        }
+        Ok(())
+    }
+
+    // Export Methods
+
+    /// First and last day the next export covers: the custom range, or else the viewed week
+    pub fn export_bounds(&self) -> (NaiveDate, NaiveDate) {
+        match self.export_range {
+            Some(range) if self.export_kind.takes_range() => range,
+            _ => (self.current_week.start, self.current_week.end()),
+        }
+    }
+
+    /// Whether the next export covers a custom range rather than the viewed week
+    pub fn exporting_range(&self) -> bool {
+        self.export_range.is_some() && self.export_kind.takes_range()
+    }
+
+    /// What the next export covers, e.g. "Oct 13 - Oct 19, 2025" or "Oct 01, 2025 to Oct 31, 2025"
+    pub fn export_period(&self) -> String {
+        if self.exporting_range() {
+            let (start, end) = self.export_bounds();
+            range_label(start, end)
+        } else {
+            self.current_week.format()
+        }
+    }
+
+    /// Gather the data shared by every export format for the days `start` through `end`
+    /// A range that is exactly one week is reported as that week, with its reflection.
+    fn range_report(&self, start: NaiveDate, end: NaiveDate) -> WeekReport {
+        let habits = self.habits();
+        let days: Vec<NaiveDate> = start.iter_days().take_while(|&date| date <= end).collect();
+        let week = self.storage.week_of(start);
+        let weekly = week.start == start && week.end() == end;
+
+        let totals = habits.iter().map(|habit| {
+            let mut done = 0;
+            let mut partial = 0;
+            let mut skipped = 0;
+            for &date in &days {
+                match self.get_habit_status(habit.id, date) {
+                    HabitStatus::Done => done += 1,
+                    HabitStatus::Partial => partial += 1,
+                    // Vacation skips aren't among the trackable days, as in the stats
+                    HabitStatus::Skipped if self.storage.is_vacation(date) => {}
+                    HabitStatus::Skipped => skipped += 1,
+                    HabitStatus::NotApplicable | HabitStatus::Unmarked => {}
+                }
+            }
+            let trackable = self.storage.trackable_days(habit.id, start, end);
+            HabitWeekTotals {
+                habit_id: habit.id,
+                name: habit.display_name(),
+                done,
+                partial,
+                skipped,
+                unmarked: trackable.saturating_sub(done + partial + skipped),
+                trackable,
+            }
+        }).collect();
+
+        let days = days.iter().map(|&date| {
+            let entries = habits.iter().map(|habit| ReportEntry {
+                habit_id: habit.id,
+                habit_name: habit.display_name(),
+                status: self.get_habit_status(habit.id, date),
+                note: self.storage.get_log(habit.id, date)
+                    .map(|log| log.notes.iter()
+                        .map(|(_, text)| text.trim())
+                        .filter(|text| !text.is_empty())
+                        .collect::<Vec<_>>()
+                        .join("; "))
+                    .filter(|notes| !notes.is_empty()),
+            }).collect();
+            (date, entries)
+        }).collect();
+
+        WeekReport {
+            period: if weekly { week.format() } else { range_label(start, end) },
+            weekly,
+            generated: self.now().format("%B %d, %Y at %I:%M %p").to_string(),
+            reflection: if weekly { self.storage.get_week_note(&week).map(str::to_string) } else { None },
+            totals,
+            days,
+        }
+    }
+
+    /// Export the current week (or the custom range) in the currently selected format
+    pub fn export_week(&self) -> String {
+        let (start, end) = self.export_bounds();
+        match self.export_kind {
+            ExportKind::Markdown => self.export_range_to_markdown(start, end),
+            ExportKind::PlainText => self.export_range_to_text(start, end),
+            ExportKind::Html => self.export_range_to_html(start, end),
+            ExportKind::Json => self.export_range_to_json(start, end),
+            ExportKind::Csv => self.export_range_to_csv(start, end),
+            ExportKind::TrendCsv => self.export_trends_to_csv(TREND_WEEKS),
+            ExportKind::MonthlyTrendCsv => self.export_monthly_trends_to_csv(TREND_MONTHS),
+        }
+    }
+
+    /// Export the current week's data to markdown format
+    pub fn export_week_to_markdown(&self) -> String {
+        self.export_range_to_markdown(self.current_week.start, self.current_week.end())
+    }
+
+    /// Export the days `start` through `end` to markdown format
+    pub fn export_range_to_markdown(&self, start: NaiveDate, end: NaiveDate) -> String {
+        let report = self.range_report(start, end);
+        let mut output = String::new();
+
+        // Header
+        output.push_str("# Habit Tracking Report\n\n");
+        if report.weekly {
+            output.push_str(&format!("**Week of {}**\n\n", report.period));
+        } else {
+            output.push_str(&format!("**{}**\n\n", report.period));
+        }
+        output.push_str(&format!("Generated: {}\n\n", report.generated));
+
+        // Week reflection
+        if let Some(note) = &report.reflection {
+            output.push_str("## Reflection\n\n");
+            output.push_str(note);
+            output.push_str("\n\n");
+        }
+
+        // Summary over the whole period
+        output.push_str(if report.weekly { "## Weekly Summary\n\n" } else { "## Summary\n\n" });
+
+        if report.totals.is_empty() {
+            output.push_str("*No habits tracked this week.*\n\n");
+            return output;
+        }
+
+        // Display stats table
+        output.push_str("| Habit | Done | Partial | Skipped | Unmarked | Completion Rate | Of Marked Days |\n");
+        output.push_str("|-------|------|---------|---------|----------|------------------|----------------|\n");
+
+        for totals in &report.totals {
+            let rate = round_rate(totals.done, totals.trackable);
+            let marked_rate = round_rate(totals.done, totals.done + totals.partial + totals.skipped);
+            output.push_str(&format!("| {} | {} | {} | {} | {} | {}% | {}% |\n",
+                totals.name, totals.done, totals.partial, totals.skipped, totals.unmarked, rate, marked_rate));
+        }
+        output.push_str("\n*Completion Rate counts unmarked days as not done; Of Marked Days only counts days given a status.*\n\n");
+
+        // Streaks and adherence since each habit was created, as of the last exported day
+        output.push_str("## Streaks & Adherence\n\n");
+        output.push_str("| Habit | Current Streak | All-Time Adherence |\n");
+        output.push_str("|-------|----------------|--------------------|\n");
+        let first_created = self.habits().iter().map(|h| h.created_at).min().unwrap_or(start);
+        let all_time = self.storage.get_stats(first_created, end);
+        for totals in &report.totals {
+            let adherence = all_time.get(&totals.habit_id)
+                .map_or(0, |stats| round_rate(stats.done, stats.trackable()));
+            output.push_str(&format!("| {} | {} days | {}% |\n",
+                totals.name, self.storage.current_streak(totals.habit_id, end), adherence));
+        }
+        output.push('\n');
+
+        // Daily breakdown
+        output.push_str("## Daily Breakdown\n\n");
+
+        for (date, entries) in &report.days {
+            let weekday = Week::full_weekday_name(*date);
+            output.push_str(&format!("### {} - {}\n\n", weekday, date.format("%B %d, %Y")));
+
+            let mut has_activity = false;
+
+            for entry in entries {
+                let status_str = match entry.status {
+                    HabitStatus::Done => "✓ Done",
+                    HabitStatus::Partial => "◐ Partial",
+                    HabitStatus::Skipped => "✗ Skipped",
+                    HabitStatus::NotApplicable => "∅ N/A",
+                    HabitStatus::Unmarked => "○ Not tracked",
+                };
+
+                match (&entry.note, entry.status) {
+                    // A skip's note is its reason, so it goes on the same line
+                    (Some(note), HabitStatus::Skipped) => {
+                        let reason = note.strip_prefix("Skipped: ").unwrap_or(note);
+                        output.push_str(&format!("- **{}**: {} - *{}*\n", entry.habit_name, status_str, reason));
+                    }
+                    (Some(note), _) => {
+                        output.push_str(&format!("- **{}**: {}\n", entry.habit_name, status_str));
+                        output.push_str(&format!("  *Note: {}*\n", note));
+                    }
+                    (None, _) => output.push_str(&format!("- **{}**: {}\n", entry.habit_name, status_str)),
+                }
+                if entry.note.is_some() {
+                    has_activity = true;
+                }
+
+                if entry.status != HabitStatus::Unmarked {
+                    has_activity = true;
+                }
+            }
+
+            if !has_activity {
+                output.push_str("*No activity recorded for this day.*\n");
+            }
+
+            output.push('\n');
+        }
+
+        // Footer
+        output.push_str("---\n\n");
+        output.push_str("*Report generated by Healing-Habits habit tracker*\n");
+
+        output
+    }
+
+    /// Export the current week's data as a self-contained HTML document
+    pub fn export_week_to_html(&self) -> String {
+        self.export_range_to_html(self.current_week.start, self.current_week.end())
+    }
+
+    /// Export the days `start` through `end` as a self-contained HTML document
+    /// Styles are inlined in the head, so the file can be opened or sent on its own.
+    pub fn export_range_to_html(&self, start: NaiveDate, end: NaiveDate) -> String {
+        let report = self.range_report(start, end);
+        let title = if report.weekly { format!("Week of {}", report.period) } else { report.period.clone() };
+        let mut output = String::new();
+
+        // Header
+        output.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        output.push_str(&format!("<title>Habit Tracking Report - {}</title>\n", html_escape(&title)));
+        output.push_str(concat!(
+            "<style>\n",
+            "body { font-family: sans-serif; margin: 2em; color: #222; }\n",
+            "table { border-collapse: collapse; margin-bottom: 1.5em; }\n",
+            "th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: center; }\n",
+            "th:first-child, td:first-child { text-align: left; }\n",
+            ".done { background: #c8e6c9; }\n",
+            ".partial { background: #fff3c4; }\n",
+            ".skipped { background: #ffcdd2; }\n",
+            ".na, .unmarked { background: #eeeeee; color: #777; }\n",
+            "</style>\n",
+        ));
+        output.push_str("</head>\n<body>\n<h1>Habit Tracking Report</h1>\n");
+        output.push_str(&format!("<p><strong>{}</strong></p>\n", html_escape(&title)));
+        output.push_str(&format!("<p>Generated: {}</p>\n", html_escape(&report.generated)));
+
+        // Week reflection
+        if let Some(note) = &report.reflection {
+            output.push_str("<h2>Reflection</h2>\n");
+            output.push_str(&format!("<p>{}</p>\n", html_escape(note).replace('\n', "<br>\n")));
+        }
+
+        // Summary over the whole period
+        output.push_str(if report.weekly { "<h2>Weekly Summary</h2>\n" } else { "<h2>Summary</h2>\n" });
+
+        if report.totals.is_empty() {
+            output.push_str("<p><em>No habits tracked this week.</em></p>\n</body>\n</html>\n");
+            return output;
+        }
+
+        output.push_str("<table>\n<tr><th>Habit</th><th>Done</th><th>Partial</th><th>Skipped</th><th>Unmarked</th><th>Completion Rate</th></tr>\n");
+        for totals in &report.totals {
+            let rate = round_rate(totals.done, totals.trackable);
+            output.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}%</td></tr>\n",
+                html_escape(&totals.name), totals.done, totals.partial, totals.skipped, totals.unmarked, rate));
+        }
+        output.push_str("</table>\n");
+
+        // A row per habit, a colored cell per day
+        output.push_str("<h2>Daily Status</h2>\n<table>\n<tr><th>Habit</th>");
+        for (date, _) in &report.days {
+            output.push_str(&format!("<th>{}</th>", date.format("%a %b %d")));
+        }
+        output.push_str("</tr>\n");
+        for (row, totals) in report.totals.iter().enumerate() {
+            output.push_str(&format!("<tr><td>{}</td>", html_escape(&totals.name)));
+            for (_, entries) in &report.days {
+                let (class, label) = match entries[row].status {
+                    HabitStatus::Done => ("done", "✓"),
+                    HabitStatus::Partial => ("partial", "◐"),
+                    HabitStatus::Skipped => ("skipped", "✗"),
+                    HabitStatus::NotApplicable => ("na", "∅"),
+                    HabitStatus::Unmarked => ("unmarked", ""),
+                };
+                output.push_str(&format!("<td class=\"{}\">{}</td>", class, label));
+            }
+            output.push_str("</tr>\n");
+        }
+        output.push_str("</table>\n");
+
+        // Notes, day by day
+        let notes: Vec<_> = report.days.iter()
+            .flat_map(|(date, entries)| entries.iter()
+                .filter_map(move |entry| entry.note.as_ref().map(|note| (date, &entry.habit_name, note))))
+            .collect();
+        if !notes.is_empty() {
+            output.push_str("<h2>Notes</h2>\n<ul>\n");
+            for (date, habit_name, note) in notes {
+                output.push_str(&format!("<li><strong>{}</strong> - {}: {}</li>\n",
+                    date.format("%A, %B %d"), html_escape(habit_name), html_escape(note)));
+            }
+            output.push_str("</ul>\n");
+        }
+
+        // Footer
+        output.push_str("<hr>\n<p><em>Report generated by Healing-Habits habit tracker</em></p>\n</body>\n</html>\n");
+
+        output
+    }
+
+    /// Build the structured export of the current week
+    pub fn week_export(&self) -> WeekExport {
+        self.range_export(self.current_week.start, self.current_week.end())
+    }
+
+    /// Build the structured export of the days `start` through `end`
+    pub fn range_export(&self, start: NaiveDate, end: NaiveDate) -> WeekExport {
+        let report = self.range_report(start, end);
+        WeekExport {
+            export_schema: EXPORT_SCHEMA,
+            week_start: start,
+            week_end: end,
+            generated: report.generated,
+            reflection: report.reflection,
+            habits: report.totals.into_iter().map(|totals| HabitTotalsExport {
+                habit_id: totals.habit_id,
+                name: totals.name,
+                done: totals.done,
+                partial: totals.partial,
+                skipped: totals.skipped,
+                unmarked: totals.unmarked,
+                trackable_days: totals.trackable,
+                completion_rate: round_rate(totals.done, totals.trackable),
+            }).collect(),
+            days: report.days.into_iter().map(|(date, entries)| DayExport {
+                date,
+                entries: entries.into_iter().map(|entry| EntryExport {
+                    habit_id: entry.habit_id,
+                    name: entry.habit_name,
+                    status: entry.status.into(),
+                    note: entry.note,
+                }).collect(),
+            }).collect(),
+        }
+    }
+
+    /// Export the current week's data as pretty-printed JSON
+    pub fn export_week_to_json(&self) -> String {
+        self.export_range_to_json(self.current_week.start, self.current_week.end())
+    }
+
+    /// Export the days `start` through `end` as pretty-printed JSON
+    pub fn export_range_to_json(&self, start: NaiveDate, end: NaiveDate) -> String {
+        // Plain data with string keys, so serializing can't fail
+        serde_json::to_string_pretty(&self.range_export(start, end)).unwrap_or_default()
+    }
+
+    /// Export one row per (week, habit) for the `weeks` weeks ending with the viewed week,
+    /// oldest first. Weeks without any logs come out as zeros.
+    pub fn export_trends_to_csv(&self, weeks: usize) -> String {
+        let mut output = String::from("week_start,habit,done,skipped,total,rate\n");
+        let habits = self.habits();
+
+        let mut week = self.current_week;
+        for _ in 1..weeks {
+            week = week.prev();
+        }
+
+        for _ in 0..weeks {
+            let label = week.start.format("%Y-%m-%d").to_string();
+            self.push_trend_rows(&mut output, &label, week.start, week.end(), &habits);
+            week = week.next();
+        }
+
+        output
+    }
+
+    /// Export one row per (month, habit) for the `months` months ending with the selected
+    /// day's month, oldest first. Months without any logs come out as zeros.
+    pub fn export_monthly_trends_to_csv(&self, months: usize) -> String {
+        let mut output = String::from("month,habit,done,skipped,total,rate\n");
+        let habits = self.habits();
+
+        let mut month = month_bounds(self.selected_date());
+        for _ in 1..months {
+            month = month_bounds(month.0.pred_opt().unwrap());
+        }
+
+        for _ in 0..months {
+            let (start, end) = month;
+            self.push_trend_rows(&mut output, &start.format("%Y-%m").to_string(), start, end, &habits);
+            month = month_bounds(end.succ_opt().unwrap());
+        }
+
+        output
+    }
+
+    /// Add a trend CSV row per habit for the days `start` through `end`, labelled `period`
+    fn push_trend_rows(
+        &self,
+        output: &mut String,
+        period: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+        habits: &[&crate::models::Habit],
+    ) {
+        let stats = self.storage.get_stats(start, end);
+        for habit in habits {
+            let stats = stats.get(&habit.id).copied().unwrap_or_default();
+            let total = stats.trackable();
+            output.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                period,
+                csv_field(&habit.display_name()),
+                stats.done,
+                stats.skipped,
+                total,
+                round_rate(stats.done, total)
+            ));
+        }
+    }
+
+    /// Export the current week as CSV, one row per day and habit
+    pub fn export_week_to_csv(&self) -> String {
+        self.export_range_to_csv(self.current_week.start, self.current_week.end())
+    }
+
+    /// Export the days `start` through `end` as CSV, one row per day and habit
+    pub fn export_range_to_csv(&self, start: NaiveDate, end: NaiveDate) -> String {
+        let mut output = String::from("date,habit,status,note\n");
+        for (date, entries) in self.range_report(start, end).days {
+            for entry in entries {
+                output.push_str(&format!(
+                    "{},{},{},{}\n",
+                    date.format("%Y-%m-%d"),
+                    csv_field(&entry.habit_name),
+                    StatusExport::from(entry.status).as_str(),
+                    csv_field(entry.note.as_deref().unwrap_or(""))
+                ));
+            }
+        }
+        output
+    }
+
+    /// Export the current week's data as plain text, without markdown syntax
+    pub fn export_week_to_text(&self) -> String {
+        self.export_range_to_text(self.current_week.start, self.current_week.end())
+    }
+
+    /// Export the days `start` through `end` as plain text, without markdown syntax
+    pub fn export_range_to_text(&self, start: NaiveDate, end: NaiveDate) -> String {
+        let report = self.range_report(start, end);
+        let mut output = String::new();
+
+        // Header
+        output.push_str("Habit Tracking Report\n");
+        if report.weekly {
+            output.push_str(&format!("Week of {}\n", report.period));
+        } else {
+            output.push_str(&format!("{}\n", report.period));
+        }
+        output.push_str(&format!("Generated: {}\n\n", report.generated));
+
+        // Week reflection
+        if let Some(note) = &report.reflection {
+            output.push_str("Reflection:\n");
+            output.push_str(note);
+            output.push_str("\n\n");
+        }
+
+        // Summary over the whole period
+        output.push_str(if report.weekly { "Weekly Summary:\n" } else { "Summary:\n" });
+
+        if report.totals.is_empty() {
+            output.push_str("No habits tracked this week.\n");
+            return output;
+        }
+
+        for totals in &report.totals {
+            let rate = round_rate(totals.done, totals.trackable);
+            output.push_str(&format!("  {}: {} done, {} partial, {} skipped, {} unmarked ({}%)\n",
+                totals.name, totals.done, totals.partial, totals.skipped, totals.unmarked, rate));
+        }
+        output.push('\n');
+
+        // Daily breakdown
+        output.push_str("Daily Breakdown:\n");
+
+        for (date, entries) in &report.days {
+            let weekday = Week::full_weekday_name(*date);
+            output.push_str(&format!("\n{} - {}\n", weekday, date.format("%B %d, %Y")));
+
+            let mut has_activity = false;
+
+            for entry in entries {
+                let status_str = match entry.status {
+                    HabitStatus::Done => "Done",
+                    HabitStatus::Partial => "Partial",
+                    HabitStatus::Skipped => "Skipped",
+                    HabitStatus::NotApplicable => "N/A",
+                    HabitStatus::Unmarked => "Not tracked",
+                };
+
+                output.push_str(&format!("  {}: {}\n", entry.habit_name, status_str));
+
+                if let Some(note) = &entry.note {
+                    output.push_str(&format!("    Note: {}\n", note));
+                    has_activity = true;
+                }
+
+                if entry.status != HabitStatus::Unmarked {
+                    has_activity = true;
+                }
+            }
+
+            if !has_activity {
+                output.push_str("  No activity recorded for this day.\n");
+            }
+        }
+
+        output
+    }
+
+    /// Show the export in-app without writing anything
+    pub fn start_export_preview(&mut self) -> Result<()> {
+        self.commit_staged_status()?;
+        self.preview_scroll = 0;
+        self.export_range = None;
+        self.export_preview = self.export_week();
+        self.view = AppView::ExportPreview;
+        Ok(())
+    }
+
+    /// Start typing the days to export, e.g. "2025-10-01 to 2025-10-31"
+    pub fn start_export_range_input(&mut self) {
+        let (start, end) = self.export_range
+            .unwrap_or((self.current_week.start, self.current_week.end()));
+        self.input_buffer = format!("{} to {}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"));
+        self.input_cursor_from_end = 0;
+        self.view = AppView::ExportRange;
+    }
+
+    /// Export the typed range from now on (a blank entry goes back to the viewed week)
+    /// An entry that isn't two loggable dates at most `MAX_EXPORT_DAYS` apart leaves the prompt open.
+    pub fn save_export_range(&mut self) {
+        if self.input_buffer.trim().is_empty() {
+            self.export_range = None;
+        } else {
+            let Some((start, end)) = parse_date_range(&self.input_buffer) else {
+                self.flash("Enter the first and last day as YYYY-MM-DD to YYYY-MM-DD");
+                return;
+            };
+            let today = self.today();
+            if let Err(e) = validate_log_date(start, today).and_then(|_| validate_log_date(end, today)) {
+                self.flash(e.to_string());
+                return;
+            }
+            if (end - start).num_days() >= MAX_EXPORT_DAYS {
+                self.flash(format!("Export at most {} days at a time", MAX_EXPORT_DAYS));
+                return;
+            }
+            self.export_range = Some((start, end));
+        }
+        self.clear_input();
+        self.preview_scroll = 0;
+        self.export_preview = self.export_week();
+        self.view = AppView::ExportPreview;
+    }
+
+    /// Leave the range prompt without changing what's exported
+    pub fn cancel_export_range(&mut self) {
+        self.clear_input();
+        self.view = AppView::ExportPreview;
+    }
+
+    /// Scroll the export preview up one line
+    pub fn preview_scroll_up(&mut self) {
+        self.preview_scroll = self.preview_scroll.saturating_sub(1);
+    }
+
+    /// Scroll the export preview down one line
+    pub fn preview_scroll_down(&mut self) {
+        let line_count = self.export_preview.lines().count() as u16;
+        if self.preview_scroll + 1 < line_count {
+            self.preview_scroll += 1;
+        }
+    }
+
+    /// Switch the export to the next format (markdown, plain text, JSON)
+    pub fn cycle_export_kind(&mut self) {
+        self.export_kind = self.export_kind.next();
+        self.preview_scroll = 0;
+        self.export_preview = self.export_week();
+    }
+
+    /// Write the previewed export to disk
+    pub fn confirm_export_preview(&mut self) -> Result<()> {
+        self.export_and_show_confirmation()
+    }
+
+    /// Export current week (or the custom range) and save to file
+    pub fn export_and_show_confirmation(&mut self) -> Result<()> {
+        let report = self.export_week();
+
+        std::fs::create_dir_all(&self.export_dir)
+            .context("Failed to create export directory")?;
+
+        // Generate filename with the first day, and the last one for a custom range
+        let (start, end) = self.export_bounds();
+        let dates = if self.exporting_range() {
+            format!("{}-to-{}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"))
+        } else {
+            self.current_week.start.format("%Y-%m-%d").to_string()
+        };
+        let filename = format!("{}-{}.{}", self.export_kind.file_prefix(), dates, self.export_kind.extension());
+        let file_path = self.export_dir.join(filename);
+
+        std::fs::write(&file_path, report)
+            .context("Failed to write export file")?;
+
+        // Never prune the export just written
+        if let Some(keep) = self.storage.settings().export_retention {
+            prune_exports(&self.export_dir, keep.max(1))?;
+        }
+
+        self.last_export = Some(LastExport::new(self.export_kind, Some(file_path), !self.exporting_range()));
+        self.view = AppView::ExportConfirmation;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    fn test_app() -> (App, NamedTempFile) {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        // Keep exports out of the real home directory
+        app.export_dir = std::env::temp_dir().join(format!("healing-habits-test-{}", Uuid::new_v4()));
+        // Use a fully past week so every cell is editable
+        app.current_week = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
+        // Default habits are created today; backdate them so the test week counts
+        for mut habit in app.habits().into_iter().cloned().collect::<Vec<_>>() {
+            habit.set_created_at(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+            app.storage.update_habit(habit).unwrap();
+        }
+        (app, temp_file)
+    }
+
+    #[test]
+    fn test_last_view_restored_on_launch() {
+        let (mut app, temp_file) = test_app();
+        app.selected_day_idx = 4;
+        app.quit().unwrap();
+        app.storage.flush().unwrap();
+
+        let mut reopened = App::new(temp_file.path()).unwrap();
+        assert_eq!(reopened.current_week, app.current_week);
+        assert_eq!(reopened.selected_day_idx, 4);
+
+        // Going back to today is still one key away
+        reopened.go_to_today();
+        assert_eq!(reopened.current_week, reopened.storage.week_of(reopened.today()));
+    }
+
+    #[test]
+    fn test_grid_cursor_movement() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_day_idx = 0;
+        app.enter_grid_view().unwrap();
+        assert_eq!(app.view, AppView::Grid);
+        assert_eq!((app.grid_row, app.grid_col), (0, 0));
+
+        // Moving up/left at the edge stays put
+        app.grid_up().unwrap();
+        app.grid_left().unwrap();
+        assert_eq!((app.grid_row, app.grid_col), (0, 0));
+
+        app.grid_down().unwrap();
+        app.grid_down().unwrap();
+        app.grid_right().unwrap();
+        assert_eq!((app.grid_row, app.grid_col), (2, 1));
+
+        // Clamped at the last habit and Sunday
+        for _ in 0..10 {
+            app.grid_down().unwrap();
+            app.grid_right().unwrap();
+        }
+        assert_eq!((app.grid_row, app.grid_col), (app.habits().len() - 1, 6));
+    }
+
+    #[test]
+    fn test_grid_enter_updates_selected_cell() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_day_idx = 0;
+        app.enter_grid_view().unwrap();
+        app.grid_down().unwrap();
+        app.grid_right().unwrap();
+        app.grid_right().unwrap();
+
+        let habit_id = app.habits()[1].id;
+        let date = app.current_week.day(2).unwrap();
+
+        app.toggle_grid_cell_status();
+        assert_eq!(app.get_habit_status(habit_id, date), HabitStatus::Done);
+
+        // Moving off the cell commits it, and no other cell changes
+        app.grid_right().unwrap();
+        assert_eq!(app.storage.get_log(habit_id, date).unwrap().status, HabitStatus::Done);
+        assert!(app.storage.get_log(app.habits()[0].id, date).is_none());
+        assert!(app.storage.get_log(habit_id, app.current_week.day(3).unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_skip_without_note_rejected_when_required() {
+        let (mut app, _temp_file) = test_app();
+        app.toggle_require_skip_note().unwrap();
+        assert!(app.storage.settings().require_skip_note);
+
+        let habit_id = app.habits()[0].id;
+        let date = app.selected_date();
+        app.storage.update_log_status(habit_id, date, HabitStatus::Partial).unwrap();
+
+        // Partial -> Skipped opens the note editor instead of committing
+        app.toggle_habit_status();
+        assert_eq!(app.view, AppView::NoteInput);
+
+        // Saving an empty note is rejected and the status reverts
+        app.save_note_input().unwrap();
+        assert_eq!(app.view, AppView::Main);
+        assert_eq!(app.staged_status, None);
+        assert_eq!(app.get_habit_status(habit_id, date), HabitStatus::Partial);
+
+        // Committing a Skipped with no note directly is also rejected
+        app.staged_status = Some((habit_id, date, HabitStatus::Skipped));
+        app.commit_staged_status().unwrap();
+        assert_eq!(app.get_habit_status(habit_id, date), HabitStatus::Partial);
+
+        // With a note, the Skipped status saves
+        app.toggle_habit_status();
+        for c in "Rough day".chars() {
+            app.input_char(c);
+        }
+        app.save_note_input().unwrap();
+        let log = app.storage.get_log(habit_id, date).unwrap();
+        assert_eq!(log.status, HabitStatus::Skipped);
+        assert_eq!(log.latest_note(), Some("Rough day"));
+    }
+
+    #[test]
+    fn test_every_n_days_visibility() {
+        let (mut app, _temp_file) = test_app();
+        let start = NaiveDate::from_ymd_opt(2025, 10, 13).unwrap();
+        let mut habit = app.habits()[0].clone();
+        habit.set_frequency(Frequency::EveryNDays(3));
+        habit.set_created_at(start);
+        app.storage.update_habit(habit.clone()).unwrap();
+
+        let visible: Vec<i64> = (0..14)
+            .filter(|&offset| {
+                let date = start + chrono::Duration::days(offset);
+                app.habits_for_date(date).iter().any(|h| h.id == habit.id)
+            })
+            .collect();
+        assert_eq!(visible, vec![0, 3, 6, 9, 12]);
+
+        // A zero interval is treated as every day
+        habit.set_frequency(Frequency::EveryNDays(0));
+        app.storage.update_habit(habit.clone()).unwrap();
+        assert!((0..14).all(|offset| {
+            let date = start + chrono::Duration::days(offset);
+            app.habits_for_date(date).iter().any(|h| h.id == habit.id)
+        }));
+    }
+
+    #[test]
+    fn test_today_index() {
+        let (mut app, _temp_file) = test_app();
+        app.set_clock(fixed_clock);
+        assert_eq!(app.today_index(), Some(2));
+        app.current_week = app.current_week.next();
+        assert_eq!(app.today_index(), None);
+    }
+
+    #[test]
+    fn test_due_reminders_at_fixed_time() {
+        let (mut app, _temp_file) = test_app();
+        app.set_clock(fixed_clock);
+        let today = fixed_clock().date();
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+        let at = |h, m| chrono::NaiveTime::from_hms_opt(h, m, 0);
+
+        // Shower is past its time and unmarked; Brush teeth is past but done; Meds isn't due yet
+        app.storage.update_habit_reminder(ids[0], at(9, 0)).unwrap();
+        app.storage.update_habit_reminder(ids[1], at(11, 30)).unwrap();
+        app.storage.update_habit_reminder(ids[3], at(20, 0)).unwrap();
+        app.storage.update_log_status(ids[1], today, HabitStatus::Done).unwrap();
+
+        let due: Vec<Uuid> = app.due_reminders().iter().map(|h| h.id).collect();
+        assert_eq!(due, vec![ids[0]]);
+        app.check_reminders();
+        assert_eq!(app.reminders_due, vec!["Shower".to_string()]);
+
+        // Marking it clears the reminder
+        app.storage.update_log_status(ids[0], today, HabitStatus::Skipped).unwrap();
+        app.check_reminders();
+        assert!(app.reminders_due.is_empty());
+    }
+
+    #[test]
+    fn test_cycle_habit_color() {
+        let (mut app, _temp_file) = test_app();
+        app.habit_mgmt_selected_idx = 1;
+        let habit_id = app.habits()[1].id;
+        let color = |app: &App| app.storage.get_habit(habit_id).unwrap().color.clone();
+
+        app.cycle_habit_color().unwrap();
+        assert_eq!(color(&app).as_deref(), Some(HABIT_COLORS[0]));
+        for _ in 1..HABIT_COLORS.len() {
+            app.cycle_habit_color().unwrap();
+        }
+        assert_eq!(color(&app).as_deref(), HABIT_COLORS.last().copied());
+        app.cycle_habit_color().unwrap();
+        assert_eq!(color(&app), None);
+
+        // A hand-picked color isn't in the palette, so cycling starts it over
+        let mut habit = app.storage.get_habit(habit_id).unwrap().clone();
+        habit.color = Some("#ff8800".to_string());
+        app.storage.update_habit(habit).unwrap();
+        app.cycle_habit_color().unwrap();
+        assert_eq!(color(&app).as_deref(), Some(HABIT_COLORS[0]));
+    }
+
+    #[test]
+    fn test_habit_has_note() {
+        let (mut app, _temp_file) = test_app();
+        let days = app.current_week.days();
+        let shower = app.habits()[0].id;
+        let meds = app.habits()[3].id;
+        assert!(!app.habit_has_note(shower, days[1]));
+
+        // A note counts without any status being set
+        app.storage.add_log_note(shower, days[1], app.now_local(), "Cold water".to_string()).unwrap();
+        assert_eq!(app.get_habit_status(shower, days[1]), HabitStatus::Unmarked);
+        assert!(app.habit_has_note(shower, days[1]));
+        assert!(!app.habit_has_note(shower, days[2]));
+        assert!(!app.habit_has_note(meds, days[1]));
+
+        // A blank note doesn't
+        app.storage.add_log_note(meds, days[1], app.now_local(), "  ".to_string()).unwrap();
+        assert!(!app.habit_has_note(meds, days[1]));
+    }
+
+    #[test]
+    fn test_unmet_weekly_habit_is_overdue_next_week() {
+        let (mut app, _temp_file) = test_app();
+        // Trim nails is the default Weekly habit; the test week leaves it undone
+        let trim_nails = app.habits()[2].id;
+        let days = app.current_week.days();
+        let next_week = app.current_week.next().days();
+        assert_eq!(app.overdue_weekly_habits(next_week[0]), vec![trim_nails]);
+        assert_eq!(app.overdue_weekly_habits(next_week[6]), vec![trim_nails]);
+
+        // Doing it this week clears it from that day on
+        app.storage.update_log_status(trim_nails, next_week[2], HabitStatus::Done).unwrap();
+        assert_eq!(app.overdue_weekly_habits(next_week[1]), vec![trim_nails]);
+        assert!(app.overdue_weekly_habits(next_week[2]).is_empty());
+
+        // Done in the prior week means nothing carries over
+        app.storage.update_log_status(trim_nails, days[4], HabitStatus::Done).unwrap();
+        assert!(app.overdue_weekly_habits(next_week[0]).is_empty());
+
+        // A habit added after the prior week can't be overdue from it
+        let mut habit = app.storage.get_habit(trim_nails).unwrap().clone();
+        habit.set_created_at(next_week[0]);
+        app.storage.update_habit(habit).unwrap();
+        app.storage.update_log_status(trim_nails, days[4], HabitStatus::Unmarked).unwrap();
+        assert!(app.overdue_weekly_habits(next_week[0]).is_empty());
+    }
+
+    #[test]
+    fn test_weekly_goal_progress() {
+        let (mut app, _temp_file) = test_app();
+        let days = app.current_week.days();
+        let shower = app.habits()[0].id;
+        assert_eq!(app.weekly_goal_progress(shower), None);
+
+        app.enter_habit_management();
+        app.start_edit_goal();
+        app.input_buffer = "9".to_string();
+        app.save_edited_goal().unwrap();
+        assert_eq!(app.habit_mgmt_mode, HabitMgmtMode::EditGoal);
+        app.input_buffer = "4".to_string();
+        app.save_edited_goal().unwrap();
+        assert_eq!(app.weekly_goal_progress(shower), Some((0, 4)));
+
+        // Only Done counts; Partial and Skipped days don't
+        app.storage.update_log_status(shower, days[0], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(shower, days[1], HabitStatus::Partial).unwrap();
+        app.storage.update_log_status(shower, days[2], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(shower, days[3], HabitStatus::Skipped).unwrap();
+        assert_eq!(app.weekly_goal_progress(shower), Some((2, 4)));
+
+        // Each week counts on its own
+        app.storage.update_log_status(shower, app.current_week.next().start, HabitStatus::Done).unwrap();
+        assert_eq!(app.weekly_goal_progress(shower), Some((2, 4)));
+        app.next_week().unwrap();
+        assert_eq!(app.weekly_goal_progress(shower), Some((1, 4)));
+    }
+
+    #[test]
+    fn test_week_navigation_commits_staged_status() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_day_idx = 3;
+        app.selected_habit_idx = 1;
+        let date = app.selected_date();
+        let brush = app.habits()[1].id;
+        app.toggle_habit_status();
+        assert_eq!(app.storage.get_log(brush, date).map(|log| log.status), None);
+
+        app.next_week().unwrap();
+        assert_eq!(app.staged_status, None);
+        assert_eq!(app.storage.get_log(brush, date).map(|log| log.status), Some(HabitStatus::Done));
+        let moved_to = date + chrono::Duration::days(7);
+        assert_eq!(app.selected_date(), moved_to);
+        assert_eq!(app.get_habit_status(brush, moved_to), HabitStatus::Unmarked);
+        assert_eq!(app.selected_habit_idx, 0);
+
+        app.prev_week().unwrap();
+        assert_eq!(app.selected_date(), date);
+    }
+
+    #[test]
+    fn test_edit_reminder_time() {
+        let (mut app, _temp_file) = test_app();
+        app.enter_habit_management();
+        app.habit_mgmt_selected_idx = 3;
+        let meds = app.habits()[3].id;
+
+        app.start_edit_reminder();
+        app.input_buffer = "25:00".to_string();
+        app.save_edited_reminder().unwrap();
+        assert_eq!(app.habit_mgmt_mode, HabitMgmtMode::EditReminder);
+        assert_eq!(app.storage.get_habit(meds).unwrap().remind_at, None);
+
+        app.input_buffer = "8:30".to_string();
+        app.save_edited_reminder().unwrap();
+        assert_eq!(app.habit_mgmt_mode, HabitMgmtMode::List);
+        assert_eq!(app.storage.get_habit(meds).unwrap().remind_at, chrono::NaiveTime::from_hms_opt(8, 30, 0));
+
+        app.start_edit_reminder();
+        assert_eq!(app.input_buffer, "08:30");
+        app.input_buffer.clear();
+        app.save_edited_reminder().unwrap();
+        assert_eq!(app.storage.get_habit(meds).unwrap().remind_at, None);
+    }
+
+    #[test]
+    fn test_weekly_done_writes_once() {
+        let (mut app, _temp_file) = test_app();
+        // Trim nails is the default Weekly habit; Done on Sunday fills the six days before it
+        let habit_id = app.habits()[2].id;
+        let days = app.current_week.days();
+        let writes = app.storage.write_count();
+
+        app.staged_status = Some((habit_id, days[6], HabitStatus::Done));
+        app.commit_staged_status().unwrap();
+        assert_eq!(app.get_habit_status(habit_id, days[0]), HabitStatus::Skipped);
+        assert_eq!(app.storage.write_count(), writes + 1);
+        assert!(!app.storage.is_dirty());
+    }
+
+    #[test]
+    fn test_select_day_and_habit_commit_staged_status() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_day_idx = 1;
+        app.selected_habit_idx = 2;
+        let habit_id = app.selected_habit().unwrap().id;
+        let date = app.selected_date();
+
+        app.toggle_habit_status();
+        app.select_habit(0).unwrap();
+        assert_eq!(app.staged_status, None);
+        assert_eq!(app.get_habit_status(habit_id, date), HabitStatus::Done);
+        assert_eq!(app.selected_habit_idx, 0);
+
+        // Out-of-range rows are ignored; a new day starts at its first habit
+        app.select_habit(99).unwrap();
+        assert_eq!(app.selected_habit_idx, 0);
+        app.selected_habit_idx = 1;
+        app.select_day(4).unwrap();
+        assert_eq!((app.selected_day_idx, app.selected_habit_idx), (4, 0));
+    }
+
+    #[test]
+    fn test_mark_all_done() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_day_idx = 2;
+        let days = app.current_week.days();
+        let trim_nails = app.habits()[2].id;
+        let meds = app.habits()[3].id;
+        app.storage.update_log_status(meds, days[2], HabitStatus::NotApplicable).unwrap();
+        assert_eq!(app.get_day_status(2), ' ');
+
+        app.mark_all(HabitStatus::Done).unwrap();
+        assert_eq!(app.get_day_status(2), '✓');
+        assert_eq!(app.get_habit_status(meds, days[2]), HabitStatus::NotApplicable);
+        // The Weekly habit fills the days before it as usual
+        assert_eq!(app.get_habit_status(trim_nails, days[0]), HabitStatus::Skipped);
+
+        // The whole day comes back with one undo
+        app.undo().unwrap();
+        assert_eq!(app.get_day_status(2), ' ');
+        assert_eq!(app.get_habit_status(trim_nails, days[0]), HabitStatus::Unmarked);
+    }
+
+    #[test]
+    fn test_mark_all_skipped_respects_note_policy() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_day_idx = 2;
+        let date = app.selected_date();
+        let shower = app.habits()[0].id;
+        app.toggle_require_skip_note().unwrap();
+        app.storage.add_log_note(shower, date, app.now_local(), "Out of hot water".to_string()).unwrap();
+
+        app.mark_all(HabitStatus::Skipped).unwrap();
+        assert_eq!(app.get_habit_status(shower, date), HabitStatus::Skipped);
+        assert_eq!(app.get_habit_status(app.habits()[1].id, date), HabitStatus::Unmarked);
+        assert!(app.flash_message.as_deref().unwrap().contains("need a note"));
+    }
+
+    #[test]
+    fn test_skip_reason_written_to_note() {
+        let (mut app, _temp_file) = test_app();
+        let date = app.selected_date();
+        let shower = app.habits()[0].id;
+        app.set_habit_status(HabitStatus::Skipped).unwrap();
+        app.prompt_skip_reason();
+        assert_eq!(app.view, AppView::Main, "off by default");
+
+        app.toggle_ask_skip_reason().unwrap();
+        app.selected_habit_idx = 3;
+        let meds = app.habits()[3].id;
+        app.toggle_habit_status();
+        app.toggle_habit_status();
+        app.toggle_habit_status();
+        // Moving on commits the skip, then the prompt asks about that habit
+        app.prev_habit().unwrap();
+        app.prompt_skip_reason();
+        assert_eq!(app.view, AppView::NoteInput);
+        assert_eq!(app.note_target(), Some((meds, date)));
+        app.input_buffer = "Ran out, refill tomorrow".to_string();
+        app.save_note_input().unwrap();
+        assert_eq!(app.view, AppView::Main);
+        let log = app.storage.get_log(meds, date).unwrap();
+        assert_eq!(log.status, HabitStatus::Skipped);
+        assert_eq!(log.latest_note(), Some("Skipped: Ran out, refill tomorrow"));
+        assert!(app.export_week_to_markdown().contains("- **Meds**: ✗ Skipped - *Ran out, refill tomorrow*"));
+
+        // Esc leaves the reason blank and keeps the skip
+        app.selected_habit_idx = 1;
+        app.set_habit_status(HabitStatus::Skipped).unwrap();
+        app.prompt_skip_reason();
+        app.cancel_note_input();
+        assert_eq!(app.skip_reason_for, None);
+        assert_eq!(app.get_habit_status(app.habits()[1].id, date), HabitStatus::Skipped);
+        assert!(!app.habit_has_note(app.habits()[1].id, date));
+        assert!(!app.habit_has_note(shower, date));
+    }
+
+    #[test]
+    fn test_bell_rings_once_for_a_single_done() {
+        let (mut app, _temp_file) = test_app();
+        app.set_habit_status(HabitStatus::Done).unwrap();
+        assert!(!app.take_bell(), "bell is off by default");
+
+        app.toggle_bell_on_complete().unwrap();
+        app.selected_habit_idx = 1;
+        app.set_habit_status(HabitStatus::Done).unwrap();
+        assert!(app.take_bell());
+        assert!(!app.take_bell());
+
+        // Already Done, Skipped or Unmarked: quiet
+        app.set_habit_status(HabitStatus::Done).unwrap();
+        app.set_habit_status(HabitStatus::Skipped).unwrap();
+        app.set_habit_status(HabitStatus::Unmarked).unwrap();
+        assert!(!app.take_bell());
+
+        // Marking the whole day is quiet too
+        app.selected_day_idx = 3;
+        app.mark_all(HabitStatus::Done).unwrap();
+        assert!(!app.take_bell());
+    }
+
+    #[test]
+    fn test_parse_date_input() {
+        let today = NaiveDate::from_ymd_opt(2025, 10, 15).unwrap();
+        assert_eq!(parse_date_input("2025-03-09", today), NaiveDate::from_ymd_opt(2025, 3, 9));
+        assert_eq!(parse_date_input(" -7 ", today), NaiveDate::from_ymd_opt(2025, 10, 8));
+        assert_eq!(parse_date_input("+3", today), NaiveDate::from_ymd_opt(2025, 10, 18));
+        assert_eq!(parse_date_input("-0", today), Some(today));
+        assert_eq!(parse_date_input("7", today), None);
+        assert_eq!(parse_date_input("2025-13-01", today), None);
+        assert_eq!(parse_date_input("last week", today), None);
+        assert_eq!(parse_date_input("-99999999999999", today), None);
+    }
+
+    #[test]
+    fn test_go_to_date_prompt() {
+        let (mut app, _temp_file) = test_app();
+        let shower = app.habits()[0].id;
+        app.staged_status = Some((shower, app.selected_date(), HabitStatus::Done));
+        app.start_go_to_date().unwrap();
+        assert_eq!(app.staged_status, None);
+        assert_eq!(app.view, AppView::GoToDate);
+
+        app.input_buffer = "someday".to_string();
+        app.save_go_to_date();
+        assert_eq!(app.view, AppView::GoToDate);
+        assert!(app.flash_message.is_some());
+
+        app.input_buffer = "2025-03-09".to_string();
+        app.save_go_to_date();
+        assert_eq!(app.view, AppView::Main);
+        assert_eq!(app.selected_date(), NaiveDate::from_ymd_opt(2025, 3, 9).unwrap());
+        assert!(app.current_week.days().contains(&app.selected_date()));
+    }
+
+    #[test]
+    fn test_vacation_keys_and_strip() {
+        let (mut app, _temp_file) = test_app();
+        let days = app.current_week.days();
+        let shower = app.habits()[0].id;
+        app.selected_day_idx = 1;
+        app.toggle_vacation().unwrap();
+        app.selected_day_idx = 3;
+        app.end_vacation_here().unwrap();
+        assert_eq!(app.storage.vacations(), &[(days[1], days[3])]);
+
+        assert_eq!(app.get_day_status(2), '·');
+        assert_eq!(app.get_habit_day_status(shower, 2), '·');
+        // What got done on vacation still shows
+        app.storage.update_log_status(shower, days[2], HabitStatus::Done).unwrap();
+        assert_eq!(app.get_day_status(2), '✓');
+        assert_eq!(app.get_day_status(4), ' ');
+
+        // Starting on a vacation day removes it
+        app.selected_day_idx = 2;
+        app.toggle_vacation().unwrap();
+        assert!(app.storage.vacations().is_empty());
+        app.selected_day_idx = 0;
+        app.end_vacation_here().unwrap();
+        assert!(app.flash_message.as_deref().unwrap().contains("No vacation"));
+    }
+
+    #[test]
+    fn test_vacations_are_undoable_and_left_out_of_reports() {
+        let (mut app, _temp_file) = test_app();
+        let days = app.current_week.days();
+        let shower = app.habits()[0].id;
+        app.selected_day_idx = 1;
+        app.toggle_habit_status();
+        app.toggle_vacation().unwrap();
+        // The staged mark was saved first, so undo takes back only the vacation
+        assert_eq!(app.storage.get_log(shower, days[1]).unwrap().status, HabitStatus::Done);
+        app.undo().unwrap();
+        assert!(app.storage.vacations().is_empty());
+        assert_eq!(app.storage.get_log(shower, days[1]).unwrap().status, HabitStatus::Done);
+        app.redo().unwrap();
+        assert_eq!(app.storage.vacations(), &[(days[1], days[1])]);
+
+        app.storage.update_log_status(shower, days[1], HabitStatus::Skipped).unwrap();
+        let report = app.range_report(days[0], days[6]);
+        let totals = report.totals.iter().find(|t| t.habit_id == shower).unwrap();
+        assert_eq!(totals.skipped, 0);
+        assert_eq!(totals.trackable, app.storage.trackable_days(shower, days[0], days[6]));
+    }
+
+    #[test]
+    fn test_weekly_habit_is_not_overdue_after_a_vacation_week() {
+        let (mut app, _temp_file) = test_app();
+        let next_week = app.current_week.next().days();
+        assert!(!app.overdue_weekly_habits(next_week[0]).is_empty());
+        app.storage.add_vacation(app.current_week.start, app.current_week.end()).unwrap();
+        assert!(app.overdue_weekly_habits(next_week[0]).is_empty());
+    }
+
+    #[test]
+    fn test_weekly_auto_fill_can_be_disabled() {
+        let (mut app, _temp_file) = test_app();
+        // Trim nails is the default Weekly habit
+        let habit_id = app.habits()[2].id;
+        let days = app.current_week.days();
+
+        app.staged_status = Some((habit_id, days[3], HabitStatus::Done));
+        app.commit_staged_status().unwrap();
+        assert_eq!(app.get_habit_status(habit_id, days[0]), HabitStatus::Skipped);
+
+        let next_week = app.current_week.next().days();
+        app.habit_mgmt_selected_idx = 2;
+        app.toggle_habit_auto_fill_week().unwrap();
+        assert!(!app.storage.get_habit(habit_id).unwrap().auto_fill_week);
+
+        app.staged_status = Some((habit_id, next_week[3], HabitStatus::Done));
+        app.commit_staged_status().unwrap();
+        assert_eq!(app.get_habit_status(habit_id, next_week[3]), HabitStatus::Done);
+        for &day in &next_week[..3] {
+            assert_eq!(app.get_habit_status(habit_id, day), HabitStatus::Unmarked);
+        }
+    }
+
+    #[test]
+    fn test_week_strip_follows_selected_habit() {
+        let (mut app, _temp_file) = test_app();
+        let days = app.current_week.days();
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+
+        // Only Meds is logged, so the aggregate strip shows partial days
+        app.storage.update_log_status(ids[3], days[0], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(ids[3], days[1], HabitStatus::Skipped).unwrap();
+        app.storage.update_log_status(ids[3], days[2], HabitStatus::Partial).unwrap();
+        let mut habit = app.storage.get_habit(ids[3]).unwrap().clone();
+        habit.skip_weekends = true;
+        app.storage.update_habit(habit).unwrap();
+
+        app.selected_day_idx = 0;
+        app.selected_habit_idx = app.habits_for_date(days[0]).iter().position(|h| h.id == ids[3]).unwrap();
+        assert_eq!(app.week_strip_status(0), '~');
+
+        app.toggle_strip_follows_habit();
+        let strip: String = (0..7).map(|idx| app.week_strip_status(idx)).collect();
+        assert_eq!(strip, "✓✗◐  --");
+    }
+
+    #[test]
+    fn test_partial_day_status() {
+        let (mut app, _temp_file) = test_app();
+        let days = app.current_week.days();
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+        app.set_clock(|| NaiveDate::from_ymd_opt(2030, 1, 1).unwrap().and_hms_opt(12, 0, 0).unwrap());
+
+        // Everything done but one partial: not all done, nothing skipped
+        for &id in &ids {
+            app.storage.update_log_status(id, days[0], HabitStatus::Done).unwrap();
+        }
+        app.storage.update_log_status(ids[0], days[0], HabitStatus::Partial).unwrap();
+        assert_eq!(app.get_day_status(0), '~');
+
+        // Partial counts toward trackable days but not toward Done
+        let stats = app.storage.get_stats(days[0], days[0]);
+        assert_eq!((stats[&ids[0]].done, stats[&ids[0]].partial), (0, 1));
+        assert!(app.export_week_to_markdown().contains("◐ Partial"));
+        assert!(app.export_week_to_text().contains("Partial"));
+    }
+
+    #[test]
+    fn test_trackable_days() {
+        let (mut app, _temp_file) = test_app();
+        let week = app.current_week;
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+
+        // Plain daily habit
+        assert_eq!(app.trackable_days(ids[0], week), 7);
+
+        // Weekend-skipping habit
+        let mut habit = app.storage.get_habit(ids[1]).unwrap().clone();
+        habit.skip_weekends = true;
+        app.storage.update_habit(habit).unwrap();
+        assert_eq!(app.trackable_days(ids[1], week), 5);
+        // A logged weekend day still counts
+        app.storage.update_log_status(ids[1], week.days()[5], HabitStatus::Done).unwrap();
+        assert_eq!(app.trackable_days(ids[1], week), 6);
+
+        // Habit only tracked from Thursday on
+        let mut habit = app.storage.get_habit(ids[3]).unwrap().clone();
+        habit.set_created_at(week.days()[3]);
+        app.storage.update_habit(habit).unwrap();
+        assert_eq!(app.trackable_days(ids[3], week), 4);
+        assert_eq!(app.trackable_days(ids[3], week.next()), 7);
+        assert!(app.export_week_to_markdown().contains("| Meds | 0 | 0 | 0 | 4 | 0% |"));
+    }
+
+    #[test]
+    fn test_toggle_back_undoes_forward_toggle() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_day_idx = 0;
+        let habit_id = app.selected_habit().unwrap().id;
+        let date = app.selected_date();
+
+        app.toggle_habit_status();
+        assert_eq!(app.get_habit_status(habit_id, date), HabitStatus::Done);
+        app.toggle_habit_status_back();
+        assert_eq!(app.get_habit_status(habit_id, date), HabitStatus::Unmarked);
+
+        // Going back from Unmarked lands on N/A, still only staged
+        app.toggle_habit_status_back();
+        assert_eq!(app.staged_status, Some((habit_id, date, HabitStatus::NotApplicable)));
+        assert!(app.storage.get_log(habit_id, date).is_none());
+    }
+
+    #[test]
+    fn test_labels_tell_apart_same_named_habits() {
+        let (mut app, _temp_file) = test_app();
+        app.enter_habit_management();
+        app.habit_mgmt_selected_idx = 3;
+
+        // A second "Meds" is refused while the first has no label
+        app.start_add_habit();
+        app.input_buffer = "Meds".to_string();
+        app.save_new_habit().unwrap();
+        assert_eq!(app.habits().len(), 4);
+        assert!(app.flash_message.is_some());
+
+        app.start_edit_label();
+        app.input_buffer = "AM".to_string();
+        app.save_edited_label().unwrap();
+        app.start_add_habit();
+        app.input_buffer = "Meds".to_string();
+        app.save_new_habit().unwrap();
+        assert_eq!(app.habits().len(), 5);
+
+        app.habit_mgmt_selected_idx = 4;
+        app.start_edit_label();
+        app.input_buffer = "PM".to_string();
+        app.save_edited_label().unwrap();
+
+        let names: Vec<String> = app.habits().iter().map(|h| h.display_name()).collect();
+        assert_eq!(names[3], "Meds (AM)");
+        assert_eq!(names[4], "Meds (PM)");
+        assert!(app.export_week_to_markdown().contains("| Meds (PM) |"));
+    }
+
+    /// Wednesday, October 15, 2025 at noon
+    fn fixed_clock() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 10, 15).unwrap().and_hms_opt(12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_go_to_today_uses_clock() {
+        let (mut app, _temp_file) = test_app();
+        app.set_clock(fixed_clock);
+        app.current_week = Week::containing(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+
+        app.go_to_today();
+        assert_eq!(app.current_week.start, NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
+        assert_eq!(app.selected_day_idx, 2);
+        assert_eq!(app.today(), NaiveDate::from_ymd_opt(2025, 10, 15).unwrap());
+    }
+
+    #[test]
+    fn test_future_days_are_blank_against_clock() {
+        let (mut app, _temp_file) = test_app();
+        app.set_clock(fixed_clock);
+        let days = app.current_week.days();
+        for habit in app.habits().iter().map(|h| h.id).collect::<Vec<_>>() {
+            for &day in &days {
+                app.storage.update_log_status(habit, day, HabitStatus::Done).unwrap();
+            }
+        }
+
+        // Monday-Wednesday show as done; Thursday onwards is the future
+        let strip: String = (0..7).map(|idx| app.get_day_status(idx)).collect();
+        assert_eq!(strip, "✓✓✓    ");
+        assert!(!app.is_future(days[2]));
+        assert!(app.is_future(days[3]));
+        assert!(app.export_week_to_markdown().contains("Generated: October 15, 2025 at 12:00 PM"));
+    }
+
+    #[test]
+    fn test_tag_filter_limits_day_and_strip() {
+        let (mut app, _temp_file) = test_app();
+        app.set_clock(fixed_clock);
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+        for (idx, tags) in [(0, "Hygiene"), (1, "hygiene"), (3, "meds")] {
+            let mut habit = app.storage.get_habit(ids[idx]).unwrap().clone();
+            habit.tags = vec![tags.to_string()];
+            app.storage.update_habit(habit).unwrap();
+        }
+        assert_eq!(app.all_tags(), vec!["Hygiene".to_string(), "meds".to_string()]);
+
+        // Only the tagged habits show, and the day's status only counts them
+        let monday = app.current_week.days()[0];
+        app.storage.update_log_status(ids[0], monday, HabitStatus::Done).unwrap();
+        app.storage.update_log_status(ids[1], monday, HabitStatus::Done).unwrap();
+        assert_eq!(app.get_day_status(0), '~');
+        app.cycle_tag_filter().unwrap();
+        assert_eq!(app.tag_filter.as_deref(), Some("Hygiene"));
+        let visible: Vec<Uuid> = app.habits_for_date(monday).iter().map(|h| h.id).collect();
+        assert_eq!(visible, vec![ids[0], ids[1]]);
+        assert_eq!(app.get_day_status(0), '✓');
+        assert!(app.stats_title().ends_with("#Hygiene"));
+
+        app.cycle_tag_filter().unwrap();
+        assert_eq!(app.habits_for_date(monday).len(), 1);
+        app.cycle_tag_filter().unwrap();
+        assert_eq!(app.tag_filter, None);
+        assert_eq!(app.habits_for_date(monday).len(), 4);
+    }
+
+    #[test]
+    fn test_archive_tag_from_management() {
+        let (mut app, _temp_file) = test_app();
+        app.enter_habit_management();
+        for idx in [0, 1] {
+            app.habit_mgmt_selected_idx = idx;
+            app.start_edit_tags();
+            app.input_buffer = "hygiene, Hygiene,  ".to_string();
+            app.save_edited_tags().unwrap();
+        }
+        assert_eq!(app.habits()[0].tags, vec!["hygiene".to_string()]);
+
+        // Nothing matches: no confirmation step
+        app.start_archive_tag();
+        app.input_buffer = "sleep".to_string();
+        app.request_archive_tag();
+        assert_eq!(app.habit_mgmt_mode, HabitMgmtMode::List);
+        assert_eq!(app.habits().len(), 4);
+
+        app.start_archive_tag();
+        app.input_buffer = "hygiene".to_string();
+        app.request_archive_tag();
+        assert_eq!(app.habit_mgmt_mode, HabitMgmtMode::ConfirmArchiveTag);
+        assert_eq!(app.habits().len(), 4);
+        app.confirm_archive_tag().unwrap();
+
+        assert_eq!(app.flash_message.as_deref(), Some("Archived 2 habits tagged 'hygiene'"));
+        let names: Vec<&str> = app.habits().iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, vec!["Trim nails", "Meds"]);
+    }
+
+    #[test]
+    fn test_review_pairs_for_partial_week() {
+        let (mut app, _temp_file) = test_app();
+        app.set_clock(fixed_clock); // Wednesday
+        let days = app.current_week.days();
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+
+        // Monday fully marked, Tuesday partly, Wednesday untouched
+        for &id in &ids {
+            app.storage.update_log_status(id, days[0], HabitStatus::Done).unwrap();
+        }
+        app.storage.update_log_status(ids[0], days[1], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(ids[1], days[1], HabitStatus::Skipped).unwrap();
+        // Meds is only scheduled every other day from Monday
+        let mut meds = app.storage.get_habit(ids[3]).unwrap().clone();
+        meds.set_frequency(Frequency::EveryNDays(2));
+        meds.set_created_at(days[0]);
+        app.storage.update_habit(meds).unwrap();
+
+        let pairs = app.review_pairs();
+        assert_eq!(pairs, vec![
+            (days[1], ids[2]),
+            (days[2], ids[0]),
+            (days[2], ids[1]),
+            (days[2], ids[2]),
+            (days[2], ids[3]),
+        ]);
+        // Nothing from Thursday on
+        assert!(pairs.iter().all(|&(date, _)| date <= days[2]));
+
+        app.start_review().unwrap();
+        assert_eq!(app.view, AppView::Review);
+        assert_eq!(app.review_current(), Some((days[1], ids[2])));
+        app.review_skip_pair().unwrap();
+        app.review_mark(HabitStatus::Done).unwrap();
+        assert_eq!(app.get_habit_status(ids[0], days[2]), HabitStatus::Done);
+        assert_eq!(app.review_current(), Some((days[2], ids[1])));
+
+        app.finish_review().unwrap();
+        assert_eq!(app.view, AppView::Main);
+        assert_eq!(app.review_pairs().len(), 4);
+    }
+
+    #[test]
+    fn test_edit_in_middle_of_input() {
+        let (mut app, _temp_file) = test_app();
+        app.start_add_habit();
+        for c in "Stréch".chars() {
+            app.input_char(c);
+        }
+        // Fix the typo after the multi-byte 'é'
+        app.input_cursor_left();
+        app.input_cursor_left();
+        app.input_char('t');
+        assert_eq!(app.input_buffer, "Strétch");
+        assert_eq!(app.input_cursor(), 5);
+        app.input_cursor_left();
+        app.input_backspace();
+        app.input_char('e');
+        assert_eq!(app.input_buffer, "Stretch");
+
+        // Delete removes the character under the cursor and keeps the cursor in place
+        app.input_cursor_home();
+        app.input_delete();
+        assert_eq!(app.input_buffer, "tretch");
+        assert_eq!(app.input_cursor(), 0);
+        app.input_char('S');
+        assert_eq!(app.input_buffer, "Stretch");
+    }
+
+    #[test]
+    fn test_backspace_at_start_and_delete_at_end_do_nothing() {
+        let (mut app, _temp_file) = test_app();
+        app.start_add_habit();
+        for c in "Walk".chars() {
+            app.input_char(c);
+        }
+        app.input_cursor_home();
+        app.input_backspace();
+        assert_eq!(app.input_buffer, "Walk");
+        assert_eq!(app.input_cursor(), 0);
+
+        app.input_cursor_end();
+        app.input_delete();
+        assert_eq!(app.input_buffer, "Walk");
+        assert_eq!(app.input_cursor(), 4);
+
+        // The cursor can't move past either end
+        app.input_cursor_right();
+        assert_eq!(app.input_cursor(), 4);
+        for _ in 0..10 {
+            app.input_cursor_left();
+        }
+        assert_eq!(app.input_cursor(), 0);
+    }
+
+    #[test]
+    fn test_insert_snippet_at_cursor() {
+        let (mut app, _temp_file) = test_app();
+        let mut settings = app.storage.settings().clone();
+        settings.snippets = vec!["used grounding".to_string(), "felt anxious".to_string()];
+        app.storage.update_settings(settings).unwrap();
+
+        app.start_note_input();
+        for c in "Today I , it helped".chars() {
+            app.input_char(c);
+        }
+        for _ in 0..", it helped".chars().count() {
+            app.input_cursor_left();
+        }
+        app.open_snippet_picker();
+        app.snippet_picker_next();
+        app.snippet_picker_next(); // stays on the last snippet
+        app.insert_selected_snippet();
+        assert_eq!(app.input_buffer, "Today I felt anxious, it helped");
+        assert_eq!(app.snippet_picker, None);
+
+        // Typing continues right after the snippet
+        app.input_char('!');
+        assert_eq!(app.input_buffer, "Today I felt anxious!, it helped");
+        app.input_backspace();
+        app.input_cursor_home();
+        app.input_char('>');
+        assert_eq!(app.input_buffer, ">Today I felt anxious, it helped");
+    }
+
+    #[test]
+    fn test_save_note_as_snippet_persists() {
+        let (mut app, temp_file) = test_app();
+        app.start_note_input();
+        app.input_buffer = "  used coping skill X ".to_string();
+        app.save_note_as_snippet().unwrap();
+        app.save_note_as_snippet().unwrap();
+        assert_eq!(app.storage.settings().snippets, vec!["used coping skill X".to_string()]);
+
+        app.storage.flush().unwrap();
+        let reopened = App::new(temp_file.path()).unwrap();
+        assert_eq!(reopened.storage.settings().snippets, vec!["used coping skill X".to_string()]);
+    }
+
+    #[test]
+    fn test_empty_day_reason() {
+        let (mut app, _temp_file) = test_app();
+        let start = app.current_week.start;
+
+        // Leave a single habit that is only scheduled every 7 days from Monday
+        for habit in app.habits().iter().skip(1).map(|h| h.id).collect::<Vec<_>>() {
+            app.storage.delete_habit(habit).unwrap();
+        }
+        let mut habit = app.habits()[0].clone();
+        habit.set_frequency(Frequency::EveryNDays(7));
+        habit.set_created_at(start);
+        app.storage.update_habit(habit.clone()).unwrap();
+
+        let tuesday = start.succ_opt().unwrap();
+        assert_eq!(app.empty_day_reason(start), None);
+        assert_eq!(app.empty_day_reason(tuesday), Some(EmptyDay::NoneScheduled));
+
+        // Showing all habits brings it back
+        app.toggle_show_all_habits().unwrap();
+        assert_eq!(app.empty_day_reason(tuesday), None);
+
+        app.storage.delete_habit(habit.id).unwrap();
+        assert_eq!(app.empty_day_reason(tuesday), Some(EmptyDay::NoHabits));
+    }
+
+    #[test]
+    fn test_week_activity_ordering_and_filtering() {
+        let (mut app, _temp_file) = test_app();
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+        let days = app.current_week.days();
+
+        app.storage.update_log_status(ids[3], days[1], HabitStatus::Skipped).unwrap();
+        app.storage.add_log_note(ids[3], days[1], Local::now(), "sick").unwrap();
+        app.storage.update_log_status(ids[0], days[2], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(ids[1], days[1], HabitStatus::Done).unwrap();
+        // Unmarked and out-of-week logs are left out
+        app.storage.update_log_status(ids[2], days[2], HabitStatus::Unmarked).unwrap();
+        app.storage.update_log_status(ids[0], app.current_week.next().start, HabitStatus::Done).unwrap();
+
+        let activity = app.week_activity();
+        assert_eq!(activity.len(), 3);
+        assert_eq!(activity[0].date, days[2]);
+        assert_eq!(activity[0].habit_name, "Shower");
+        assert_eq!(activity[1].date, days[1]);
+        assert_eq!(activity[1].habit_name, "Brush teeth");
+        assert_eq!(activity[2].habit_name, "Meds");
+        assert_eq!(activity[2].status, HabitStatus::Skipped);
+        assert_eq!(activity[2].note, Some("sick".to_string()));
+    }
+
+    #[test]
+    fn test_export_includes_week_reflection() {
+        let (mut app, _temp_file) = test_app();
+        assert!(!app.export_week_to_markdown().contains("## Reflection"));
+
+        app.start_week_note_input().unwrap();
+        assert_eq!(app.view, AppView::WeekNoteInput);
+        for c in "Proud of showing up.".chars() {
+            app.input_char(c);
+        }
+        app.save_week_note_input().unwrap();
+
+        let export = app.export_week_to_markdown();
+        let reflection = export.find("## Reflection\n\nProud of showing up.").unwrap();
+        assert!(reflection < export.find("## Weekly Summary").unwrap());
+    }
+
+    #[test]
+    fn test_export_lists_streaks_and_adherence() {
+        let (mut app, _temp_file) = test_app();
+        let days = app.current_week.days();
+        let habits: Vec<_> = app.habits().into_iter().cloned().collect();
+        for mut habit in habits {
+            habit.created_at = days[0];
+            app.storage.update_habit(habit).unwrap();
+        }
+        let shower = app.habits()[0].id;
+        let teeth = app.habits()[1].id;
+        for &day in &days[2..] {
+            app.storage.update_log_status(shower, day, HabitStatus::Done).unwrap();
+        }
+        app.storage.update_log_status(teeth, days[0], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(teeth, days[1], HabitStatus::Done).unwrap();
+
+        // Streaks run up to the week's last day, adherence covers everything since creation
+        let export = app.export_week_to_markdown();
+        let section = export.find("## Streaks & Adherence").unwrap();
+        assert!(section < export.find("## Daily Breakdown").unwrap());
+        assert!(export.contains("| Shower | 5 days | 71% |"));
+        assert!(export.contains("| Brush teeth | 0 days | 29% |"));
+        assert!(export.contains("| Meds | 0 days | 0% |"));
+
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+        for id in ids {
+            app.storage.delete_habit(id).unwrap();
+        }
+        assert!(!app.export_week_to_markdown().contains("## Streaks & Adherence"));
+    }
+
+    #[test]
+    fn test_set_habit_status_directly() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_habit_idx = 1;
+        let habit_id = app.habits()[1].id;
+        let date = app.selected_date();
+
+        for status in [HabitStatus::Done, HabitStatus::Skipped, HabitStatus::Unmarked] {
+            app.set_habit_status(status).unwrap();
+            assert_eq!(app.staged_status, None);
+            assert_eq!(app.storage.get_log(habit_id, date).unwrap().status, status);
+        }
+    }
+
+    #[test]
+    fn test_export_sets_last_export() {
+        let (mut app, _temp_file) = test_app();
+        app.export_and_show_confirmation().unwrap();
+
+        let last = app.last_export.clone().unwrap();
+        assert_eq!(last.kind, ExportKind::Markdown);
+        assert_eq!(last.message, "Your weekly markdown report has been exported to:");
+        let path = last.path.unwrap();
+        assert!(path.starts_with(&app.export_dir));
+        assert!(path.to_string_lossy().ends_with("habit-report-2025-10-13.md"));
+        assert_eq!(app.view, AppView::ExportConfirmation);
+
+        std::fs::remove_dir_all(&app.export_dir).unwrap();
+    }
+
+    #[test]
+    fn test_export_two_week_range() {
+        let (mut app, _temp_file) = test_app();
+        let meds = app.habits()[3].id;
+        let days = app.current_week.days();
+        let next_monday = app.current_week.next().start;
+        app.storage.update_log_status(meds, days[0], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(meds, next_monday, HabitStatus::Done).unwrap();
+        app.storage.update_log_status(meds, next_monday.succ_opt().unwrap(), HabitStatus::Skipped).unwrap();
+
+        let end = next_monday + chrono::Duration::days(6);
+        let export = app.export_range_to_markdown(days[0], end);
+        assert!(export.contains("**Oct 13, 2025 to Oct 26, 2025**"));
+        assert!(export.contains("## Summary"));
+        assert!(export.contains("| Meds | 2 | 0 | 1 | 11 | 14% |"));
+        assert_eq!(export.matches("### ").count(), 14);
+        assert!(export.contains("### Sunday - October 26, 2025"));
+
+        // The week version is the range version over the viewed week
+        assert_eq!(app.export_week_to_markdown(), app.export_range_to_markdown(days[0], days[6]));
+        assert!(app.export_week_to_markdown().contains("## Weekly Summary"));
+    }
+
+    #[test]
+    fn test_export_range_prompt() {
+        let (mut app, _temp_file) = test_app();
+        app.start_export_preview().unwrap();
+        app.start_export_range_input();
+        assert_eq!(app.input_buffer, "2025-10-13 to 2025-10-19");
+
+        app.input_buffer = "2025-10-31 2025-10-01".to_string();
+        app.save_export_range();
+        assert_eq!(app.view, AppView::ExportRange);
+        assert_eq!(app.export_range, None);
+
+        app.input_buffer = "2025-10-01 to 2025-10-31".to_string();
+        app.save_export_range();
+        assert_eq!(app.view, AppView::ExportPreview);
+        let october = (
+            NaiveDate::from_ymd_opt(2025, 10, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 10, 31).unwrap(),
+        );
+        assert_eq!(app.export_bounds(), october);
+        assert!(app.export_week().contains("### Friday - October 31, 2025"));
+        assert_eq!(app.export_preview, app.export_week());
+
+        // Ranges past the loggable days, or too long to preview, are refused
+        for text in ["2025-10-01 to 9999-12-31", "2023-01-01 to 2025-01-01"] {
+            app.start_export_range_input();
+            app.input_buffer = text.to_string();
+            app.save_export_range();
+            assert_eq!(app.view, AppView::ExportRange);
+            assert_eq!(app.export_bounds(), october);
+        }
+        app.cancel_export_range();
+
+        app.export_and_show_confirmation().unwrap();
+        let last = app.last_export.clone().unwrap();
+        assert_eq!(last.message, "Your markdown report has been exported to:");
+        assert!(last.path.unwrap().ends_with("habit-report-2025-10-01-to-2025-10-31.md"));
+        assert!(ExportKind::is_export_file_name("habit-report-2025-10-01-to-2025-10-31.md"));
+        std::fs::remove_dir_all(&app.export_dir).unwrap();
+
+        // Reopening the export starts from the week again
+        app.start_export_preview().unwrap();
+        assert_eq!(app.export_bounds(), (app.current_week.start, app.current_week.end()));
+    }
+
+    #[test]
+    fn test_json_export_matches_week() {
+        let (mut app, _temp_file) = test_app();
+        let meds = app.habits()[3].id;
+        let days = app.current_week.days();
+        app.storage.update_log_status(meds, days[1], HabitStatus::Done).unwrap();
+
+        app.cycle_export_kind();
+        app.cycle_export_kind();
+        app.cycle_export_kind();
+        assert_eq!(app.export_kind, ExportKind::Json);
+        let export: WeekExport = serde_json::from_str(&app.export_week()).unwrap();
+        assert_eq!(export, app.week_export());
+        assert_eq!(export.export_schema, EXPORT_SCHEMA);
+        assert_eq!(export.week_start, days[0]);
+        assert_eq!(export.habits[3].done, 1);
+        assert_eq!(export.habits[3].completion_rate, 14);
+        assert_eq!(export.days[1].entries[3].status, crate::exports::StatusExport::Done);
+    }
+
+    #[test]
+    fn test_export_retention_keeps_newest() {
+        let (mut app, _temp_file) = test_app();
+        std::fs::create_dir_all(&app.export_dir).unwrap();
+        let base = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        for day in 1..=7 {
+            let path = app.export_dir.join(format!("habit-report-2025-09-{:02}.md", day));
+            let file = std::fs::File::create(&path).unwrap();
+            file.set_modified(base + std::time::Duration::from_secs(day)).unwrap();
+        }
+        std::fs::write(app.export_dir.join("my-notes.md"), "keep me").unwrap();
+
+        assert_eq!(prune_exports(&app.export_dir, 5).unwrap(), 2);
+        let mut names: Vec<String> = std::fs::read_dir(&app.export_dir).unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec![
+            "habit-report-2025-09-03.md",
+            "habit-report-2025-09-04.md",
+            "habit-report-2025-09-05.md",
+            "habit-report-2025-09-06.md",
+            "habit-report-2025-09-07.md",
+            "my-notes.md",
+        ]);
+
+        // Exporting applies the setting and keeps the new file
+        let mut settings = app.storage.settings().clone();
+        settings.export_retention = Some(5);
+        app.storage.update_settings(settings).unwrap();
+        app.export_and_show_confirmation().unwrap();
+        assert!(app.export_dir.join("habit-report-2025-10-13.md").exists());
+        assert!(!app.export_dir.join("habit-report-2025-09-03.md").exists());
+        assert_eq!(std::fs::read_dir(&app.export_dir).unwrap().count(), 6);
+        std::fs::remove_dir_all(&app.export_dir).unwrap();
+    }
+
+    #[test]
+    fn test_habit_detail_gathers_fields() {
+        let (mut app, _temp_file) = test_app();
+        app.set_clock(fixed_clock);
+        let habit_id = app.habits()[3].id;
+        let today = app.today();
+        app.storage.update_habit_tags(habit_id, vec!["health".to_string()]).unwrap();
+        app.storage.update_habit_label(habit_id, Some("AM".to_string())).unwrap();
+        let mut habit = app.storage.get_habit(habit_id).unwrap().clone();
+        habit.description = Some("With breakfast".to_string());
+        habit.set_created_at(today - chrono::Duration::days(9));
+        app.storage.update_habit(habit).unwrap();
+
+        // Done 3 days running, a skip, then done yesterday and today
+        for offset in [9, 8, 7, 1, 0] {
+            app.storage.update_log_status(habit_id, today - chrono::Duration::days(offset), HabitStatus::Done).unwrap();
+        }
+        app.storage.update_log_status(habit_id, today - chrono::Duration::days(6), HabitStatus::Skipped).unwrap();
+
+        let detail = app.habit_detail(habit_id).unwrap();
+        assert_eq!(detail, HabitDetail {
+            name: "Meds (AM)".to_string(),
+            description: Some("With breakfast".to_string()),
+            frequency: "Daily".to_string(),
+            created_at: today - chrono::Duration::days(9),
+            tags: vec!["health".to_string()],
+            current_streak: 2,
+            best_streak: 3,
+            completion_rate: 50, // 5 of 10 days
+            last_done: Some(today),
+        });
+        assert!(app.habit_detail(Uuid::new_v4()).is_none());
+    }
+
+    #[test]
+    fn test_habit_detail_returns_to_opening_view() {
+        let (mut app, _temp_file) = test_app();
+        app.enter_habit_management();
+        app.habit_mgmt_selected_idx = 2;
+        app.open_habit_detail().unwrap();
+        assert_eq!(app.view, AppView::HabitDetail);
+        assert_eq!(app.detail_habit, Some(app.habits()[2].id));
+
+        app.close_habit_detail();
+        assert_eq!(app.view, AppView::HabitManagement);
+        assert_eq!(app.detail_habit, None);
+    }
+
+    #[test]
+    fn test_heatmap_weeks_align_to_weekdays() {
+        let (mut app, _temp_file) = test_app();
+        app.set_clock(fixed_clock);
+        app.selected_habit_idx = 3;
+        let meds = app.habits()[3].id;
+        let new_year = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        app.storage.update_log_status(meds, new_year, HabitStatus::Done).unwrap();
+        app.storage.update_log_status(meds, NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(), HabitStatus::Skipped).unwrap();
+
+        app.open_heatmap().unwrap();
+        assert_eq!(app.view, AppView::Heatmap);
+        assert_eq!((app.heatmap_habit_idx, app.heatmap_year), (3, 2025));
+
+        // 2025 starts on a Wednesday: the first column's Monday and Tuesday are 2024
+        let weeks = app.heatmap_weeks();
+        assert_eq!(weeks.len(), 53);
+        assert_eq!(weeks[0][..3], [None, None, Some(HabitStatus::Done)]);
+        assert_eq!(weeks[1][0], Some(HabitStatus::Skipped));
+        // Days after the clock's Oct 15 are still to come
+        assert!(weeks.last().unwrap().iter().all(Option::is_none));
+
+        app.heatmap_next_habit();
+        assert_eq!(app.heatmap_habit_idx, 0);
+        app.heatmap_change_year(-1);
+        assert_eq!(app.heatmap_year, 2024);
+        app.heatmap_change_year(3);
+        assert_eq!(app.heatmap_year, 2024);
+    }
+
+    #[test]
+    fn test_search_jumps_to_habit_day() {
+        let (mut app, _temp_file) = test_app();
+        let meds = app.habits()[3].id;
+        let date = NaiveDate::from_ymd_opt(2025, 9, 2).unwrap();
+        app.storage.add_log_note(meds, date, Local::now(), "Bad sleep, skipped breakfast").unwrap();
+        app.only_unmarked = true;
+        app.storage.update_log_status(meds, date, HabitStatus::Done).unwrap();
+
+        app.open_search().unwrap();
+        assert_eq!(app.view, AppView::Search);
+        for c in "SLEEP".chars() {
+            app.search_input_char(c);
+        }
+        assert_eq!(app.search_results, None);
+        app.confirm_search().unwrap();
+        assert_eq!(app.search_results.as_ref().map(Vec::len), Some(1));
+
+        // The day is shown with the habit selected, even though the filter would hide it
+        app.confirm_search().unwrap();
+        assert_eq!(app.view, AppView::Main);
+        assert_eq!(app.selected_date(), date);
+        assert_eq!(app.selected_habit().map(|h| h.id), Some(meds));
+        assert_eq!(app.input_buffer, "");
+    }
+
+    #[test]
+    fn test_notes_are_appended_with_times() {
+        let (mut app, _temp_file) = test_app();
+        app.set_clock(fixed_clock);
+        app.selected_habit_idx = 0;
+        let (habit_id, date) = app.note_target().unwrap();
+
+        for text in ["Slept badly", "", "Walked it off"] {
+            app.start_note_input();
+            assert_eq!(app.input_buffer, "");
+            for c in text.chars() {
+                app.input_char(c);
+            }
+            app.save_note_input().unwrap();
+        }
+
+        // The blank note adds nothing; the latest is shown, the rest kept in order
+        let log = app.storage.get_log(habit_id, date).unwrap();
+        assert_eq!(log.notes.len(), 2);
+        assert_eq!(log.notes[0].1, "Slept badly");
+        assert_eq!(log.notes[0].0.naive_local(), fixed_clock());
+        assert_eq!(app.get_current_note().as_deref(), Some("Walked it off"));
+
+        // Undo takes back only the last note
+        app.undo().unwrap();
+        assert_eq!(app.get_current_note().as_deref(), Some("Slept badly"));
+
+        app.open_notes_history().unwrap();
+        assert_eq!(app.view, AppView::NotesHistory);
+        assert_eq!(app.current_notes().len(), 1);
+        app.notes_scroll_down();
+        assert_eq!(app.notes_scroll, 1);
+        app.notes_scroll_down();
+        app.notes_scroll_down();
+        assert_eq!(app.notes_scroll, 2);
+    }
+
+    #[test]
+    fn test_note_input_starts_from_template_on_empty_log() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_habit_idx = 0;
+        let (habit_id, date) = app.note_target().unwrap();
+        app.storage.update_habit_note_template(habit_id, Some("Mood:, Sleep:, Energy:".to_string())).unwrap();
+
+        // Saving the template untouched adds nothing
+        app.start_note_input();
+        assert_eq!(app.input_buffer, "Mood:, Sleep:, Energy:");
+        app.save_note_input().unwrap();
+        assert!(app.storage.get_log(habit_id, date).is_none_or(|log| log.notes.is_empty()));
+
+        app.start_note_input();
+        app.input_buffer = "Mood: good, Sleep: 7h, Energy: ok".to_string();
+        app.save_note_input().unwrap();
+
+        // Once the day has a note, new notes start empty
+        app.start_note_input();
+        assert_eq!(app.input_buffer, "");
+        app.cancel_note_input();
+
+        // Other habits have no template
+        app.selected_habit_idx = 1;
+        app.start_note_input();
+        assert_eq!(app.input_buffer, "");
+    }
+
+    #[test]
+    fn test_note_draft_restored_for_same_target() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("habits.json");
+        let mut app = App::new(&path).unwrap();
+        app.selected_habit_idx = 0;
+        let (habit_id, date) = app.note_target().unwrap();
+
+        app.start_note_input();
+        for c in "Long day".chars() {
+            app.input_char(c);
+        }
+        assert_eq!(app.storage.load_note_draft().unwrap().text, "Long day");
+
+        // The app dies mid-note; a fresh one restores the draft for the same habit and day
+        drop(app);
+        let mut app = App::new(&path).unwrap();
+        app.selected_habit_idx = 1;
+        app.start_note_input();
+        assert_eq!(app.input_buffer, "");
+        app.cancel_note_input();
+        assert_eq!(
+            app.storage.load_note_draft(),
+            Some(NoteDraft { habit_id, date, text: "Long day".to_string() })
+        );
+
+        app.selected_habit_idx = 0;
+        app.start_note_input();
+        assert_eq!(app.input_buffer, "Long day");
+
+        app.save_note_input().unwrap();
+        assert!(app.storage.load_note_draft().is_none());
+        assert_eq!(app.storage.get_log(habit_id, date).unwrap().latest_note(), Some("Long day"));
+    }
+
+    #[test]
+    fn test_habit_name_input_edits_mid_name() {
+        let (mut app, _temp_file) = test_app();
+        app.enter_habit_management();
+
+        // Add: fix a typo in the middle of a new name
+        app.start_add_habit();
+        for c in "Strech".chars() {
+            app.input_char(c);
+        }
+        app.input_cursor_left();
+        app.input_cursor_left();
+        app.input_char('t');
+        assert_eq!(app.input_buffer, "Stretch");
+        app.save_new_habit().unwrap();
+        assert_eq!(app.habits().last().unwrap().name, "Stretch");
+
+        // Edit: delete a character mid-name; the cursor starts at the end again
+        app.habit_mgmt_selected_idx = 1;
+        app.start_edit_habit();
+        assert_eq!(app.input_cursor(), "Brush teeth".len());
+        app.input_cursor_home();
+        for _ in 0..6 {
+            app.input_cursor_right();
+        }
+        app.input_backspace();
+        assert_eq!(app.input_buffer, "Brushteeth");
+        app.input_char('-');
+        app.save_edited_habit().unwrap();
+        assert_eq!(app.habits()[1].name, "Brush-teeth");
+    }
+
+    #[test]
+    fn test_import_applies_only_on_confirm() {
+        let (mut app, _temp_file) = test_app();
+        let other = NamedTempFile::new().unwrap();
+        let mut source = Storage::new(other.path());
+        source.load().unwrap();
+        source.add_habit("Stretch".to_string()).unwrap();
+        source.flush().unwrap();
+
+        app.start_import(other.path()).unwrap();
+        assert_eq!(app.view, AppView::ImportPreview);
+        // The other file's default habits have their own IDs but match by name
+        assert_eq!(app.pending_import.as_ref().unwrap().1.habits_added, vec!["Stretch".to_string()]);
+        app.cancel_import();
+        assert_eq!(app.habits().len(), 4);
+
+        app.start_import(other.path()).unwrap();
+        app.confirm_import().unwrap();
+        assert_eq!(app.view, AppView::Main);
+        assert_eq!(app.habits().len(), 5);
+        assert!(app.pending_import.is_none());
+    }
+
+    #[test]
+    fn test_only_unmarked_filter() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_day_idx = 1; // All four default habits show on Tuesday
+        let date = app.selected_date();
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+        app.storage.update_log_status(ids[0], date, HabitStatus::Done).unwrap();
+        app.storage.update_log_status(ids[1], date, HabitStatus::Skipped).unwrap();
+
+        app.selected_habit_idx = 3;
+        app.toggle_only_unmarked().unwrap();
+        let visible: Vec<Uuid> = app.habits_for_date(date).iter().map(|h| h.id).collect();
+        assert_eq!(visible, vec![ids[2], ids[3]]);
+        assert_eq!(app.selected_habit_idx, 1);
+
+        // Marking the last visible habit drops it; selection stays in bounds
+        app.set_habit_status(HabitStatus::Done).unwrap();
+        assert_eq!(app.habits_for_date(date).len(), 1);
+        assert_eq!(app.selected_habit_idx, 0);
+        assert_eq!(app.selected_habit().unwrap().id, ids[2]);
+
+        app.set_habit_status(HabitStatus::Done).unwrap();
+        assert!(app.selected_habit().is_none());
+        assert_eq!(app.empty_day_reason(date), Some(EmptyDay::AllMarked));
+        // The day's overall status still counts every habit
+        assert_eq!(app.get_day_status(1), '✗');
+
+        app.toggle_only_unmarked().unwrap();
+        assert_eq!(app.habits_for_date(date).len(), 4);
+    }
+
+    #[test]
+    fn test_failed_save_is_reported_and_retried() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let data_dir = temp_dir.path().join("data");
+        let mut app = App::new(data_dir.join("habits.json")).unwrap();
+        app.selected_habit_idx = 0;
+        let habit_id = app.selected_habit().unwrap().id;
+        let date = app.selected_date();
+
+        // The data folder vanishes mid-session: the change stays in memory
+        std::fs::remove_dir_all(&data_dir).unwrap();
+        app.set_habit_status(HabitStatus::Done).unwrap();
+        assert_eq!(app.get_habit_status(habit_id, date), HabitStatus::Done);
+        assert!(app.has_unsaved_changes());
+        app.report_save_error();
+        assert!(app.flash_message.as_deref().unwrap().starts_with("Save failed — will retry"));
+
+        // The next change writes everything out
+        std::fs::create_dir_all(&data_dir).unwrap();
+        app.set_habit_status(HabitStatus::Skipped).unwrap();
+        assert!(!app.has_unsaved_changes());
+        let reloaded = App::new(data_dir.join("habits.json")).unwrap();
+        assert_eq!(reloaded.get_habit_status(habit_id, date), HabitStatus::Skipped);
+    }
+
+    #[test]
+    fn test_palette_query_starts_at_the_cursor_end_after_an_edit() {
+        let (mut app, _temp_file) = test_app();
+        app.habit_mgmt_selected_idx = 0;
+        app.start_edit_description();
+        app.input_char('x');
+        app.input_cursor_left();
+        app.save_edited_description().unwrap();
+
+        app.open_command_palette().unwrap();
+        for c in "exp".chars() {
+            app.palette_input_char(c);
         }
-        Ok(())
+        assert_eq!(app.input_buffer, "exp");
     }
 
-    /// Get the day status symbol for a specific day
-    /// ✓ = All habits done, ✗ = Some skipped, ~ = Partial, space = Unmarked/future
-    pub fn get_day_status(&self, day_idx: usize) -> char {
-        let date = self.current_week.day(day_idx).unwrap();
-        let habits = self.habits_for_date(date);
+    #[test]
+    fn test_lock_warning_choices() {
+        let (mut app, temp_file) = test_app();
+        let lock = crate::storage::lock_path(temp_file.path());
+        std::fs::write(&lock, "424242").unwrap();
 
-        if habits.is_empty() {
-            return ' ';
-        }
+        app.warn_data_locked(424242, lock.clone());
+        assert_eq!(app.view, AppView::LockWarning);
+        app.open_read_only();
+        assert!(app.storage.is_read_only());
+        assert_eq!(std::fs::read_to_string(&lock).unwrap(), "424242");
 
-        let mut done_count = 0;
-        let mut skipped_count = 0;
-        let mut unmarked_count = 0;
+        app.warn_data_locked(424242, lock.clone());
+        app.force_take_lock().unwrap();
+        assert_eq!(app.view, AppView::Main);
+        assert_eq!(std::fs::read_to_string(&lock).unwrap(), std::process::id().to_string());
+        drop(app);
+        assert!(!lock.exists());
+    }
 
-        for habit in &habits {
-            match self.get_habit_status(habit.id, date) {
-                HabitStatus::Done => done_count += 1,
-                HabitStatus::Skipped => skipped_count += 1,
-                HabitStatus::Unmarked => unmarked_count += 1,
-            }
-        }
+    #[test]
+    fn test_import_waits_for_the_lock_warning() {
+        let (mut app, temp_file) = test_app();
+        let lock = crate::storage::lock_path(temp_file.path());
+        std::fs::write(&lock, "424242").unwrap();
+        let other = NamedTempFile::new().unwrap();
+        let mut source = Storage::new(other.path());
+        source.load().unwrap();
+        source.add_habit("Stretch".to_string()).unwrap();
+        source.flush().unwrap();
 
-        // If all unmarked or future date, show space
-        if unmarked_count == habits.len() || date > Local::now().date_naive() {
-            ' '
-        } else if done_count == habits.len() {
-            '✓'
-        } else if skipped_count > 0 {
-            '✗'
-        } else {
-            '~'
-        }
+        app.warn_data_locked(424242, lock.clone());
+        app.start_import(other.path()).unwrap();
+        assert_eq!(app.view, AppView::LockWarning);
+        assert!(app.pending_import.is_none());
+
+        // Read-only: nothing could be saved, so the import is dropped
+        app.open_read_only();
+        assert_eq!(app.view, AppView::Main);
+        assert!(app.pending_import.is_none());
+        assert!(app.flash_message.as_deref().unwrap().contains("wasn't imported"));
+
+        // Taking the lock over carries on to the preview
+        app.storage.set_read_only(false);
+        app.warn_data_locked(424242, lock);
+        app.start_import(other.path()).unwrap();
+        app.force_take_lock().unwrap();
+        assert_eq!(app.view, AppView::ImportPreview);
     }
 
-    /// Change the current view
-    pub fn set_view(&mut self, view: AppView) {
-        self.view = view;
+    #[test]
+    fn test_unreadable_file_is_only_moved_under_the_lock() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("habits.json");
+        let lock = crate::storage::lock_path(&path);
+        std::fs::write(&path, "{\"habits\": [").unwrap();
+
+        // Another copy holds the lock: going read-only leaves the file alone
+        let mut app = App::new(&path).unwrap();
+        app.warn_data_locked(424242, lock.clone());
+        app.open_read_only();
+        assert!(app.flash_message.as_deref().unwrap().contains("couldn't be read"));
+        app.storage.flush().unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"habits\": [");
+
+        // Holding the lock, it's moved aside and a fresh file takes its place
+        let mut app = App::new(&path).unwrap();
+        app.claim_data_lock(lock).unwrap();
+        assert!(app.flash_message.as_deref().unwrap().contains("moved to"));
+        assert!(std::fs::read_to_string(&path).unwrap().contains("Shower"));
     }
 
-    /// Request the app to quit
-    pub fn quit(&mut self) {
-        self.should_quit = true;
+    #[test]
+    fn test_week_start_keeps_selected_day() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_day_idx = 2; // Wednesday Oct 15
+        let wednesday = app.selected_date();
+
+        app.toggle_week_start().unwrap();
+        assert_eq!(app.current_week.start, NaiveDate::from_ymd_opt(2025, 10, 12).unwrap());
+        assert_eq!(app.selected_day_idx, 3);
+        assert_eq!(app.selected_date(), wednesday);
+
+        // Wrapping past the first day lands on the previous Saturday
+        app.selected_day_idx = 0;
+        app.prev_day().unwrap();
+        assert_eq!(app.selected_date(), NaiveDate::from_ymd_opt(2025, 10, 11).unwrap());
+        assert_eq!(app.selected_day_idx, 6);
+
+        app.set_clock(fixed_clock);
+        app.go_to_today();
+        assert_eq!(app.selected_day_idx, 3);
     }
 
-    /// Enter note editing mode
-    pub fn start_note_input(&mut self) {
-        // Load existing note if any
-        self.input_buffer = self.get_current_note().unwrap_or_default();
-        self.view = AppView::NoteInput;
+    #[test]
+    fn test_undo_status_change() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_day_idx = 2;
+        let before = app.storage.snapshot();
+        let shower = app.habits()[0].id;
+
+        app.toggle_habit_status();
+        app.commit_staged_status().unwrap();
+        assert_eq!(app.get_habit_status(shower, app.selected_date()), HabitStatus::Done);
+
+        app.undo().unwrap();
+        assert_eq!(app.storage.snapshot(), before);
+        assert_eq!(app.flash_message.as_deref(), Some("Undid status change"));
+
+        app.redo().unwrap();
+        assert_eq!(app.get_habit_status(shower, app.selected_date()), HabitStatus::Done);
     }
 
-    /// Save the note and return to main view
-    pub fn save_note_input(&mut self) -> Result<()> {
-        let note = if self.input_buffer.trim().is_empty() {
-            None
-        } else {
-            Some(self.input_buffer.trim().to_string())
-        };
-        self.update_current_note(note)?;
-        self.input_buffer.clear();
-        self.view = AppView::Main;
-        Ok(())
+    #[test]
+    fn test_undo_staged_status_and_weekly_auto_fill_together() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_day_idx = 2; // Wednesday: Monday and Tuesday get auto-skipped
+        app.selected_habit_idx = 2; // Trim nails (Weekly)
+        let before = app.storage.snapshot();
+
+        // Undo commits the staged change first, then takes it back in one step
+        app.toggle_habit_status();
+        app.undo().unwrap();
+        assert_eq!(app.storage.snapshot(), before);
+        assert!(app.staged_status.is_none());
     }
 
-    /// Cancel note editing and return to main view
-    pub fn cancel_note_input(&mut self) {
-        self.input_buffer.clear();
-        self.view = AppView::Main;
+    #[test]
+    fn test_undo_note_edit() {
+        let (mut app, _temp_file) = test_app();
+        app.selected_day_idx = 2;
+        let before = app.storage.snapshot();
+
+        app.start_note_input();
+        for c in "rough day".chars() {
+            app.input_char(c);
+        }
+        app.save_note_input().unwrap();
+        assert_eq!(app.get_current_note().as_deref(), Some("rough day"));
+
+        app.undo().unwrap();
+        assert_eq!(app.storage.snapshot(), before);
+        assert_eq!(app.get_current_note(), None);
     }
 
-    /// Handle character input for note editing
-    pub fn input_char(&mut self, c: char) {
-        self.input_buffer.push(c);
+    #[test]
+    fn test_undo_reset_week() {
+        let (mut app, _temp_file) = test_app();
+        let week = app.current_week;
+        let (shower, meds) = (app.habits()[0].id, app.habits()[3].id);
+        app.storage.update_log_status(shower, week.start, HabitStatus::Done).unwrap();
+        app.storage.update_log_status(meds, week.days()[3], HabitStatus::Skipped).unwrap();
+        let before = app.storage.snapshot();
+
+        app.start_reset_week().unwrap();
+        assert_eq!(app.confirm_reset_week().unwrap(), 2);
+        assert!(app.storage.get_log(shower, week.start).is_none());
+        assert!(app.storage.get_log(meds, week.days()[3]).is_none());
+
+        app.undo().unwrap();
+        assert_eq!(app.storage.snapshot(), before);
+        assert_eq!(app.flash_message.as_deref(), Some("Undid reset week"));
+
+        app.redo().unwrap();
+        assert!(app.storage.get_log(shower, week.start).is_none());
+        assert!(app.storage.get_log(meds, week.days()[3]).is_none());
     }
 
-    /// Handle backspace in note editing
-    pub fn input_backspace(&mut self) {
-        self.input_buffer.pop();
+    #[test]
+    fn test_undo_week_note() {
+        let (mut app, _temp_file) = test_app();
+        let week = app.current_week;
+
+        app.start_week_note_input().unwrap();
+        for c in "calm week".chars() {
+            app.input_char(c);
+        }
+        app.save_week_note_input().unwrap();
+        assert_eq!(app.storage.get_week_note(&week), Some("calm week"));
+
+        app.undo().unwrap();
+        assert_eq!(app.storage.get_week_note(&week), None);
+        app.redo().unwrap();
+        assert_eq!(app.storage.get_week_note(&week), Some("calm week"));
     }
 
-    // Habit Management Methods
+    #[test]
+    fn test_undo_habit_changes() {
+        let (mut app, _temp_file) = test_app();
+        app.view = AppView::HabitManagement;
+        let shower = app.habits()[0].id;
+        app.storage.update_log_status(shower, NaiveDate::from_ymd_opt(2025, 10, 13).unwrap(), HabitStatus::Done).unwrap();
 
-    /// Enter habit management view
-    pub fn enter_habit_management(&mut self) {
-        self.habit_mgmt_mode = HabitMgmtMode::List;
-        self.habit_mgmt_selected_idx = 0;
-        self.view = AppView::HabitManagement;
+        let actions: [fn(&mut App); 4] = [
+            |app| {
+                app.start_add_habit();
+                app.input_buffer = "Stretch".to_string();
+                app.save_new_habit().unwrap();
+            },
+            |app| {
+                app.start_edit_habit();
+                app.input_buffer = "Bath".to_string();
+                app.save_edited_habit().unwrap();
+            },
+            |app| app.move_habit_down().unwrap(),
+            |app| app.delete_selected_habit().unwrap(),
+        ];
+        for action in actions {
+            app.habit_mgmt_selected_idx = 0;
+            let before = app.storage.snapshot();
+            action(&mut app);
+            assert_ne!(app.storage.snapshot(), before);
+            app.undo().unwrap();
+            assert_eq!(app.storage.snapshot(), before);
+        }
+
+        // The deleted habit came back with its history
+        assert_eq!(app.habits()[0].id, shower);
+        assert_eq!(app.storage.logs_for_habit(shower).len(), 1);
     }
 
-    /// Start adding a new habit
-    pub fn start_add_habit(&mut self) {
-        self.input_buffer.clear();
-        self.habit_mgmt_mode = HabitMgmtMode::AddHabit;
+    #[test]
+    fn test_new_action_clears_redo() {
+        let (mut app, _temp_file) = test_app();
+        app.toggle_habit_status();
+        app.commit_staged_status().unwrap();
+        app.undo().unwrap();
+        assert_eq!(app.redo_stack.len(), 1);
+
+        app.next_habit().unwrap();
+        app.toggle_habit_status();
+        app.commit_staged_status().unwrap();
+        assert!(app.redo_stack.is_empty());
+        app.redo().unwrap();
+        assert_eq!(app.flash_message.as_deref(), Some("Nothing to redo"));
     }
 
-    /// Start editing the selected habit
-    pub fn start_edit_habit(&mut self) {
-        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
-            self.input_buffer = habit.name.clone();
-            self.habit_mgmt_mode = HabitMgmtMode::EditHabit;
-        }
+    #[test]
+    fn test_habit_settings_are_undone_in_order() {
+        let (mut app, _temp_file) = test_app();
+        app.habit_mgmt_selected_idx = 0;
+        let shower = app.habits()[0].id;
+
+        app.start_edit_habit();
+        app.input_buffer = "Bath".to_string();
+        app.save_edited_habit().unwrap();
+        app.cycle_habit_frequency().unwrap();
+        app.toggle_habit_skip_weekends().unwrap();
+        app.input_buffer = "hygiene".to_string();
+        app.save_edited_tags().unwrap();
+        app.cycle_habit_color().unwrap();
+        assert_eq!(app.undo_stack.len(), 5);
+
+        // Each undo takes back only its own change, newest first
+        app.undo().unwrap();
+        assert_eq!(app.storage.get_habit(shower).unwrap().color, None);
+        app.undo().unwrap();
+        let habit = app.storage.get_habit(shower).unwrap();
+        assert!(habit.tags.is_empty() && habit.skip_weekends);
+        app.undo().unwrap();
+        app.undo().unwrap();
+        let habit = app.storage.get_habit(shower).unwrap();
+        assert_eq!((habit.name.as_str(), &habit.frequency), ("Bath", &Frequency::Daily));
+        app.undo().unwrap();
+        assert_eq!(app.storage.get_habit(shower).unwrap().name, "Shower");
+
+        // Archiving by tag is one undoable change too
+        app.storage.update_habit_tags(shower, vec!["hygiene".to_string()]).unwrap();
+        app.input_buffer = "hygiene".to_string();
+        app.confirm_archive_tag().unwrap();
+        assert_eq!(app.habits().len(), 3);
+        app.undo().unwrap();
+        assert_eq!(app.habits().len(), 4);
     }
 
-    /// Save new habit
-    pub fn save_new_habit(&mut self) -> Result<()> {
-        if !self.input_buffer.trim().is_empty() {
-            self.storage.add_habit(self.input_buffer.trim().to_string())?;
+    #[test]
+    fn test_undo_stack_is_capped() {
+        let (mut app, _temp_file) = test_app();
+        for _ in 0..UNDO_LIMIT + 10 {
+            app.toggle_habit_status();
+            app.commit_staged_status().unwrap();
         }
-        self.input_buffer.clear();
-        self.habit_mgmt_mode = HabitMgmtMode::List;
-        Ok(())
+        assert_eq!(app.undo_stack.len(), UNDO_LIMIT);
     }
 
-    /// Save edited habit
-    pub fn save_edited_habit(&mut self) -> Result<()> {
-        if !self.input_buffer.trim().is_empty() {
-            if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
-                let habit_id = habit.id;
-                self.storage.update_habit_name(habit_id, self.input_buffer.trim().to_string())?;
-            }
-        }
-        self.input_buffer.clear();
-        self.habit_mgmt_mode = HabitMgmtMode::List;
-        Ok(())
+    #[test]
+    fn test_backup_and_restore_replace() {
+        let (mut app, _temp_file) = test_app();
+        app.set_clock(fixed_clock);
+        app.start_backup();
+        let backup = app.export_dir.join("habits-backup-2025-10-15.json");
+        assert_eq!(app.input_buffer, backup.display().to_string());
+        app.save_backup();
+        assert!(backup.exists());
+
+        app.storage.add_habit("Stretch".to_string()).unwrap();
+        app.start_restore();
+        app.input_buffer = backup.display().to_string();
+        app.request_restore();
+        assert_eq!(app.view, AppView::ImportPreview);
+        app.toggle_habit_status();
+        app.commit_staged_status().unwrap();
+        assert!(!app.undo_stack.is_empty());
+        app.confirm_import_replace().unwrap();
+        assert_eq!(app.habits().len(), 4);
+        // Nothing recorded against the replaced data can be undone into it
+        assert!(app.undo_stack.is_empty() && app.redo_stack.is_empty());
+
+        app.start_restore();
+        app.input_buffer = "/nonexistent/backup.json".to_string();
+        app.request_restore();
+        assert_eq!(app.view, AppView::Main);
+        assert!(app.flash_message.as_deref().unwrap().starts_with("Import failed: Failed to read"));
     }
 
-    /// Cancel habit input
-    pub fn cancel_habit_input(&mut self) {
-        self.input_buffer.clear();
-        self.habit_mgmt_mode = HabitMgmtMode::List;
+    #[test]
+    fn test_specific_days_habit_scheduling() {
+        let (mut app, _temp_file) = test_app();
+        let mut meds = app.habits()[3].clone();
+        meds.set_frequency(Frequency::SpecificDays(vec![Weekday::Mon, Weekday::Wed, Weekday::Fri]));
+        app.storage.update_habit(meds.clone()).unwrap();
+        let days = app.current_week.days();
+
+        // Hidden on Tuesday, shown on Monday
+        assert!(!app.habits_for_date(days[1]).iter().any(|h| h.id == meds.id));
+        assert!(app.habits_for_date(days[0]).iter().any(|h| h.id == meds.id));
+
+        app.storage.update_log_status(meds.id, days[0], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(meds.id, days[2], HabitStatus::Skipped).unwrap();
+        let stats = app.storage.get_stats(days[0], days[6]);
+        // Friday is the only scheduled day left unmarked
+        assert_eq!((stats[&meds.id].done, stats[&meds.id].skipped, stats[&meds.id].unmarked), (1, 1, 1));
+        assert_eq!(app.trackable_days(meds.id, app.current_week), 3);
     }
 
-    /// Delete the selected habit
-    pub fn delete_selected_habit(&mut self) -> Result<()> {
-        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
-            let habit_id = habit.id;
-            self.storage.remove_habit(habit_id)?;
-            // Adjust selected index if necessary
-            let habit_count = self.habits().len();
-            if self.habit_mgmt_selected_idx >= habit_count && habit_count > 0 {
-                self.habit_mgmt_selected_idx = habit_count - 1;
-            }
+    #[test]
+    fn test_toggle_habit_day_in_management() {
+        let (mut app, _temp_file) = test_app();
+        app.habit_mgmt_selected_idx = 3;
+        let meds = app.habits()[3].id;
+
+        // Not a specific-days habit yet: digits do nothing
+        app.toggle_habit_day(Weekday::Tue).unwrap();
+        assert_eq!(app.storage.get_habit(meds).unwrap().frequency, Frequency::Daily);
+
+        for _ in 0..3 {
+            app.cycle_habit_frequency().unwrap();
         }
-        Ok(())
+        app.toggle_habit_day(Weekday::Tue).unwrap();
+        app.toggle_habit_day(Weekday::Mon).unwrap();
+        assert_eq!(
+            app.storage.get_habit(meds).unwrap().frequency,
+            Frequency::SpecificDays(vec![Weekday::Tue, Weekday::Wed, Weekday::Fri])
+        );
+        app.cycle_habit_frequency().unwrap();
+        assert_eq!(app.storage.get_habit(meds).unwrap().frequency, Frequency::AsNeeded);
     }
 
-    /// Move selected habit up in the list
-    pub fn move_habit_up(&mut self) -> Result<()> {
-        if self.habit_mgmt_selected_idx > 0 {
-            let habits = self.habits();
-            if let Some(habit) = habits.get(self.habit_mgmt_selected_idx) {
-                let habit_id = habit.id;
-                let new_order = self.habit_mgmt_selected_idx - 1;
-                self.storage.reorder_habit(habit_id, new_order)?;
-                self.habit_mgmt_selected_idx = new_order;
-            }
+    #[test]
+    fn test_day_status_ignores_as_needed_habits() {
+        let (mut app, _temp_file) = test_app();
+        app.set_clock(fixed_clock);
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+        app.storage.update_habit_frequency(ids[3], Frequency::AsNeeded).unwrap();
+        let monday = app.current_week.days()[0];
+        for &id in &ids[..3] {
+            app.storage.update_log_status(id, monday, HabitStatus::Done).unwrap();
         }
-        Ok(())
+
+        // The unmarked As-needed habit is still listed but doesn't hold the day back
+        assert_eq!(app.habits_for_date(monday).len(), 4);
+        assert_eq!(app.get_day_status(0), '✓');
+        app.storage.update_log_status(ids[3], monday, HabitStatus::Skipped).unwrap();
+        assert_eq!(app.get_day_status(0), '✓');
+
+        // Required habits still decide it
+        app.storage.update_log_status(ids[1], monday, HabitStatus::Skipped).unwrap();
+        assert_eq!(app.get_day_status(0), '✗');
+
+        // When only As-needed habits are listed, they are the day
+        app.toggle_as_needed_panel().unwrap();
+        assert_eq!(app.get_day_status(0), '✗');
+        app.storage.update_log_status(ids[3], monday, HabitStatus::Done).unwrap();
+        assert_eq!(app.get_day_status(0), '✓');
     }
 
-    /// Move selected habit down in the list
-    pub fn move_habit_down(&mut self) -> Result<()> {
-        let habit_count = self.habits().len();
-        if self.habit_mgmt_selected_idx < habit_count.saturating_sub(1) {
-            let habits = self.habits();
-            if let Some(habit) = habits.get(self.habit_mgmt_selected_idx) {
-                let habit_id = habit.id;
-                let new_order = self.habit_mgmt_selected_idx + 1;
-                self.storage.reorder_habit(habit_id, new_order)?;
-                self.habit_mgmt_selected_idx = new_order;
-            }
+    #[test]
+    fn test_strict_frequency_mode_across_a_week() {
+        let (mut app, _temp_file) = test_app();
+        let meds = app.habits()[3].id;
+        app.storage.update_habit_frequency(meds, Frequency::AsNeeded).unwrap();
+        let days = app.current_week.days();
+        let names = |app: &App, date| -> Vec<String> {
+            app.habits_for_date(date).iter().map(|h| h.name.clone()).collect()
+        };
+
+        // Off by default: every habit shows every day
+        for &date in &days {
+            assert_eq!(names(&app, date), ["Shower", "Brush teeth", "Trim nails", "Meds"]);
         }
-        Ok(())
-    }
 
-    /// Navigate to previous habit in management view
-    pub fn habit_mgmt_prev(&mut self) {
-        let habit_count = self.habits().len();
-        if habit_count > 0 && self.habit_mgmt_selected_idx > 0 {
-            self.habit_mgmt_selected_idx -= 1;
+        // Strict: Weekly only on the week's first day, As-needed nowhere
+        app.toggle_strict_frequency().unwrap();
+        assert_eq!(names(&app, days[0]), ["Shower", "Brush teeth", "Trim nails"]);
+        for &date in &days[1..] {
+            assert_eq!(names(&app, date), ["Shower", "Brush teeth"]);
         }
-    }
 
-    /// Navigate to next habit in management view
-    pub fn habit_mgmt_next(&mut self) {
-        let habit_count = self.habits().len();
-        if habit_count > 0 && self.habit_mgmt_selected_idx < habit_count - 1 {
-            self.habit_mgmt_selected_idx += 1;
+        // The chosen day moves the Weekly habit
+        let mut settings = app.storage.settings().clone();
+        settings.weekly_day = Some(Weekday::Sat);
+        app.storage.update_settings(settings).unwrap();
+        for (idx, &date) in days.iter().enumerate() {
+            assert_eq!(names(&app, date).contains(&"Trim nails".to_string()), idx == 5);
+        }
+
+        // The As-needed panel lists only As-needed habits, every day, in either mode
+        app.toggle_as_needed_panel().unwrap();
+        for &date in &days {
+            assert_eq!(names(&app, date), ["Meds"]);
         }
+        app.toggle_strict_frequency().unwrap();
+        assert_eq!(names(&app, days[3]), ["Meds"]);
+        app.toggle_as_needed_panel().unwrap();
+        assert_eq!(names(&app, days[3]).len(), 4);
     }
 
-    /// Cycle through frequencies for the selected habit
-    pub fn cycle_habit_frequency(&mut self) -> Result<()> {
-        if let Some(habit) = self.habits().get(self.habit_mgmt_selected_idx) {
-            let habit_id = habit.id;
-            let current_frequency = habit.frequency;
+    #[test]
+    fn test_merge_picked_habit_into_selected() {
+        let (mut app, _temp_file) = test_app();
+        let teeth = app.habits()[1].id;
+        let meds = app.habits()[3].id;
+        let date = app.selected_date();
+        app.storage.update_log_status(teeth, date, HabitStatus::Done).unwrap();
 
-            let new_frequency = match current_frequency {
-                Frequency::Daily => Frequency::Weekly,
-                Frequency::Weekly => Frequency::AsNeeded,
-                Frequency::AsNeeded => Frequency::Daily,
-            };
+        app.habit_mgmt_selected_idx = 1;
+        app.start_merge_habit();
+        assert_eq!(app.habit_mgmt_mode, HabitMgmtMode::MergeHabit);
 
-            self.storage.update_habit_frequency(habit_id, new_frequency)?;
-        }
-        Ok(())
+        // The habit can't be merged into itself
+        app.merge_into_selected_habit().unwrap();
+        assert_eq!(app.habits().len(), 4);
+        assert_eq!(app.habit_mgmt_mode, HabitMgmtMode::MergeHabit);
+
+        app.habit_mgmt_selected_idx = 3;
+        app.merge_into_selected_habit().unwrap();
+        assert_eq!(app.habit_mgmt_mode, HabitMgmtMode::List);
+        assert_eq!(app.merge_source, None);
+        assert_eq!(app.habits().len(), 3);
+        assert_eq!(app.habits()[app.habit_mgmt_selected_idx].id, meds);
+        assert_eq!(app.storage.get_log(meds, date).unwrap().status, HabitStatus::Done);
+        assert_eq!(app.flash_message.as_deref(), Some("Merged Brush teeth into Meds"));
+
+        // One undo brings both habits back as they were
+        app.undo().unwrap();
+        assert_eq!(app.habits().len(), 4);
+        assert_eq!(app.storage.get_log(teeth, date).unwrap().status, HabitStatus::Done);
+        assert!(app.storage.get_log(meds, date).is_none());
     }
 
-    // Export Methods
+    #[test]
+    fn test_edit_description_empty_clears() {
+        let (mut app, _temp_file) = test_app();
+        app.habit_mgmt_selected_idx = 2;
+        let nails = app.habits()[2].id;
+        assert!(app.storage.get_habit(nails).unwrap().description.is_some());
 
-    /// Export the current week's data to markdown format
-    pub fn export_week_to_markdown(&self) -> String {
-        let mut output = String::new();
+        app.start_edit_description();
+        app.input_buffer = "  Fingers and toes ".to_string();
+        app.save_edited_description().unwrap();
+        assert_eq!(app.storage.get_habit(nails).unwrap().description.as_deref(), Some("Fingers and toes"));
 
-        // Header
-        output.push_str(&format!("# Habit Tracking Report\n\n"));
-        output.push_str(&format!("**Week of {}**\n\n", self.current_week.format()));
-        output.push_str(&format!("Generated: {}\n\n", Local::now().format("%B %d, %Y at %I:%M %p")));
+        app.start_edit_description();
+        assert_eq!(app.input_buffer, "Fingers and toes");
+        app.input_buffer = "   ".to_string();
+        app.save_edited_description().unwrap();
+        assert_eq!(app.storage.get_habit(nails).unwrap().description, None);
+        assert_eq!(app.habit_mgmt_mode, HabitMgmtMode::List);
+    }
 
-        // Weekly summary
-        output.push_str("## Weekly Summary\n\n");
-        let days = self.current_week.days();
-        let habits = self.habits();
+    #[test]
+    fn test_month_bounds() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(month_bounds(date(2025, 10, 15)), (date(2025, 10, 1), date(2025, 10, 31)));
+        assert_eq!(month_bounds(date(2025, 10, 1)), (date(2025, 10, 1), date(2025, 10, 31)));
+        assert_eq!(month_bounds(date(2025, 11, 30)), (date(2025, 11, 1), date(2025, 11, 30)));
+        // December rolls into the next year
+        assert_eq!(month_bounds(date(2025, 12, 31)), (date(2025, 12, 1), date(2025, 12, 31)));
+        assert_eq!(month_bounds(date(2026, 1, 1)), (date(2026, 1, 1), date(2026, 1, 31)));
+        // February in leap and common years
+        assert_eq!(month_bounds(date(2024, 2, 29)), (date(2024, 2, 1), date(2024, 2, 29)));
+        assert_eq!(month_bounds(date(2025, 2, 1)), (date(2025, 2, 1), date(2025, 2, 28)));
+    }
 
-        if habits.is_empty() {
-            output.push_str("*No habits tracked this week.*\n\n");
-            return output;
-        }
+    #[test]
+    fn test_stats_range_bounds() {
+        let (mut app, _temp_file) = test_app();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        app.selected_day_idx = 2;
+        assert_eq!(app.stats_bounds(), Some((date(2025, 10, 13), date(2025, 10, 19))));
 
-        // Calculate weekly stats
-        let mut weekly_stats: Vec<(String, usize, usize, usize)> = Vec::new();
-        for habit in &habits {
-            let mut done = 0;
-            let mut skipped = 0;
-            let mut unmarked = 0;
+        app.cycle_stats_range();
+        assert_eq!(app.stats_range, StatsRange::Month);
+        assert_eq!(app.stats_bounds(), Some((date(2025, 10, 1), date(2025, 10, 31))));
+        assert_eq!(app.stats_title(), "Monthly Stats - October 2025");
 
-            for &date in &days {
-                match self.get_habit_status(habit.id, date) {
-                    HabitStatus::Done => done += 1,
-                    HabitStatus::Skipped => skipped += 1,
-                    HabitStatus::Unmarked => unmarked += 1,
-                }
-            }
+        app.cycle_stats_range();
+        assert_eq!(app.stats_bounds(), None);
+        let shower = app.habits()[0].id;
+        app.storage.update_log_status(shower, date(2025, 9, 2), HabitStatus::Done).unwrap();
+        app.storage.update_log_status(shower, date(2025, 10, 14), HabitStatus::Skipped).unwrap();
+        assert_eq!(app.stats_bounds(), Some((date(2025, 9, 2), date(2025, 10, 14))));
+        assert_eq!(app.stats_title(), "All-time Stats - Sep 02, 2025 to Oct 14, 2025");
+
+        app.cycle_stats_range();
+        assert_eq!(app.stats_range, StatsRange::Week);
+    }
+
+    #[test]
+    fn test_week_csv_rows() {
+        let (mut app, _temp_file) = test_app();
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+        let days = app.current_week.days();
+        app.storage.update_log_status(ids[0], days[0], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(ids[3], days[1], HabitStatus::Skipped).unwrap();
+        app.storage.add_log_note(ids[3], days[1], Local::now(), "felt sick, \"rested\"\nall day").unwrap();
+        app.storage.update_habit_name(ids[1], "Teeth, flossing".to_string()).unwrap();
 
-            weekly_stats.push((habit.name.clone(), done, skipped, unmarked));
+        let csv = app.export_week_to_csv();
+        assert_eq!(csv.lines().next(), Some("date,habit,status,note"));
+        assert!(csv.starts_with(
+            "date,habit,status,note\n2025-10-13,Shower,done,\n2025-10-13,\"Teeth, flossing\",unmarked,\n"
+        ));
+        assert!(csv.contains("2025-10-14,Meds,skipped,\"felt sick, \"\"rested\"\"\nall day\"\n"));
+        // A row for every habit on every day, plus the header (the note adds one line break)
+        assert_eq!(csv.lines().count(), 1 + 7 * ids.len() + 1);
+
+        app.export_kind = ExportKind::Json.next();
+        assert_eq!(app.export_week(), csv);
+    }
+
+    #[test]
+    fn test_trend_csv_rows() {
+        let (mut app, _temp_file) = test_app();
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+        let days = app.current_week.days();
+        app.storage.update_log_status(ids[0], days[0], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(ids[0], days[1], HabitStatus::Skipped).unwrap();
+        app.storage.update_habit_name(ids[1], "Teeth, flossing".to_string()).unwrap();
+
+        let csv = app.export_trends_to_csv(3);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 1 + 3 * ids.len());
+        assert_eq!(lines[0], "week_start,habit,done,skipped,total,rate");
+
+        // Oldest week first; weeks without logs are zeros over their trackable days
+        let two_back = app.current_week.prev().prev().start.format("%Y-%m-%d").to_string();
+        assert_eq!(lines[1], format!("{},Shower,0,0,7,0", two_back));
+        assert_eq!(lines[9], "2025-10-13,Shower,1,1,7,14");
+        assert_eq!(lines[10], "2025-10-13,\"Teeth, flossing\",0,0,7,0");
+    }
+
+    #[test]
+    fn test_monthly_trend_csv_rows() {
+        let (mut app, _temp_file) = test_app();
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+        let days = app.current_week.days();
+        app.storage.update_log_status(ids[0], days[0], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(ids[0], days[1], HabitStatus::Skipped).unwrap();
+        for id in &ids {
+            let mut habit = app.storage.get_habit(*id).unwrap().clone();
+            habit.set_created_at(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+            app.storage.update_habit(habit).unwrap();
         }
 
-        // Display stats table
-        output.push_str("| Habit | Done | Skipped | Unmarked | Completion Rate |\n");
-        output.push_str("|-------|------|---------|----------|------------------|\n");
+        let csv = app.export_monthly_trends_to_csv(2);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 1 + 2 * ids.len());
+        assert_eq!(lines[0], "month,habit,done,skipped,total,rate");
+        // Oldest month first, over every trackable day of the month
+        assert_eq!(lines[1], "2025-09,Shower,0,0,30,0");
+        assert_eq!(lines[5], "2025-10,Shower,1,1,31,3");
+    }
 
-        for (name, done, skipped, unmarked) in &weekly_stats {
-            let total_tracked = done + skipped;
-            let rate = if total_tracked > 0 {
-                ((*done as f64 / total_tracked as f64) * 100.0) as usize
-            } else {
-                0
-            };
-            output.push_str(&format!("| {} | {} | {} | {} | {}% |\n",
-                name, done, skipped, unmarked, rate));
+    #[test]
+    fn test_text_export_has_no_markdown_markers() {
+        let (mut app, _temp_file) = test_app();
+        let ids: Vec<Uuid> = app.habits().iter().map(|h| h.id).collect();
+        let days = app.current_week.days();
+        app.storage.update_log_status(ids[0], days[0], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(ids[3], days[1], HabitStatus::Skipped).unwrap();
+        app.storage.add_log_note(ids[3], days[1], Local::now(), "sick").unwrap();
+
+        let text = app.export_week_to_text();
+        for marker in ['|', '#', '*'] {
+            assert!(!text.contains(marker), "found {:?} in:\n{}", marker, text);
         }
-        output.push_str("\n");
+        for habit in app.habits() {
+            assert!(text.contains(&habit.name));
+        }
+        assert!(text.contains("Shower: Done"));
+        assert!(text.contains("Meds: Skipped"));
+        assert!(text.contains("Note: sick"));
+        assert!(text.contains("Not tracked"));
+    }
 
-        // Daily breakdown
-        output.push_str("## Daily Breakdown\n\n");
+    #[test]
+    fn test_html_export_escapes_text() {
+        let (mut app, _temp_file) = test_app();
+        let days = app.current_week.days();
+        let shower = app.habits()[0].id;
+        let mut habit = app.habits()[0].clone();
+        habit.name = "Tea & <toast>".to_string();
+        app.storage.update_habit(habit).unwrap();
+        app.storage.update_log_status(shower, days[0], HabitStatus::Done).unwrap();
+        app.storage.add_log_note(shower, days[0], Local::now(), "ate <b>two</b> & \"slept\"").unwrap();
 
-        for &date in &days {
-            let weekday = Week::full_weekday_name(date);
-            output.push_str(&format!("### {} - {}\n\n", weekday, date.format("%B %d, %Y")));
+        let html = app.export_week_to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("Tea &amp; &lt;toast&gt;"));
+        assert!(html.contains("ate &lt;b&gt;two&lt;/b&gt; &amp; &quot;slept&quot;"));
+        assert!(!html.contains("<toast>") && !html.contains("<b>two"));
+        assert!(html.contains("<td class=\"done\">✓</td>"));
+        assert!(html.contains("<td>14%</td>"));
 
-            let mut has_activity = false;
+        app.export_kind = ExportKind::Html;
+        assert_eq!(app.export_week(), html);
+        assert!(ExportKind::is_export_file_name("habit-report-2025-10-13.html"));
+    }
 
-            for habit in &habits {
-                let status = self.get_habit_status(habit.id, date);
-                let status_str = match status {
-                    HabitStatus::Done => "✓ Done",
-                    HabitStatus::Skipped => "✗ Skipped",
-                    HabitStatus::Unmarked => "○ Not tracked",
-                };
+    #[test]
+    fn test_cycle_export_kind_writes_text_file() {
+        let (mut app, _temp_file) = test_app();
+        app.cycle_export_kind();
+        assert_eq!(app.export_kind, ExportKind::PlainText);
+        assert_eq!(app.export_week(), app.export_week_to_text());
 
-                output.push_str(&format!("- **{}**: {}\n", habit.name, status_str));
+        app.export_and_show_confirmation().unwrap();
+        let last = app.last_export.clone().unwrap();
+        assert_eq!(last.kind, ExportKind::PlainText);
+        assert!(last.path.unwrap().to_string_lossy().ends_with("habit-report-2025-10-13.txt"));
 
-                // Include notes if present
-                if let Some(log) = self.storage.get_log(habit.id, date) {
-                    if let Some(note) = &log.note {
-                        if !note.trim().is_empty() {
-                            output.push_str(&format!("  *Note: {}*\n", note));
-                            has_activity = true;
-                        }
-                    }
-                }
+        std::fs::remove_dir_all(&app.export_dir).unwrap();
+    }
 
-                if status != HabitStatus::Unmarked {
-                    has_activity = true;
-                }
-            }
+    #[test]
+    fn test_last_export_message_without_file() {
+        let last = LastExport::new(ExportKind::Markdown, None, true);
+        assert_eq!(last.message, "Your weekly markdown report is ready.");
+    }
 
-            if !has_activity {
-                output.push_str("*No activity recorded for this day.*\n");
-            }
+    #[test]
+    fn test_skip_weekends_hides_habit_and_shrinks_stats() {
+        let (mut app, _temp_file) = test_app();
+        app.habit_mgmt_selected_idx = 0;
+        app.toggle_habit_skip_weekends().unwrap();
+        let habit_id = app.habits()[0].id;
+        let days = app.current_week.days();
 
-            output.push_str("\n");
+        for (idx, &day) in days.iter().enumerate() {
+            let visible = app.habits_for_date(day).iter().any(|h| h.id == habit_id);
+            assert_eq!(visible, idx < 5, "day {}", idx);
         }
 
-        // Footer
-        output.push_str("---\n\n");
-        output.push_str("*Report generated by Healing-Habits habit tracker*\n");
-
-        output
+        app.storage.update_log_status(habit_id, days[0], HabitStatus::Done).unwrap();
+        let stats = app.storage.get_stats(app.current_week.start, app.current_week.end());
+        let stats = stats[&habit_id];
+        assert_eq!((stats.done, stats.skipped, stats.unmarked), (1, 0, 4));
     }
 
-    /// Export current week and save to file
-    pub fn export_and_show_confirmation(&mut self) -> Result<()> {
-        let markdown = self.export_week_to_markdown();
+    #[test]
+    fn test_recent_status_strip() {
+        let (mut app, _temp_file) = test_app();
+        let habit_id = app.habits()[0].id;
+        let today = NaiveDate::from_ymd_opt(2025, 10, 15).unwrap();
 
-        // Determine export directory
-        let export_dir = dirs::home_dir()
-            .unwrap_or_else(|| std::env::current_dir().unwrap())
-            .join("Documents")
-            .join("healing-habits-exports");
+        app.storage.update_log_status(habit_id, today, HabitStatus::Done).unwrap();
+        app.storage.update_log_status(habit_id, today - chrono::Duration::days(1), HabitStatus::Done).unwrap();
+        app.storage.update_log_status(habit_id, today - chrono::Duration::days(2), HabitStatus::Skipped).unwrap();
+        app.storage.update_log_status(habit_id, today - chrono::Duration::days(6), HabitStatus::Done).unwrap();
+        // Outside the 7-day window
+        app.storage.update_log_status(habit_id, today - chrono::Duration::days(7), HabitStatus::Skipped).unwrap();
 
-        std::fs::create_dir_all(&export_dir)
-            .context("Failed to create export directory")?;
+        assert_eq!(app.recent_status_strip(habit_id, today), "✓···✗✓✓");
+    }
 
-        // Generate filename with date
-        let filename = format!(
-            "habit-report-{}.md",
-            self.current_week.days()[0].format("%Y-%m-%d")
-        );
-        let file_path = export_dir.join(filename);
+    #[test]
+    fn test_export_preview_writes_only_on_confirm() {
+        let (mut app, _temp_file) = test_app();
 
-        std::fs::write(&file_path, markdown)
-            .context("Failed to write export file")?;
+        app.start_export_preview().unwrap();
+        assert_eq!(app.view, AppView::ExportPreview);
+        assert!(!app.export_dir.exists());
 
-        self.last_export_path = Some(file_path);
-        self.view = AppView::ExportConfirmation;
+        // Cancelling leaves nothing behind
+        app.set_view(AppView::Main);
+        assert!(!app.export_dir.exists());
 
-        Ok(())
+        app.start_export_preview().unwrap();
+        app.confirm_export_preview().unwrap();
+        assert_eq!(app.view, AppView::ExportConfirmation);
+        let path = app.last_export.as_ref().unwrap().path.clone().unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().next(), Some("# Habit Tracking Report"));
+
+        std::fs::remove_dir_all(&app.export_dir).unwrap();
     }
 }
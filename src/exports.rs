@@ -0,0 +1,158 @@
+// Serializable shapes for structured (JSON) exports
+//
+// These types are the contract with anything consuming an export. They are kept
+// separate from the internal models so refactoring storage doesn't change the
+// output; any breaking change to them must bump `EXPORT_SCHEMA`.
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::HabitStatus;
+
+/// Version of the export layout, written as the top-level `export_schema` field
+pub const EXPORT_SCHEMA: u32 = 3;
+
+/// A week of habit tracking, as exported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeekExport {
+    /// Always `EXPORT_SCHEMA` at the time of writing
+    pub export_schema: u32,
+    /// First day of the exported week (a Monday unless weeks start on Sunday)
+    pub week_start: NaiveDate,
+    /// Last day of the exported week
+    pub week_end: NaiveDate,
+    /// When the export was produced, e.g. "October 15, 2025 at 12:00 PM"
+    pub generated: String,
+    /// Reflection note for the whole week, if one was written
+    pub reflection: Option<String>,
+    /// Per-habit totals for the week
+    pub habits: Vec<HabitTotalsExport>,
+    /// Every day of the week, in order from its first day
+    pub days: Vec<DayExport>,
+}
+
+/// One habit's totals for the exported week
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HabitTotalsExport {
+    /// Stable habit identifier
+    pub habit_id: Uuid,
+    /// Name as displayed, including any label, e.g. "Meds (AM)"
+    pub name: String,
+    /// Days marked done
+    pub done: usize,
+    /// Days marked partly done
+    pub partial: usize,
+    /// Days marked skipped
+    pub skipped: usize,
+    /// Trackable days left unmarked
+    pub unmarked: usize,
+    /// Days that count toward the completion rate
+    pub trackable_days: usize,
+    /// Whole-number percentage of trackable days done, rounded half-up
+    pub completion_rate: usize,
+}
+
+/// One day of the exported week
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayExport {
+    /// The day
+    pub date: NaiveDate,
+    /// One entry per habit
+    pub entries: Vec<EntryExport>,
+}
+
+/// One habit's status on a day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryExport {
+    /// Stable habit identifier
+    pub habit_id: Uuid,
+    /// Name as displayed, including any label
+    pub name: String,
+    /// Recorded status
+    pub status: StatusExport,
+    /// Note attached to the day, if any
+    pub note: Option<String>,
+}
+
+/// Status of a habit on a day, as exported (`"done"`, `"partial"`, `"skipped"`, `"n/a"` or `"unmarked"`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusExport {
+    Done,
+    Partial,
+    Skipped,
+    #[serde(rename = "n/a")]
+    NotApplicable,
+    Unmarked,
+}
+
+impl StatusExport {
+    /// The status as written in exports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusExport::Done => "done",
+            StatusExport::Partial => "partial",
+            StatusExport::Skipped => "skipped",
+            StatusExport::NotApplicable => "n/a",
+            StatusExport::Unmarked => "unmarked",
+        }
+    }
+}
+
+impl From<HabitStatus> for StatusExport {
+    fn from(status: HabitStatus) -> Self {
+        match status {
+            HabitStatus::Done => StatusExport::Done,
+            HabitStatus::Partial => StatusExport::Partial,
+            HabitStatus::Skipped => StatusExport::Skipped,
+            HabitStatus::NotApplicable => StatusExport::NotApplicable,
+            HabitStatus::Unmarked => StatusExport::Unmarked,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_week_export_round_trip() {
+        let monday = NaiveDate::from_ymd_opt(2025, 10, 13).unwrap();
+        let habit_id = Uuid::new_v4();
+        let export = WeekExport {
+            export_schema: EXPORT_SCHEMA,
+            week_start: monday,
+            week_end: NaiveDate::from_ymd_opt(2025, 10, 19).unwrap(),
+            generated: "October 15, 2025 at 12:00 PM".to_string(),
+            reflection: Some("Steady week".to_string()),
+            habits: vec![HabitTotalsExport {
+                habit_id,
+                name: "Meds (AM)".to_string(),
+                done: 1,
+                partial: 0,
+                skipped: 0,
+                unmarked: 6,
+                trackable_days: 7,
+                completion_rate: 14,
+            }],
+            days: vec![DayExport {
+                date: monday,
+                entries: vec![EntryExport {
+                    habit_id,
+                    name: "Meds (AM)".to_string(),
+                    status: HabitStatus::Done.into(),
+                    note: None,
+                }],
+            }],
+        };
+
+        let json = serde_json::to_string(&export).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["export_schema"], EXPORT_SCHEMA);
+        assert_eq!(value["days"][0]["entries"][0]["status"], "done");
+        assert_eq!(value["week_start"], "2025-10-13");
+
+        let parsed: WeekExport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, export);
+    }
+}
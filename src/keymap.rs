@@ -0,0 +1,348 @@
+// Key bindings for the main, grid and stats views, loaded from keys.json next to the data file
+use crossterm::event::KeyCode;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Something a key can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Cancel,
+    PrevDay,
+    NextDay,
+    PrevHabit,
+    NextHabit,
+    PageUpHabits,
+    PageDownHabits,
+    ToggleStatus,
+    ToggleStatusBack,
+    SetDone,
+    SetPartial,
+    SetSkipped,
+    SetUnmarked,
+    SetNotApplicable,
+    MarkAllDone,
+    MarkAllSkipped,
+    StartVacation,
+    EndVacation,
+    EditNote,
+    ViewNotes,
+    GoToToday,
+    PrevWeek,
+    NextWeek,
+    FirstActivity,
+    LastActivity,
+    GoToDate,
+    ViewStats,
+    ViewActivity,
+    ManageHabits,
+    HabitDetail,
+    WeekNote,
+    Grid,
+    Heatmap,
+    ShowAllHabits,
+    OnlyUnmarked,
+    AsNeededPanel,
+    CycleTagFilter,
+    StripFollowsHabit,
+    CommandPalette,
+    Search,
+    ResetWeek,
+    Review,
+    Undo,
+    Export,
+    Help,
+}
+
+/// Keys vim mode puts in front of the map: h/l for days, k/j for habits, m for habit management
+const VIM_KEYS: [(KeyCode, Action); 5] = [
+    (KeyCode::Char('h'), Action::PrevDay),
+    (KeyCode::Char('l'), Action::NextDay),
+    (KeyCode::Char('k'), Action::PrevHabit),
+    (KeyCode::Char('j'), Action::NextHabit),
+    (KeyCode::Char('m'), Action::ManageHabits),
+];
+
+/// Actions a key map can't leave without a key, or there'd be no way out
+const REQUIRED: [Action; 2] = [Action::Quit, Action::Cancel];
+
+/// Which keys trigger which actions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMap {
+    bindings: Vec<(Action, Vec<KeyCode>)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        use Action::*;
+        use KeyCode::*;
+        let bindings = vec![
+            (Quit, vec![Char('q')]),
+            (Cancel, vec![Esc]),
+            (PrevDay, vec![Left]),
+            (NextDay, vec![Right]),
+            (PrevHabit, vec![Up]),
+            (NextHabit, vec![Down]),
+            (ToggleStatus, vec![Char(' '), Enter]),
+            (ToggleStatusBack, vec![BackTab, Char('-')]),
+            (SetDone, vec![Char('D')]),
+            (SetPartial, vec![Char('P')]),
+            (SetSkipped, vec![Char('S')]),
+            (SetUnmarked, vec![Char('U')]),
+            (SetNotApplicable, vec![Char('N')]),
+            (MarkAllDone, vec![Char('A')]),
+            (MarkAllSkipped, vec![Char('X')]),
+            (StartVacation, vec![Char('V')]),
+            (EndVacation, vec![Char('E')]),
+            (EditNote, vec![Char('n')]),
+            (ViewNotes, vec![Char('J')]),
+            (GoToToday, vec![Char('t'), Char('w')]),
+            (PageUpHabits, vec![PageUp]),
+            (PageDownHabits, vec![PageDown]),
+            (PrevWeek, vec![Char('[')]),
+            (NextWeek, vec![Char(']')]),
+            (FirstActivity, vec![Home]),
+            (LastActivity, vec![End]),
+            (GoToDate, vec![Char('G')]),
+            (ViewStats, vec![Char('v')]),
+            (ViewActivity, vec![Char('a')]),
+            (ManageHabits, vec![Char('h')]),
+            (HabitDetail, vec![Char('i')]),
+            (WeekNote, vec![Char('W')]),
+            (Grid, vec![Char('g')]),
+            (Heatmap, vec![Char('y')]),
+            (ShowAllHabits, vec![Char('*')]),
+            (OnlyUnmarked, vec![Char('o')]),
+            (AsNeededPanel, vec![Char('O')]),
+            (CycleTagFilter, vec![Char('#')]),
+            (StripFollowsHabit, vec![Char('p')]),
+            (CommandPalette, vec![Char(':')]),
+            (Search, vec![Char('/')]),
+            (ResetWeek, vec![Char('R')]),
+            (Review, vec![Char('r')]),
+            (Undo, vec![Char('u')]),
+            (Export, vec![Char('x')]),
+            (Help, vec![Char('?')]),
+        ];
+        Self { bindings }
+    }
+}
+
+impl KeyMap {
+    /// Load a key map file, falling back to the defaults if it's missing or invalid
+    /// The warning (if any) explains why a file that exists wasn't used.
+    pub fn load(path: &Path) -> (Self, Option<String>) {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return (Self::default(), None);
+        };
+        match Self::from_json(&contents) {
+            Ok(keymap) => (keymap, None),
+            Err(problem) => (
+                Self::default(),
+                Some(format!("Ignoring {} ({}) - using the default keys", path.display(), problem)),
+            ),
+        }
+    }
+
+    /// Default map with the actions named in `json` rebound,
+    /// e.g. `{"next_day": ["Right", "l"], "manage_habits": ["m"]}`
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let overrides: HashMap<Action, Vec<String>> = serde_json::from_str(json)
+            .map_err(|err| err.to_string())?;
+
+        let mut keymap = Self::default();
+        for (action, names) in overrides {
+            let keys = names.iter()
+                .map(|name| parse_key(name).ok_or_else(|| format!("unknown key '{}'", name)))
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some((_, bound)) = keymap.bindings.iter_mut().find(|(a, _)| *a == action) {
+                *bound = keys;
+            }
+        }
+
+        for action in REQUIRED {
+            if keymap.keys(action, false).is_empty() {
+                return Err(format!("{:?} needs at least one key", action));
+            }
+        }
+
+        // A key can only do one thing
+        let mut seen: HashMap<KeyCode, Action> = HashMap::new();
+        for (action, keys) in &keymap.bindings {
+            for key in keys {
+                if let Some(other) = seen.insert(*key, *action) {
+                    return Err(format!("'{}' is bound to both {:?} and {:?}", key_label(*key), other, action));
+                }
+            }
+        }
+        Ok(keymap)
+    }
+
+    /// Keys this map binds that vim mode would take over, described for a warning
+    /// Only keys that differ from the defaults count; vim mode is meant to replace `h`.
+    pub fn vim_conflicts(&self) -> Option<String> {
+        let defaults = Self::default();
+        let taken: Vec<String> = VIM_KEYS.iter()
+            .filter_map(|&(key, vim_action)| {
+                let bound = self.action(key, false)?;
+                let custom = bound != vim_action && defaults.action(key, false) != Some(bound);
+                custom.then(|| format!("'{}' from {:?}", key_label(key), bound))
+            })
+            .collect();
+        if taken.is_empty() {
+            return None;
+        }
+        Some(format!("Vim keys take {}", taken.join(", ")))
+    }
+
+    /// Action for a key press, with vim mode's keys taking precedence when on
+    pub fn action(&self, key: KeyCode, vim: bool) -> Option<Action> {
+        let vim_action = VIM_KEYS.iter().filter(|_| vim).find(|(k, _)| *k == key);
+        if let Some((_, action)) = vim_action {
+            return Some(*action);
+        }
+        self.bindings.iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(action, _)| *action)
+    }
+
+    /// Keys that trigger an action, vim mode's first
+    pub fn keys(&self, action: Action, vim: bool) -> Vec<KeyCode> {
+        let bound = self.bindings.iter()
+            .filter(|(a, _)| *a == action)
+            .flat_map(|(_, keys)| keys.iter().copied());
+        let keys: Vec<KeyCode> = if vim {
+            VIM_KEYS.iter().filter(|(_, a)| *a == action).map(|(k, _)| *k).chain(bound).collect()
+        } else {
+            bound.collect()
+        };
+        // Drop keys vim mode has taken for something else
+        keys.into_iter().filter(|key| self.action(*key, vim) == Some(action)).collect()
+    }
+
+    /// How an action's keys are shown in help, e.g. "Space / Enter"
+    pub fn label(&self, action: Action, vim: bool) -> String {
+        let keys = self.keys(action, vim);
+        if keys.is_empty() {
+            return "(unbound)".to_string();
+        }
+        keys.into_iter().map(key_label).collect::<Vec<_>>().join(" / ")
+    }
+
+    /// The first key for an action, for compact hints like the footer
+    pub fn short_label(&self, action: Action, vim: bool) -> String {
+        self.keys(action, vim).first().map_or("-".to_string(), |key| key_label(*key))
+    }
+}
+
+/// Parse a key name from keys.json: a single character, or a name such as "Left" or "Enter"
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    let key = match name.to_lowercase().as_str() {
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "enter" => KeyCode::Enter,
+        "space" => KeyCode::Char(' '),
+        "esc" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" | "shift+tab" => KeyCode::BackTab,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// A key as shown on screen
+pub fn key_label(key: KeyCode) -> String {
+    match key {
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::BackTab => "Shift+Tab".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Key map file kept alongside a data file, e.g. `keys.json` next to `habits.json`
+pub fn keymap_path(data_path: &Path) -> PathBuf {
+    data_path.with_file_name("keys.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_map_matches_builtin_keys() {
+        let keymap = KeyMap::default();
+        assert_eq!(keymap.action(KeyCode::Char(' '), false), Some(Action::ToggleStatus));
+        assert_eq!(keymap.action(KeyCode::Char('h'), false), Some(Action::ManageHabits));
+        assert_eq!(keymap.action(KeyCode::Char('w'), false), Some(Action::GoToToday));
+        assert_eq!(keymap.action(KeyCode::Char('m'), false), None);
+        assert_eq!(keymap.label(Action::ToggleStatus, false), "Space / Enter");
+    }
+
+    #[test]
+    fn test_custom_map_routes_keys() {
+        let keymap = KeyMap::from_json(r#"{"next_day": ["Right", "n"], "edit_note": ["e"], "manage_habits": ["M"]}"#).unwrap();
+
+        assert_eq!(keymap.action(KeyCode::Char('n'), false), Some(Action::NextDay));
+        assert_eq!(keymap.action(KeyCode::Right, false), Some(Action::NextDay));
+        assert_eq!(keymap.action(KeyCode::Char('e'), false), Some(Action::EditNote));
+        assert_eq!(keymap.action(KeyCode::Char('M'), false), Some(Action::ManageHabits));
+        assert_eq!(keymap.action(KeyCode::Char('h'), false), None);
+    }
+
+    #[test]
+    fn test_invalid_maps_are_rejected() {
+        // Key already used by another action
+        let err = KeyMap::from_json(r#"{"view_stats": ["q"]}"#).unwrap_err();
+        assert!(err.contains("bound to both"));
+        // Unknown key name and unknown action
+        assert!(KeyMap::from_json(r#"{"next_day": ["Hyper"]}"#).is_err());
+        assert!(KeyMap::from_json(r#"{"fly": ["f"]}"#).is_err());
+        // No way left to quit or back out
+        let err = KeyMap::from_json(r#"{"quit": []}"#).unwrap_err();
+        assert!(err.contains("Quit needs at least one key"));
+        assert!(KeyMap::from_json(r#"{"cancel": []}"#).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys.json");
+        assert_eq!(KeyMap::load(&path), (KeyMap::default(), None));
+        fs::write(&path, "{ not json").unwrap();
+        let (keymap, warning) = KeyMap::load(&path);
+        assert_eq!(keymap, KeyMap::default());
+        assert!(warning.unwrap().contains("using the default keys"));
+    }
+
+    #[test]
+    fn test_vim_keys_take_precedence() {
+        let keymap = KeyMap::default();
+        assert_eq!(keymap.action(KeyCode::Char('h'), true), Some(Action::PrevDay));
+        assert_eq!(keymap.action(KeyCode::Char('m'), true), Some(Action::ManageHabits));
+        assert_eq!(keymap.label(Action::PrevDay, true), "h / ←");
+        assert_eq!(keymap.label(Action::ManageHabits, true), "m");
+        // Vim mode replacing the default `h` isn't a conflict; taking a custom key is
+        assert_eq!(keymap.vim_conflicts(), None);
+        let custom = KeyMap::from_json(r#"{"edit_note": ["j"], "view_notes": ["N", "k"], "set_not_applicable": ["Z"]}"#).unwrap();
+        assert_eq!(custom.vim_conflicts().unwrap(), "Vim keys take 'k' from ViewNotes, 'j' from EditNote");
+    }
+}
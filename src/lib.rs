@@ -3,3 +3,7 @@ pub mod models;
 pub mod storage;
 pub mod ui;
 pub mod app;
+pub mod palette;
+pub mod exports;
+pub mod theme;
+pub mod keymap;
@@ -1,47 +1,107 @@
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
     execute,
+    style::Print,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
+    layout::Rect,
     Terminal,
 };
+use chrono::Weekday;
 use std::io;
 use std::time::Duration;
 
 use healing_habits::app::{App, AppView};
-use healing_habits::ui;
+use healing_habits::keymap::Action;
+use healing_habits::models::{HabitStatus, Template};
+use healing_habits::{storage, ui};
+use healing_habits::ui::ClickTarget;
 
 fn main() -> Result<()> {
     // Get data directory path
-    let data_dir = dirs::data_local_dir()
+    let default_path = dirs::data_local_dir()
         .unwrap_or_else(|| std::env::current_dir().unwrap().join(".data"))
-        .join("healing-habits");
-    std::fs::create_dir_all(&data_dir)?;
-    let data_path = data_dir.join("habits.json");
+        .join("healing-habits")
+        .join("habits.json");
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Handle --help before touching the terminal
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print!("{}", usage(&default_path));
+        return Ok(());
+    }
+
+    let data_path = data_path_arg(&args, &default_path)?;
+
+    // Handle --version before touching the terminal
+    if args.iter().any(|arg| arg == "--version" || arg == "-V") {
+        println!("healing-habits {}", env!("CARGO_PKG_VERSION"));
+        println!("Data file: {}", data_path.display());
+        return Ok(());
+    }
+
+    let template = template_arg(&args)?;
+    let import_path = match flag_value(&args, "--import") {
+        Some("") => anyhow::bail!("--import needs the path of a habits.json file"),
+        path => path.map(std::path::PathBuf::from),
+    };
+
+    if let Some(data_dir) = data_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(data_dir)?;
+    }
+    // Only the default data file picks up data from older versions' locations
+    let migrated_from = if data_path == default_path {
+        storage::migrate_legacy_data(&data_path, &storage::legacy_data_paths())?
+    } else {
+        None
+    };
+    let lock_path = storage::lock_path(&data_path);
+    let lock_state = storage::check_lock(&lock_path, storage::process_running);
+
+    let mouse = mouse_enabled(&args, std::env::var("HEALING_HABITS_NO_MOUSE").ok().as_deref());
 
     // Setup terminal
+    ui::install_panic_hook();
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    if mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Create app
-    let mut app = App::new(data_path)?;
+    let mut app = App::with_template(data_path, template)?;
+    if let Some(source) = migrated_from {
+        app.flash(format!("Copied your data from {} to the new data folder", source.display()));
+    }
+    match lock_state {
+        storage::LockState::Held(pid) => app.warn_data_locked(pid, lock_path),
+        _ => app.claim_data_lock(lock_path)?,
+    }
+    if let Some(path) = import_path {
+        if let Err(err) = app.start_import(&path) {
+            app.flash(format!("Import failed: {}", err));
+        }
+    }
 
     // Run the app
     let res = run_app(&mut terminal, &mut app);
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    if mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
     if let Err(err) = res {
@@ -51,30 +111,178 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Decide whether to capture the mouse: on unless `--no-mouse` is passed or
+/// `HEALING_HABITS_NO_MOUSE` is set to anything other than empty or "0"
+fn mouse_enabled(args: &[String], no_mouse_env: Option<&str>) -> bool {
+    let flag = args.iter().any(|arg| arg == "--no-mouse");
+    let env = no_mouse_env.is_some_and(|v| !v.is_empty() && v != "0");
+    !(flag || env)
+}
+
+/// Value of `--flag VALUE` or `--flag=VALUE` (empty if the value is missing)
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter().enumerate().find_map(|(idx, arg)| {
+        if arg == flag {
+            Some(args.get(idx + 1).map(String::as_str).unwrap_or(""))
+        } else {
+            arg.strip_prefix(flag).and_then(|rest| rest.strip_prefix('='))
+        }
+    })
+}
+
+/// Command-line usage, printed by `--help`
+fn usage(default_path: &std::path::Path) -> String {
+    let templates: Vec<&str> = Template::ALL.iter().map(|t| t.name()).collect();
+    format!(
+        "healing-habits {}
+
+Usage: healing-habits [OPTIONS]
+
+Options:
+  -d, --data <PATH>      Use this data file instead of the default
+                         ({})
+      --template <NAME>  Habits to start a new data file with ({})
+      --import <PATH>    Preview merging another habits.json into your data
+      --no-mouse         Don't capture the mouse
+  -V, --version          Print the version and data file, then exit
+  -h, --help             Print this help, then exit
+",
+        env!("CARGO_PKG_VERSION"),
+        default_path.display(),
+        templates.join(", ")
+    )
+}
+
+/// Data file from `--data PATH` / `-d PATH` (or `=PATH`), or `default_path` if neither is given
+/// A directory is rejected, since the path names the JSON file itself.
+fn data_path_arg(args: &[String], default_path: &std::path::Path) -> Result<std::path::PathBuf> {
+    let path = match flag_value(args, "--data").or_else(|| flag_value(args, "-d")) {
+        None => return Ok(default_path.to_path_buf()),
+        Some("") => anyhow::bail!("--data needs the path of a JSON file, e.g. --data ~/habits/work.json"),
+        Some(path) => std::path::PathBuf::from(path),
+    };
+    if path.is_dir() {
+        anyhow::bail!(
+            "{} is a directory - pass the path of a JSON file, e.g. {}",
+            path.display(),
+            path.join("habits.json").display()
+        );
+    }
+    Ok(path)
+}
+
+/// Read the starter template from `--template NAME` or `--template=NAME` (default if absent)
+fn template_arg(args: &[String]) -> Result<Template> {
+    match flag_value(args, "--template") {
+        None => Ok(Template::default()),
+        Some(name) => Template::from_name(name).ok_or_else(|| {
+            let names: Vec<&str> = Template::ALL.iter().map(|t| t.name()).collect();
+            anyhow::anyhow!("Unknown template '{}' (choose from: {})", name, names.join(", "))
+        }),
+    }
+}
+
 fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+) -> Result<()> {
+    let result = event_loop(terminal, app);
+    // However the loop ended (quit, Ctrl+C or an error), write out what's pending
+    let flushed = app.storage.flush();
+    result.and(flushed)
+}
+
+fn event_loop<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
 ) -> Result<()> {
     loop {
-        terminal.draw(|f| ui::draw(f, app))?;
+        app.check_reminders();
+        let mut draw_error = None;
+        terminal.draw(|f| draw_error = ui::draw(f, app))?;
+        let view_failed = draw_error.is_some();
+        if let Some(message) = draw_error {
+            app.flash(message);
+        }
 
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                // Check for Ctrl+C
-                if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
-                    app.quit();
-                    break;
-                }
+            match event::read()? {
+                Event::Key(key) => {
+                    // Keep a draw error visible on the screen the user navigates to
+                    if !view_failed {
+                        app.flash_message = None;
+                    }
 
-                handle_key_event(app, key.code)?;
+                    handle_key_or_report(app, key);
+                    finish_input(app)?;
+                }
+                Event::Mouse(mouse) => {
+                    let size = terminal.size()?;
+                    // Like keys, a click that can't be carried out is reported, not fatal
+                    if let Err(e) = handle_mouse(app, mouse, Rect::new(0, 0, size.width, size.height)) {
+                        app.flash(e.to_string());
+                    }
+                    finish_input(app)?;
+                }
+                _ => {}
             }
         }
 
         if app.should_quit {
-            break;
+            return Ok(());
         }
     }
+}
+
+/// Wrap up after a key press or click: ask for a skip reason, save, and ring the bell
+fn finish_input(app: &mut App) -> Result<()> {
+    app.prompt_skip_reason();
+    // Everything the input changed is written once, together
+    app.storage.flush_or_report();
+    app.report_save_error();
+    // At most one bell per input, however many habits it completed
+    if app.take_bell() {
+        execute!(io::stdout(), Print('\x07'))?;
+    }
+    Ok(())
+}
+
+/// Select the day or habit under a left click on the main view; other clicks are ignored
+fn handle_mouse(app: &mut App, mouse: MouseEvent, area: Rect) -> Result<()> {
+    if app.view != AppView::Main || mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+        return Ok(());
+    }
+    match ui::main_view_click(area, app, mouse.column, mouse.row) {
+        Some(ClickTarget::Day(day_idx)) => app.select_day(day_idx),
+        Some(ClickTarget::Habit(habit_idx)) => app.select_habit(habit_idx),
+        None => Ok(()),
+    }
+}
+
+/// Handle a key press, showing anything it couldn't do in the footer instead of exiting
+fn handle_key_or_report(app: &mut App, key: KeyEvent) {
+    if let Err(e) = handle_key_press(app, key) {
+        app.flash(e.to_string());
+    }
+}
 
+/// Handle the Ctrl shortcuts that work across views, then the view's own keys
+fn handle_key_press(app: &mut App, key: KeyEvent) -> Result<()> {
+    if !key.modifiers.contains(KeyModifiers::CONTROL) {
+        return handle_key_event(app, key.code);
+    }
+    match key.code {
+        // Ctrl+C quits; pending changes are flushed on the way out
+        KeyCode::Char('c') => app.quit()?,
+        // Ctrl+S saves the note being written as a snippet
+        KeyCode::Char('s') if app.view == AppView::NoteInput => app.save_note_as_snippet()?,
+        // Ctrl+P opens the command palette from the main view
+        KeyCode::Char('p') if app.view == AppView::Main => app.open_command_palette()?,
+        // Ctrl+R redoes the last undone change from the main view
+        KeyCode::Char('r') if app.view == AppView::Main => app.redo()?,
+        KeyCode::Char('s' | 'p' | 'r') => {}
+        _ => handle_key_event(app, key.code)?,
+    }
     Ok(())
 }
 
@@ -86,66 +294,120 @@ fn handle_key_event(app: &mut App, key: KeyCode) -> Result<()> {
         AppView::HabitManagement => handle_habit_mgmt_keys(app, key)?,
         AppView::NoteInput => handle_note_input_keys(app, key)?,
         AppView::ExportConfirmation => handle_export_confirmation_keys(app, key)?,
+        AppView::Grid => handle_grid_view_keys(app, key)?,
+        AppView::ResetWeekConfirmation => handle_reset_week_confirmation_keys(app, key)?,
+        AppView::CommandPalette => handle_command_palette_keys(app, key)?,
+        AppView::Activity => handle_stats_view_keys(app, key)?,
+        AppView::WeekNoteInput => handle_week_note_input_keys(app, key)?,
+        AppView::ExportPreview => handle_export_preview_keys(app, key)?,
+        AppView::ExportRange => handle_export_range_keys(app, key),
+        AppView::GoToDate => handle_go_to_date_keys(app, key),
+        AppView::Review => handle_review_keys(app, key)?,
+        AppView::HabitDetail => handle_habit_detail_keys(app, key)?,
+        AppView::NotesHistory => handle_notes_history_keys(app, key)?,
+        AppView::Search => handle_search_keys(app, key)?,
+        AppView::Heatmap => handle_heatmap_keys(app, key)?,
+        AppView::ImportPreview => handle_import_preview_keys(app, key)?,
+        AppView::LockWarning => handle_lock_warning_keys(app, key)?,
     }
     Ok(())
 }
 
 fn handle_main_view_keys(app: &mut App, key: KeyCode) -> Result<()> {
-    match key {
-        KeyCode::Char('q') => {
+    let Some(action) = app.keymap.action(key, app.storage.settings().vim_keys) else {
+        return Ok(());
+    };
+    match action {
+        Action::Quit => {
             app.commit_staged_status()?;
-            app.quit();
-        }
-        KeyCode::Esc => app.cancel_staged_status(),
-        KeyCode::Left => app.prev_day()?,
-        KeyCode::Right => app.next_day()?,
-        KeyCode::Up => app.prev_habit()?,
-        KeyCode::Down => app.next_habit()?,
-        KeyCode::Enter | KeyCode::Char(' ') => app.toggle_habit_status(),
-        KeyCode::Char('n') => {
+            app.quit()?;
+        }
+        Action::Cancel => app.cancel_staged_status(),
+        Action::PrevDay => app.prev_day()?,
+        Action::NextDay => app.next_day()?,
+        Action::PrevHabit => app.prev_habit()?,
+        Action::NextHabit => app.next_habit()?,
+        Action::PageUpHabits => app.page_up_habits()?,
+        Action::PageDownHabits => app.page_down_habits()?,
+        Action::ToggleStatus => app.toggle_habit_status(),
+        Action::ToggleStatusBack => app.toggle_habit_status_back(),
+        Action::SetDone => app.set_habit_status(HabitStatus::Done)?,
+        Action::SetPartial => app.set_habit_status(HabitStatus::Partial)?,
+        Action::SetSkipped => app.set_habit_status(HabitStatus::Skipped)?,
+        Action::SetUnmarked => app.set_habit_status(HabitStatus::Unmarked)?,
+        Action::SetNotApplicable => app.set_habit_status(HabitStatus::NotApplicable)?,
+        Action::MarkAllDone => app.mark_all(HabitStatus::Done)?,
+        Action::MarkAllSkipped => app.mark_all(HabitStatus::Skipped)?,
+        Action::StartVacation => app.toggle_vacation()?,
+        Action::EndVacation => app.end_vacation_here()?,
+        Action::EditNote => {
             app.commit_staged_status()?;
             app.start_note_input();
         }
-        KeyCode::Char('w') => {
+        Action::ViewNotes => app.open_notes_history()?,
+        Action::GoToToday => {
             app.commit_staged_status()?;
             app.go_to_today();
         }
-        KeyCode::Char('v') => {
+        Action::ViewStats => {
             app.commit_staged_status()?;
             app.set_view(AppView::Stats);
         }
-        KeyCode::Char('h') => {
-            app.commit_staged_status()?;
-            app.enter_habit_management();
-        }
-        KeyCode::Char('?') => {
-            app.commit_staged_status()?;
-            app.set_view(AppView::Help);
-        }
-        KeyCode::Char('t') => {
-            app.commit_staged_status()?;
-            app.go_to_today();
-        }
-        KeyCode::Char('[') => {
+        Action::ViewActivity => {
             app.commit_staged_status()?;
-            app.prev_week();
+            app.set_view(AppView::Activity);
         }
-        KeyCode::Char(']') => {
+        Action::ManageHabits => {
             app.commit_staged_status()?;
-            app.next_week();
+            app.enter_habit_management();
         }
-        KeyCode::Char('x') => {
+        Action::FirstActivity => app.jump_to_first_activity()?,
+        Action::LastActivity => app.jump_to_last_activity()?,
+        Action::GoToDate => app.start_go_to_date()?,
+        Action::WeekNote => app.start_week_note_input()?,
+        Action::Grid => app.enter_grid_view()?,
+        Action::Heatmap => app.open_heatmap()?,
+        Action::ShowAllHabits => app.toggle_show_all_habits()?,
+        Action::OnlyUnmarked => app.toggle_only_unmarked()?,
+        Action::AsNeededPanel => app.toggle_as_needed_panel()?,
+        Action::CycleTagFilter => app.cycle_tag_filter()?,
+        Action::StripFollowsHabit => app.toggle_strip_follows_habit(),
+        Action::CommandPalette => app.open_command_palette()?,
+        Action::Search => app.open_search()?,
+        Action::ResetWeek => app.start_reset_week()?,
+        Action::Review => app.start_review()?,
+        Action::HabitDetail => app.open_habit_detail()?,
+        Action::Undo => app.undo()?,
+        Action::Help => {
             app.commit_staged_status()?;
-            app.export_and_show_confirmation()?;
+            app.set_view(AppView::Help);
         }
-        _ => {}
+        Action::PrevWeek => app.prev_week()?,
+        Action::NextWeek => app.next_week()?,
+        Action::Export => app.start_export_preview()?,
     }
     Ok(())
 }
 
 fn handle_stats_view_keys(app: &mut App, key: KeyCode) -> Result<()> {
-    match key {
-        KeyCode::Char('q') | KeyCode::Esc => app.set_view(AppView::Main),
+    match (key, app.keymap.action(key, app.storage.settings().vim_keys)) {
+        (KeyCode::Tab, _) if app.view == AppView::Stats => app.cycle_stats_range(),
+        (_, Some(Action::Quit | Action::Cancel)) => app.set_view(AppView::Main),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_grid_view_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    match app.keymap.action(key, app.storage.settings().vim_keys) {
+        Some(Action::Quit) => app.leave_grid_view()?,
+        Some(Action::Cancel) if app.staged_status.is_some() => app.cancel_staged_status(),
+        Some(Action::Cancel) => app.leave_grid_view()?,
+        Some(Action::PrevHabit) => app.grid_up()?,
+        Some(Action::NextHabit) => app.grid_down()?,
+        Some(Action::PrevDay) => app.grid_left()?,
+        Some(Action::NextDay) => app.grid_right()?,
+        Some(Action::ToggleStatus) => app.toggle_grid_cell_status(),
         _ => {}
     }
     Ok(())
@@ -166,12 +428,57 @@ fn handle_habit_mgmt_keys(app: &mut App, key: KeyCode) -> Result<()> {
                 KeyCode::Char('q') | KeyCode::Esc => app.set_view(AppView::Main),
                 KeyCode::Up => app.habit_mgmt_prev(),
                 KeyCode::Down => app.habit_mgmt_next(),
+                KeyCode::PageUp => app.habit_mgmt_page_up(),
+                KeyCode::PageDown => app.habit_mgmt_page_down(),
                 KeyCode::Char('a') => app.start_add_habit(),
                 KeyCode::Char('e') => app.start_edit_habit(),
+                KeyCode::Char('l') => app.start_edit_label(),
+                KeyCode::Char('E') => app.start_edit_description(),
+                KeyCode::Char('n') => app.start_edit_note_template(),
+                KeyCode::Char('t') => app.start_edit_tags(),
+                KeyCode::Char('r') => app.start_edit_reminder(),
+                KeyCode::Char('g') => app.start_edit_goal(),
+                KeyCode::Char('X') => app.start_archive_tag(),
+                KeyCode::Char('B') => app.start_backup(),
+                KeyCode::Char('R') => app.start_restore(),
+                KeyCode::Char('I') => app.start_import_list(),
                 KeyCode::Char('d') => app.delete_selected_habit()?,
+                KeyCode::Char('D') => app.duplicate_selected_habit()?,
+                KeyCode::Char('M') => app.start_merge_habit(),
                 KeyCode::Char('[') => app.move_habit_up()?,
                 KeyCode::Char(']') => app.move_habit_down()?,
                 KeyCode::Char('f') => app.cycle_habit_frequency()?,
+                KeyCode::Char('+') => app.adjust_habit_interval(1)?,
+                KeyCode::Char('-') => app.adjust_habit_interval(-1)?,
+                // 1-7 turn Monday-Sunday on or off for a specific-days habit
+                KeyCode::Char(c @ '1'..='7') => {
+                    if let Ok(day) = Weekday::try_from(c as u8 - b'1') {
+                        app.toggle_habit_day(day)?;
+                    }
+                }
+                KeyCode::Char('S') => app.toggle_require_skip_note()?,
+                KeyCode::Char('L') => app.toggle_legend()?,
+                KeyCode::Char('W') => app.toggle_week_start()?,
+                KeyCode::Char('K') => app.toggle_skips_keep_streak()?,
+                KeyCode::Char('V') => app.toggle_vim_keys()?,
+                KeyCode::Char('b') => app.toggle_bell_on_complete()?,
+                KeyCode::Char('A') => app.toggle_ask_skip_reason()?,
+                KeyCode::Char('F') => app.toggle_strict_frequency()?,
+                KeyCode::Char('w') => app.toggle_habit_auto_fill_week()?,
+                KeyCode::Char('o') => app.toggle_habit_skip_weekends()?,
+                KeyCode::Char('c') => app.cycle_habit_color()?,
+                KeyCode::Char('i') => app.open_habit_detail()?,
+                _ => {}
+            }
+        }
+        HabitMgmtMode::MergeHabit => {
+            match key {
+                KeyCode::Up => app.habit_mgmt_prev(),
+                KeyCode::Down => app.habit_mgmt_next(),
+                KeyCode::PageUp => app.habit_mgmt_page_up(),
+                KeyCode::PageDown => app.habit_mgmt_page_down(),
+                KeyCode::Enter => app.merge_into_selected_habit()?,
+                KeyCode::Esc | KeyCode::Char('q') => app.cancel_merge_habit(),
                 _ => {}
             }
         }
@@ -179,17 +486,90 @@ fn handle_habit_mgmt_keys(app: &mut App, key: KeyCode) -> Result<()> {
             match key {
                 KeyCode::Enter => app.save_new_habit()?,
                 KeyCode::Esc => app.cancel_habit_input(),
-                KeyCode::Char(c) => app.input_char(c),
-                KeyCode::Backspace => app.input_backspace(),
-                _ => {}
+                _ => edit_input(app, key),
             }
         }
         HabitMgmtMode::EditHabit => {
             match key {
                 KeyCode::Enter => app.save_edited_habit()?,
                 KeyCode::Esc => app.cancel_habit_input(),
-                KeyCode::Char(c) => app.input_char(c),
-                KeyCode::Backspace => app.input_backspace(),
+                _ => edit_input(app, key),
+            }
+        }
+        HabitMgmtMode::EditLabel => {
+            match key {
+                KeyCode::Enter => app.save_edited_label()?,
+                KeyCode::Esc => app.cancel_habit_input(),
+                _ => edit_input(app, key),
+            }
+        }
+        HabitMgmtMode::EditTags => {
+            match key {
+                KeyCode::Enter => app.save_edited_tags()?,
+                KeyCode::Esc => app.cancel_habit_input(),
+                _ => edit_input(app, key),
+            }
+        }
+        HabitMgmtMode::EditDescription => {
+            match key {
+                KeyCode::Enter => app.save_edited_description()?,
+                KeyCode::Esc => app.cancel_habit_input(),
+                _ => edit_input(app, key),
+            }
+        }
+        HabitMgmtMode::EditNoteTemplate => {
+            match key {
+                KeyCode::Enter => app.save_edited_note_template()?,
+                KeyCode::Esc => app.cancel_habit_input(),
+                _ => edit_input(app, key),
+            }
+        }
+        HabitMgmtMode::EditReminder => {
+            match key {
+                KeyCode::Enter => app.save_edited_reminder()?,
+                KeyCode::Esc => app.cancel_habit_input(),
+                _ => edit_input(app, key),
+            }
+        }
+        HabitMgmtMode::EditGoal => {
+            match key {
+                KeyCode::Enter => app.save_edited_goal()?,
+                KeyCode::Esc => app.cancel_habit_input(),
+                _ => edit_input(app, key),
+            }
+        }
+        HabitMgmtMode::ArchiveTag => {
+            match key {
+                KeyCode::Enter => app.request_archive_tag(),
+                KeyCode::Esc => app.cancel_habit_input(),
+                _ => edit_input(app, key),
+            }
+        }
+        HabitMgmtMode::BackupPath => {
+            match key {
+                KeyCode::Enter => app.save_backup(),
+                KeyCode::Esc => app.cancel_habit_input(),
+                _ => edit_input(app, key),
+            }
+        }
+        HabitMgmtMode::RestorePath => {
+            match key {
+                KeyCode::Enter => app.request_restore(),
+                KeyCode::Esc => app.cancel_habit_input(),
+                _ => edit_input(app, key),
+            }
+        }
+        HabitMgmtMode::ImportListPath => {
+            match key {
+                KeyCode::Enter => app.save_import_list()?,
+                KeyCode::Esc => app.cancel_habit_input(),
+                _ => edit_input(app, key),
+            }
+        }
+        HabitMgmtMode::ConfirmArchiveTag => {
+            match key {
+                KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_archive_tag()?,
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_habit_input(),
                 _ => {}
             }
         }
@@ -197,12 +577,125 @@ fn handle_habit_mgmt_keys(app: &mut App, key: KeyCode) -> Result<()> {
     Ok(())
 }
 
+fn handle_habit_detail_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('i') => app.close_habit_detail(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_notes_history_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('J') => app.set_view(AppView::Main),
+        KeyCode::Up => app.notes_scroll_up(),
+        KeyCode::Down => app.notes_scroll_down(),
+        KeyCode::Char('n') => app.start_note_input(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_lock_warning_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Char('r') => app.open_read_only(),
+        KeyCode::Char('f') => app.force_take_lock()?,
+        KeyCode::Char('q') | KeyCode::Esc => app.quit()?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_import_preview_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_import()?,
+        KeyCode::Char('r') | KeyCode::Char('R') => app.confirm_import_replace()?,
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_import(),
+        _ => {}
+    }
+    Ok(())
+}
+
 fn handle_note_input_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    if app.snippet_picker.is_some() {
+        match key {
+            KeyCode::Enter => app.insert_selected_snippet(),
+            KeyCode::Esc | KeyCode::Tab => app.close_snippet_picker(),
+            KeyCode::Up => app.snippet_picker_prev(),
+            KeyCode::Down => app.snippet_picker_next(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
     match key {
         KeyCode::Enter => app.save_note_input()?,
         KeyCode::Esc => app.cancel_note_input(),
+        KeyCode::Tab => app.open_snippet_picker(),
+        _ => edit_input(app, key),
+    }
+    Ok(())
+}
+
+/// Typing and cursor movement shared by the note and habit text inputs
+fn edit_input(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Left => app.input_cursor_left(),
+        KeyCode::Right => app.input_cursor_right(),
+        KeyCode::Home => app.input_cursor_home(),
+        KeyCode::End => app.input_cursor_end(),
         KeyCode::Char(c) => app.input_char(c),
         KeyCode::Backspace => app.input_backspace(),
+        KeyCode::Delete => app.input_delete(),
+        _ => {}
+    }
+}
+
+fn handle_week_note_input_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Enter => app.save_week_note_input()?,
+        KeyCode::Esc => app.cancel_week_note_input(),
+        _ => edit_input(app, key),
+    }
+    Ok(())
+}
+
+fn handle_export_preview_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Enter => app.confirm_export_preview()?,
+        KeyCode::Esc | KeyCode::Char('q') => app.set_view(AppView::Main),
+        KeyCode::Up => app.preview_scroll_up(),
+        KeyCode::Down => app.preview_scroll_down(),
+        KeyCode::Tab => app.cycle_export_kind(),
+        KeyCode::Char('r') => app.start_export_range_input(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_export_range_keys(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Enter => app.save_export_range(),
+        KeyCode::Esc => app.cancel_export_range(),
+        _ => edit_input(app, key),
+    }
+}
+
+fn handle_go_to_date_keys(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Enter => app.save_go_to_date(),
+        KeyCode::Esc => app.cancel_go_to_date(),
+        _ => edit_input(app, key),
+    }
+}
+
+fn handle_review_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Char('d') => app.review_mark(HabitStatus::Done)?,
+        KeyCode::Char('p') => app.review_mark(HabitStatus::Partial)?,
+        KeyCode::Char('s') => app.review_mark(HabitStatus::Skipped)?,
+        KeyCode::Char(' ') | KeyCode::Right => app.review_skip_pair()?,
+        KeyCode::Esc | KeyCode::Char('q') => app.finish_review()?,
         _ => {}
     }
     Ok(())
@@ -213,3 +706,217 @@ fn handle_export_confirmation_keys(app: &mut App, _key: KeyCode) -> Result<()> {
     app.set_view(AppView::Main);
     Ok(())
 }
+
+fn handle_reset_week_confirmation_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            app.confirm_reset_week()?;
+        }
+        // Any other key cancels
+        _ => app.set_view(AppView::Main),
+    }
+    Ok(())
+}
+
+fn handle_command_palette_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Enter => app.run_palette_selection()?,
+        KeyCode::Esc => app.close_command_palette(),
+        KeyCode::Up => app.palette_prev(),
+        KeyCode::Down => app.palette_next(),
+        KeyCode::Char(c) => app.palette_input_char(c),
+        KeyCode::Backspace => app.palette_input_backspace(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_heatmap_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('y') => app.set_view(AppView::Main),
+        KeyCode::Left => app.heatmap_prev_habit(),
+        KeyCode::Right => app.heatmap_next_habit(),
+        KeyCode::Up => app.heatmap_change_year(-1),
+        KeyCode::Down => app.heatmap_change_year(1),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_search_keys(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Enter => app.confirm_search()?,
+        KeyCode::Esc => app.close_search(),
+        KeyCode::Up => app.search_prev(),
+        KeyCode::Down => app.search_next(),
+        KeyCode::Char(c) => app.search_input_char(c),
+        KeyCode::Backspace => app.search_input_backspace(),
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mouse_enabled() {
+        let no_args: Vec<String> = Vec::new();
+        let no_mouse = vec!["--no-mouse".to_string()];
+
+        assert!(mouse_enabled(&no_args, None));
+        assert!(!mouse_enabled(&no_mouse, None));
+        assert!(!mouse_enabled(&no_args, Some("1")));
+        assert!(mouse_enabled(&no_args, Some("0")));
+        assert!(mouse_enabled(&no_args, Some("")));
+        assert!(!mouse_enabled(&no_mouse, Some("0")));
+    }
+
+    #[test]
+    fn test_keys_past_the_last_loggable_day_are_refused() {
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        let press = |app: &mut App, code| handle_key_or_report(app, KeyEvent::from(code));
+        let latest = app.today() + chrono::Duration::days(365);
+
+        // A year of weeks forward, then day by day, toggling along the way
+        for _ in 0..60 {
+            press(&mut app, KeyCode::Char(']'));
+        }
+        for _ in 0..10 {
+            press(&mut app, KeyCode::Char(' '));
+            press(&mut app, KeyCode::Right);
+        }
+        assert!(app.selected_date() <= latest);
+        assert!(app.flash_message.as_deref().is_some_and(|m| m.contains("out of range")));
+        assert!(!app.should_quit);
+        assert!(app.storage.most_recent_log_date().is_some_and(|date| date <= latest));
+
+        // Going to a day past the end is refused without leaving the date prompt
+        press(&mut app, KeyCode::Char('G'));
+        for c in "+400".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.view, AppView::GoToDate);
+        assert!(app.selected_date() <= latest);
+    }
+
+    #[test]
+    fn test_vim_keys_remap_main_view() {
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        app.selected_day_idx = 3;
+
+        // Off by default: h still opens habit management
+        handle_main_view_keys(&mut app, KeyCode::Char('h')).unwrap();
+        assert_eq!(app.view, AppView::HabitManagement);
+        app.set_view(AppView::Main);
+
+        app.toggle_vim_keys().unwrap();
+        handle_main_view_keys(&mut app, KeyCode::Char('h')).unwrap();
+        assert_eq!((app.view, app.selected_day_idx), (AppView::Main, 2));
+        handle_main_view_keys(&mut app, KeyCode::Char('l')).unwrap();
+        handle_main_view_keys(&mut app, KeyCode::Char('l')).unwrap();
+        assert_eq!(app.selected_day_idx, 4);
+        handle_main_view_keys(&mut app, KeyCode::Char('j')).unwrap();
+        assert_eq!(app.selected_habit_idx, 1);
+        handle_main_view_keys(&mut app, KeyCode::Char('k')).unwrap();
+        assert_eq!(app.selected_habit_idx, 0);
+        handle_main_view_keys(&mut app, KeyCode::Char('m')).unwrap();
+        assert_eq!(app.view, AppView::HabitManagement);
+    }
+
+    #[test]
+    fn test_turning_on_vim_keys_reports_custom_keys_it_takes() {
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        app.keymap = healing_habits::keymap::KeyMap::from_json(r#"{"edit_note": ["j"]}"#).unwrap();
+
+        app.toggle_vim_keys().unwrap();
+        assert_eq!(app.flash_message.as_deref(), Some("Vim keys take 'j' from EditNote"));
+        app.flash_message = None;
+        app.toggle_vim_keys().unwrap();
+        assert!(app.flash_message.is_none());
+    }
+
+    #[test]
+    fn test_custom_keymap_routes_main_view_keys() {
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        app.keymap = healing_habits::keymap::KeyMap::from_json(r#"{"view_stats": ["s"], "view_activity": ["v"]}"#).unwrap();
+
+        handle_main_view_keys(&mut app, KeyCode::Char('s')).unwrap();
+        assert_eq!(app.view, AppView::Stats);
+        handle_stats_view_keys(&mut app, KeyCode::Esc).unwrap();
+        handle_main_view_keys(&mut app, KeyCode::Char('v')).unwrap();
+        assert_eq!(app.view, AppView::Activity);
+    }
+
+    #[test]
+    fn test_f_cycles_frequency_in_habit_management() {
+        use healing_habits::models::Frequency;
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        handle_main_view_keys(&mut app, KeyCode::Char('h')).unwrap();
+        let shower = app.habits()[0].id;
+        let frequency = |app: &App| app.storage.get_habit(shower).unwrap().frequency.clone();
+
+        let mut seen = vec![frequency(&app)];
+        for _ in 0..5 {
+            handle_habit_mgmt_keys(&mut app, KeyCode::Char('f')).unwrap();
+            seen.push(frequency(&app));
+        }
+        assert_eq!(seen[0], Frequency::Daily);
+        assert_eq!(seen[1], Frequency::Weekly);
+        assert!(matches!(seen[2], Frequency::EveryNDays(_)));
+        assert!(matches!(seen[3], Frequency::SpecificDays(_)));
+        assert_eq!(seen[4], Frequency::AsNeeded);
+        assert_eq!(seen[5], Frequency::Daily);
+
+        // The change is saved, not just shown
+        handle_habit_mgmt_keys(&mut app, KeyCode::Char('f')).unwrap();
+        app.storage.flush().unwrap();
+        let reopened = App::new(temp_file.path()).unwrap();
+        assert_eq!(reopened.storage.get_habit(shower).unwrap().frequency, Frequency::Weekly);
+    }
+
+    #[test]
+    fn test_template_arg() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(template_arg(&args(&[])).unwrap(), Template::Default);
+        assert_eq!(template_arg(&args(&["--template", "minimal"])).unwrap(), Template::Minimal);
+        assert_eq!(template_arg(&args(&["--template=mental-health"])).unwrap(), Template::MentalHealth);
+        assert!(template_arg(&args(&["--template", "nope"])).is_err());
+        assert!(template_arg(&args(&["--template"])).is_err());
+    }
+
+    #[test]
+    fn test_flag_value() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(flag_value(&args(&["--import", "a.json"]), "--import"), Some("a.json"));
+        assert_eq!(flag_value(&args(&["--import=b.json"]), "--import"), Some("b.json"));
+        assert_eq!(flag_value(&args(&["--import"]), "--import"), Some(""));
+        assert_eq!(flag_value(&args(&["--imports=c"]), "--import"), None);
+        assert_eq!(flag_value(&args(&["--no-mouse"]), "--import"), None);
+    }
+
+    #[test]
+    fn test_data_path_arg() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let default = std::path::Path::new("/data/habits.json");
+        let dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(data_path_arg(&args(&[]), default).unwrap(), default);
+        assert_eq!(data_path_arg(&args(&["--data", "work.json"]), default).unwrap(), std::path::Path::new("work.json"));
+        assert_eq!(data_path_arg(&args(&["-d", "home.json"]), default).unwrap(), std::path::Path::new("home.json"));
+        assert_eq!(data_path_arg(&args(&["--data=x/y.json"]), default).unwrap(), std::path::Path::new("x/y.json"));
+        assert!(data_path_arg(&args(&["--data"]), default).is_err());
+
+        let err = data_path_arg(&args(&["-d", dir.path().to_str().unwrap()]), default).unwrap_err();
+        assert!(err.to_string().contains("is a directory"));
+    }
+}
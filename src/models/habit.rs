@@ -1,34 +1,75 @@
+use chrono::{Datelike, Local, NaiveDate, NaiveTime, Weekday};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// Frequency at which a habit should be tracked
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Frequency {
     /// Should be done every day
+    #[default]
     Daily,
-    /// Should be done once per week (rolls over if not completed)
+    /// Should be done once per week (shown as overdue the next week if not completed)
     Weekly,
     /// Optional habit, no specific frequency
     AsNeeded,
+    /// Should be done every N days, counted from the habit's creation date
+    EveryNDays(u32),
+    /// Should be done on these weekdays only, kept in Monday-first order
+    SpecificDays(Vec<Weekday>),
 }
 
 impl Frequency {
     /// Get a human-readable description
-    pub fn description(&self) -> &'static str {
+    pub fn description(&self) -> String {
         match self {
-            Frequency::Daily => "Daily",
-            Frequency::Weekly => "Weekly",
-            Frequency::AsNeeded => "As needed",
+            Frequency::Daily => "Daily".to_string(),
+            Frequency::Weekly => "Weekly".to_string(),
+            Frequency::AsNeeded => "As needed".to_string(),
+            Frequency::EveryNDays(n) => format!("Every {} days", n),
+            Frequency::SpecificDays(days) => days
+                .iter()
+                .map(|day| day.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
         }
     }
-}
 
-impl Default for Frequency {
-    fn default() -> Self {
-        Frequency::Daily
+    /// Turn a weekday on or off for a specific-days frequency, keeping at least one day
+    /// Returns false if nothing changed (another frequency, or the last remaining day).
+    pub fn toggle_day(&mut self, day: Weekday) -> bool {
+        let Frequency::SpecificDays(days) = self else {
+            return false;
+        };
+        if let Some(idx) = days.iter().position(|&d| d == day) {
+            if days.len() == 1 {
+                return false;
+            }
+            days.remove(idx);
+        } else {
+            days.push(day);
+            days.sort_by_key(|d| d.num_days_from_monday());
+        }
+        true
     }
 }
 
+/// Today's date by the system clock, the creation date for habits built here
+/// `Storage` restamps the habits it creates with its own clock's date.
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Placeholder creation date for habits saved before it was tracked
+/// (replaced on load, see `HabitData::backfill_created_at`)
+pub fn unknown_created_at() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()
+}
+
 /// Represents a habit that can be tracked
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Habit {
@@ -43,6 +84,36 @@ pub struct Habit {
     /// How often this habit should be done
     #[serde(default)]
     pub frequency: Frequency,
+    /// Date the habit was created (anchor for every-N-days scheduling)
+    #[serde(default = "unknown_created_at")]
+    pub created_at: NaiveDate,
+    /// For Weekly habits, mark earlier days Skipped when the habit is marked Done
+    #[serde(default = "default_true")]
+    pub auto_fill_week: bool,
+    /// Don't schedule the habit on Saturdays and Sundays
+    #[serde(default)]
+    pub skip_weekends: bool,
+    /// Optional short context telling apart habits with the same name (e.g. "AM")
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Free-form categories (e.g. "hygiene"), compared ignoring case
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Retired habits are hidden everywhere but keep their history
+    #[serde(default)]
+    pub archived: bool,
+    /// Time of day after which the main view nudges if the habit is still unmarked
+    #[serde(default)]
+    pub remind_at: Option<NaiveTime>,
+    /// Times per week the habit should be Done, shown as progress on the main view
+    #[serde(default)]
+    pub weekly_goal: Option<u32>,
+    /// Color the habit's name is drawn in, by name or hex (e.g. "cyan", "#ff8800")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Text a new note for the habit starts from (e.g. "Mood:, Sleep:, Energy:")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note_template: Option<String>,
 }
 
 impl Habit {
@@ -54,6 +125,16 @@ impl Habit {
             description: None,
             order: 0,
             frequency: Frequency::default(),
+            created_at: today(),
+            auto_fill_week: true,
+            skip_weekends: false,
+            label: None,
+            tags: Vec::new(),
+            archived: false,
+            remind_at: None,
+            weekly_goal: None,
+            color: None,
+            note_template: None,
         }
     }
 
@@ -65,6 +146,16 @@ impl Habit {
             description: None,
             order: 0,
             frequency: Frequency::default(),
+            created_at: today(),
+            auto_fill_week: true,
+            skip_weekends: false,
+            label: None,
+            tags: Vec::new(),
+            archived: false,
+            remind_at: None,
+            weekly_goal: None,
+            color: None,
+            note_template: None,
         }
     }
 
@@ -76,6 +167,16 @@ impl Habit {
             description: Some(description.into()),
             order: 0,
             frequency: Frequency::default(),
+            created_at: today(),
+            auto_fill_week: true,
+            skip_weekends: false,
+            label: None,
+            tags: Vec::new(),
+            archived: false,
+            remind_at: None,
+            weekly_goal: None,
+            color: None,
+            note_template: None,
         }
     }
 
@@ -93,6 +194,59 @@ impl Habit {
     pub fn set_frequency(&mut self, frequency: Frequency) {
         self.frequency = frequency;
     }
+
+    /// Check whether the habit is scheduled on a date, based on its frequency and weekend setting
+    /// Weekends are always Saturday and Sunday, whichever day the week starts on
+    pub fn is_scheduled_on(&self, date: NaiveDate) -> bool {
+        if self.skip_weekends && matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            return false;
+        }
+        // Daily, Weekly and As-needed habits show every day
+        // (frequency is informational only for those)
+        match &self.frequency {
+            Frequency::Daily => true,
+            Frequency::Weekly => true,
+            Frequency::AsNeeded => true,
+            // Treat a zero interval as daily rather than dividing by zero
+            Frequency::EveryNDays(0) => true,
+            Frequency::EveryNDays(n) => {
+                (date - self.created_at).num_days().rem_euclid(*n as i64) == 0
+            }
+            // Treat an empty day list as daily, like a zero interval
+            Frequency::SpecificDays(days) => days.is_empty() || days.contains(&date.weekday()),
+        }
+    }
+
+    /// Set or clear (with None) the label
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+
+    /// Name as shown in lists and exports, with the label in parentheses
+    pub fn display_name(&self) -> String {
+        match &self.label {
+            Some(label) => format!("{} ({})", self.name, label),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Whether the habit carries a tag, ignoring case and surrounding spaces
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim();
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Whether this habit has the same name and label, ignoring case and surrounding spaces
+    pub fn same_identity(&self, name: &str, label: Option<&str>) -> bool {
+        let normalize = |s: &str| s.trim().to_lowercase();
+        normalize(&self.name) == normalize(name)
+            && self.label.as_deref().map(normalize) == label.map(normalize)
+    }
+
+    /// Set the creation date
+    pub fn set_created_at(&mut self, created_at: NaiveDate) {
+        self.created_at = created_at;
+    }
 }
 
 /// Default habits for new users
@@ -126,6 +280,131 @@ pub fn default_habits() -> Vec<Habit> {
     ]
 }
 
+/// Starter habits focused on mental health
+pub fn mental_health_habits() -> Vec<Habit> {
+    vec![
+        {
+            let mut h = Habit::new("Meds");
+            h.set_order(0);
+            h.set_frequency(Frequency::Daily);
+            h
+        },
+        {
+            let mut h = Habit::new("Check in with feelings");
+            h.set_order(1);
+            h.set_frequency(Frequency::Daily);
+            h
+        },
+        {
+            let mut h = Habit::new("Go outside");
+            h.set_order(2);
+            h.set_frequency(Frequency::Daily);
+            h
+        },
+        {
+            let mut h = Habit::new("Reach out to someone");
+            h.set_description(Some("Weekly habit".to_string()));
+            h.set_order(3);
+            h.set_frequency(Frequency::Weekly);
+            h
+        },
+    ]
+}
+
+/// The smallest useful starter set
+pub fn minimal_habits() -> Vec<Habit> {
+    vec![
+        {
+            let mut h = Habit::new("Meds");
+            h.set_order(0);
+            h.set_frequency(Frequency::Daily);
+            h
+        },
+        {
+            let mut h = Habit::new("Drink water");
+            h.set_order(1);
+            h.set_frequency(Frequency::Daily);
+            h
+        },
+    ]
+}
+
+/// One habit in a `defaults.json` starter file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HabitSeed {
+    name: String,
+    #[serde(default)]
+    frequency: Frequency,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+/// Starter habits from a `defaults.json` list,
+/// e.g. `[{"name": "Stretch"}, {"name": "Long run", "frequency": "Weekly"}]`
+pub fn habits_from_json(json: &str) -> Result<Vec<Habit>, String> {
+    let seeds: Vec<HabitSeed> = serde_json::from_str(json).map_err(|err| err.to_string())?;
+    if seeds.is_empty() {
+        return Err("no habits listed".to_string());
+    }
+    seeds.into_iter().enumerate().map(|(order, seed)| {
+        let name = seed.name.trim();
+        if name.is_empty() {
+            return Err(format!("habit {} has no name", order + 1));
+        }
+        let mut habit = Habit::new(name);
+        habit.set_description(seed.description.filter(|d| !d.trim().is_empty()));
+        habit.set_order(order);
+        habit.set_frequency(seed.frequency);
+        Ok(habit)
+    }).collect()
+}
+
+/// Starter habits file kept alongside a data file, e.g. `defaults.json` next to `habits.json`
+pub fn defaults_path(data_path: &Path) -> PathBuf {
+    data_path.with_file_name("defaults.json")
+}
+
+/// Named starter set used to fill a brand-new data file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Template {
+    /// Physical hygiene basics (the original starter set)
+    #[default]
+    Default,
+    /// Mental health focus
+    MentalHealth,
+    /// Just a couple of habits to build on
+    Minimal,
+}
+
+impl Template {
+    /// All templates, in the order they're listed to users
+    pub const ALL: [Template; 3] = [Template::Default, Template::MentalHealth, Template::Minimal];
+
+    /// Name used to pick this template on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            Template::Default => "default",
+            Template::MentalHealth => "mental-health",
+            Template::Minimal => "minimal",
+        }
+    }
+
+    /// Look up a template by its command-line name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+
+    /// Build this template's habits
+    pub fn habits(&self) -> Vec<Habit> {
+        match self {
+            Template::Default => default_habits(),
+            Template::MentalHealth => mental_health_habits(),
+            Template::Minimal => minimal_habits(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,6 +442,117 @@ mod tests {
         assert_eq!(habits[3].name, "Meds");
     }
 
+    #[test]
+    fn test_templates_are_distinct_and_non_empty() {
+        let mut name_sets = Vec::new();
+        for template in Template::ALL {
+            let habits = template.habits();
+            assert!(!habits.is_empty(), "{} is empty", template.name());
+
+            let mut names: Vec<&str> = habits.iter().map(|h| h.name.as_str()).collect();
+            names.sort();
+            names.dedup();
+            assert_eq!(names.len(), habits.len(), "{} repeats a name", template.name());
+            name_sets.push(names.join(","));
+
+            assert_eq!(Template::from_name(template.name()), Some(template));
+        }
+        name_sets.sort();
+        name_sets.dedup();
+        assert_eq!(name_sets.len(), Template::ALL.len());
+        assert_eq!(Template::from_name("nope"), None);
+    }
+
+    #[test]
+    fn test_habits_from_json() {
+        let habits = habits_from_json(r#"[
+            {"name": "Stretch"},
+            {"name": "Long run", "frequency": "Weekly", "description": "Sunday if possible"},
+            {"name": "Rest", "frequency": {"EveryNDays": 3}}
+        ]"#).unwrap();
+        assert_eq!(habits.len(), 3);
+        assert_eq!(habits[0].frequency, Frequency::Daily);
+        assert_eq!(habits[1].order, 1);
+        assert_eq!(habits[1].description.as_deref(), Some("Sunday if possible"));
+        assert_eq!(habits[2].frequency, Frequency::EveryNDays(3));
+
+        assert!(habits_from_json("[]").is_err());
+        assert!(habits_from_json(r#"[{"name": "  "}]"#).unwrap_err().contains("no name"));
+        assert!(habits_from_json(r#"[{"name": "Run", "often": true}]"#).is_err());
+    }
+
+    #[test]
+    fn test_label_is_part_of_identity() {
+        let mut am = Habit::new("Meds");
+        am.set_label(Some("AM".to_string()));
+        assert_eq!(am.display_name(), "Meds (AM)");
+        assert_eq!(Habit::new("Meds").display_name(), "Meds");
+
+        assert!(am.same_identity("meds ", Some("am")));
+        assert!(!am.same_identity("Meds", Some("PM")));
+        assert!(!am.same_identity("Meds", None));
+        assert!(Habit::new("Meds").same_identity("Meds", None));
+    }
+
+    #[test]
+    fn test_frequency_description() {
+        assert_eq!(Frequency::Daily.description(), "Daily");
+        assert_eq!(Frequency::EveryNDays(3).description(), "Every 3 days");
+        let mwf = Frequency::SpecificDays(vec![Weekday::Mon, Weekday::Wed, Weekday::Fri]);
+        assert_eq!(mwf.description(), "Mon, Wed, Fri");
+    }
+
+    #[test]
+    fn test_specific_days_schedule_and_serde() {
+        let mut habit = Habit::new("Meds");
+        habit.set_frequency(Frequency::SpecificDays(vec![Weekday::Mon, Weekday::Wed, Weekday::Fri]));
+        // Oct 13, 2025 is a Monday
+        let monday = NaiveDate::from_ymd_opt(2025, 10, 13).unwrap();
+        let scheduled: Vec<bool> = (0..7)
+            .map(|offset| habit.is_scheduled_on(monday + chrono::Duration::days(offset)))
+            .collect();
+        assert_eq!(scheduled, vec![true, false, true, false, true, false, false]);
+
+        let json = serde_json::to_string(&habit.frequency).unwrap();
+        assert_eq!(json, r#"{"SpecificDays":["Mon","Wed","Fri"]}"#);
+        assert_eq!(serde_json::from_str::<Frequency>(&json).unwrap(), habit.frequency);
+    }
+
+    #[test]
+    fn test_toggle_specific_day() {
+        let mut frequency = Frequency::SpecificDays(vec![Weekday::Wed]);
+        assert!(frequency.toggle_day(Weekday::Mon));
+        assert!(frequency.toggle_day(Weekday::Sun));
+        assert_eq!(frequency, Frequency::SpecificDays(vec![Weekday::Mon, Weekday::Wed, Weekday::Sun]));
+        assert!(frequency.toggle_day(Weekday::Mon));
+        assert!(frequency.toggle_day(Weekday::Sun));
+        // The last day can't be turned off
+        assert!(!frequency.toggle_day(Weekday::Wed));
+        assert_eq!(frequency, Frequency::SpecificDays(vec![Weekday::Wed]));
+        assert!(!Frequency::Daily.toggle_day(Weekday::Mon));
+    }
+
+    #[test]
+    fn test_habit_without_created_at_deserializes() {
+        let json = r#"{"id":"67e55044-10b1-426f-9247-bb680e5fe0c8","name":"Old","description":null,"order":0,"frequency":"Weekly"}"#;
+        let habit: Habit = serde_json::from_str(json).unwrap();
+        assert_eq!(habit.frequency, Frequency::Weekly);
+        assert_eq!(habit.created_at, unknown_created_at());
+        assert!(habit.auto_fill_week);
+    }
+
+    #[test]
+    fn test_skip_weekends_schedule() {
+        let mut habit = Habit::new("Work routine");
+        habit.skip_weekends = true;
+        // Oct 13, 2025 is a Monday
+        let monday = NaiveDate::from_ymd_opt(2025, 10, 13).unwrap();
+        let scheduled: Vec<bool> = (0..7)
+            .map(|offset| habit.is_scheduled_on(monday + chrono::Duration::days(offset)))
+            .collect();
+        assert_eq!(scheduled, vec![true, true, true, true, true, false, false]);
+    }
+
     #[test]
     fn test_habit_ordering() {
         let habits = default_habits();
@@ -1,39 +1,53 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// Status of a habit for a given day
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HabitStatus {
     /// Habit was completed
     Done,
+    /// Habit was partly done (half the meds, a short walk); not counted as Done
+    Partial,
     /// Habit was intentionally skipped
     Skipped,
+    /// Habit didn't apply that day (e.g. a class was cancelled); left out of completion rates
+    NotApplicable,
     /// No status recorded (default)
+    #[default]
     Unmarked,
 }
 
-impl Default for HabitStatus {
-    fn default() -> Self {
-        Self::Unmarked
-    }
-}
-
 impl HabitStatus {
-    /// Cycle through statuses: Done -> Skipped -> Unmarked -> Done
+    /// Cycle through statuses: Done -> Partial -> Skipped -> N/A -> Unmarked -> Done
     pub fn cycle(&self) -> Self {
         match self {
-            Self::Done => Self::Skipped,
-            Self::Skipped => Self::Unmarked,
+            Self::Done => Self::Partial,
+            Self::Partial => Self::Skipped,
+            Self::Skipped => Self::NotApplicable,
+            Self::NotApplicable => Self::Unmarked,
             Self::Unmarked => Self::Done,
         }
     }
 
+    /// Cycle through statuses in reverse: Done -> Unmarked -> N/A -> Skipped -> Partial -> Done
+    pub fn cycle_back(&self) -> Self {
+        match self {
+            Self::Done => Self::Unmarked,
+            Self::Unmarked => Self::NotApplicable,
+            Self::NotApplicable => Self::Skipped,
+            Self::Skipped => Self::Partial,
+            Self::Partial => Self::Done,
+        }
+    }
+
     /// Get display string for the status
     pub fn display_str(&self) -> &'static str {
         match self {
             Self::Done => "[Done]",
+            Self::Partial => "[Partial]",
             Self::Skipped => "[Skipped]",
+            Self::NotApplicable => "[N/A]",
             Self::Unmarked => "[ ]",
         }
     }
@@ -48,8 +62,12 @@ pub struct HabitLog {
     pub date: NaiveDate,
     /// Status of the habit on this day
     pub status: HabitStatus,
-    /// Optional note about this habit on this day
-    pub note: Option<String>,
+    /// Notes about this habit on this day, oldest first, each with when it was written
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<(DateTime<Local>, String)>,
+    /// The single note kept by files written before note history; moved into `notes` on load
+    #[serde(default, rename = "note", skip_serializing)]
+    pub(crate) legacy_note: Option<String>,
 }
 
 impl HabitLog {
@@ -59,7 +77,8 @@ impl HabitLog {
             habit_id,
             date,
             status: HabitStatus::default(),
-            note: None,
+            notes: Vec::new(),
+            legacy_note: None,
         }
     }
 
@@ -69,13 +88,36 @@ impl HabitLog {
             habit_id,
             date,
             status,
-            note: None,
+            notes: Vec::new(),
+            legacy_note: None,
         }
     }
 
-    /// Add or update the note for this log entry
-    pub fn set_note(&mut self, note: Option<String>) {
-        self.note = note;
+    /// Add a note written at `at` to the end of this entry's history
+    pub fn add_note(&mut self, at: DateTime<Local>, text: impl Into<String>) {
+        self.notes.push((at, text.into()));
+    }
+
+    /// The most recently written non-blank note, if any
+    pub fn latest_note(&self) -> Option<&str> {
+        self.notes.iter().rev()
+            .map(|(_, text)| text.as_str())
+            .find(|text| !text.trim().is_empty())
+    }
+
+    /// Move an old-format single note into the history, dated noon on the log's day
+    /// Returns whether there was one to move.
+    pub fn migrate_legacy_note(&mut self) -> bool {
+        let Some(note) = self.legacy_note.take() else {
+            return false;
+        };
+        if !note.trim().is_empty() {
+            let noon = self.date.and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap_or_default());
+            let at = Local.from_local_datetime(&noon).earliest()
+                .unwrap_or_else(|| Local.from_utc_datetime(&noon));
+            self.notes.insert(0, (at, note));
+        }
+        true
     }
 
     /// Toggle the status to the next value
@@ -91,15 +133,37 @@ mod tests {
 
     #[test]
     fn test_habit_status_cycle() {
-        assert_eq!(HabitStatus::Done.cycle(), HabitStatus::Skipped);
-        assert_eq!(HabitStatus::Skipped.cycle(), HabitStatus::Unmarked);
+        assert_eq!(HabitStatus::Done.cycle(), HabitStatus::Partial);
+        assert_eq!(HabitStatus::Partial.cycle(), HabitStatus::Skipped);
+        assert_eq!(HabitStatus::Skipped.cycle(), HabitStatus::NotApplicable);
+        assert_eq!(HabitStatus::NotApplicable.cycle(), HabitStatus::Unmarked);
         assert_eq!(HabitStatus::Unmarked.cycle(), HabitStatus::Done);
     }
 
+    #[test]
+    fn test_habit_status_cycle_back() {
+        assert_eq!(HabitStatus::Done.cycle_back(), HabitStatus::Unmarked);
+        assert_eq!(HabitStatus::Unmarked.cycle_back(), HabitStatus::NotApplicable);
+        assert_eq!(HabitStatus::NotApplicable.cycle_back(), HabitStatus::Skipped);
+        assert_eq!(HabitStatus::Skipped.cycle_back(), HabitStatus::Partial);
+        assert_eq!(HabitStatus::Partial.cycle_back(), HabitStatus::Done);
+        for status in [
+            HabitStatus::Done,
+            HabitStatus::Partial,
+            HabitStatus::Skipped,
+            HabitStatus::NotApplicable,
+            HabitStatus::Unmarked,
+        ] {
+            assert_eq!(status.cycle().cycle_back(), status);
+        }
+    }
+
     #[test]
     fn test_habit_status_display() {
         assert_eq!(HabitStatus::Done.display_str(), "[Done]");
+        assert_eq!(HabitStatus::Partial.display_str(), "[Partial]");
         assert_eq!(HabitStatus::Skipped.display_str(), "[Skipped]");
+        assert_eq!(HabitStatus::NotApplicable.display_str(), "[N/A]");
         assert_eq!(HabitStatus::Unmarked.display_str(), "[ ]");
     }
 
@@ -112,7 +176,7 @@ mod tests {
         assert_eq!(log.habit_id, habit_id);
         assert_eq!(log.date, date);
         assert_eq!(log.status, HabitStatus::Unmarked);
-        assert_eq!(log.note, None);
+        assert!(log.notes.is_empty());
     }
 
     #[test]
@@ -124,9 +188,15 @@ mod tests {
         log.toggle_status();
         assert_eq!(log.status, HabitStatus::Done);
 
+        log.toggle_status();
+        assert_eq!(log.status, HabitStatus::Partial);
+
         log.toggle_status();
         assert_eq!(log.status, HabitStatus::Skipped);
 
+        log.toggle_status();
+        assert_eq!(log.status, HabitStatus::NotApplicable);
+
         log.toggle_status();
         assert_eq!(log.status, HabitStatus::Unmarked);
     }
@@ -137,10 +207,32 @@ mod tests {
         let date = NaiveDate::from_ymd_opt(2025, 10, 14).unwrap();
         let mut log = HabitLog::new(habit_id, date);
 
-        log.set_note(Some("Had a tough day".to_string()));
-        assert_eq!(log.note, Some("Had a tough day".to_string()));
+        let morning = Local.with_ymd_and_hms(2025, 10, 14, 9, 0, 0).unwrap();
+        let evening = Local.with_ymd_and_hms(2025, 10, 14, 21, 0, 0).unwrap();
+        log.add_note(morning, "Had a tough day");
+        log.add_note(evening, "Better by dinner");
+        assert_eq!(log.notes.len(), 2);
+        assert_eq!(log.latest_note(), Some("Better by dinner"));
+    }
 
-        log.set_note(None);
-        assert_eq!(log.note, None);
+    #[test]
+    fn test_legacy_note_migrates_into_history() {
+        let habit_id = Uuid::new_v4();
+        let json = format!(
+            r#"{{"habit_id":"{}","date":"2025-10-14","status":"Skipped","note":"sick"}}"#,
+            habit_id
+        );
+        let mut log: HabitLog = serde_json::from_str(&json).unwrap();
+        assert!(log.notes.is_empty());
+
+        assert!(log.migrate_legacy_note());
+        assert!(!log.migrate_legacy_note());
+        assert_eq!(log.latest_note(), Some("sick"));
+        assert_eq!(log.notes[0].0.date_naive(), log.date);
+
+        // Written back in the new format only
+        let saved = serde_json::to_string(&log).unwrap();
+        assert!(saved.contains("\"notes\""));
+        assert!(!saved.contains("\"note\""));
     }
 }
@@ -1,8 +1,10 @@
 // Data models for Healing-Habits habit tracker
 pub mod habit;
 pub mod log;
+pub mod settings;
 pub mod week;
 
-pub use habit::{Frequency, Habit};
+pub use habit::{Frequency, Habit, Template};
 pub use log::{HabitLog, HabitStatus};
-pub use week::Week;
+pub use settings::Settings;
+pub use week::{Week, WeekStart};
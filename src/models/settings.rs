@@ -0,0 +1,34 @@
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+
+use super::WeekStart;
+
+/// User preferences stored alongside habit data
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Require a note before a Skipped status can be saved
+    pub require_skip_note: bool,
+    /// Saved note snippets that can be inserted while writing a note
+    pub snippets: Vec<String>,
+    /// Keep only this many of the newest export files; `None` keeps them all
+    pub export_retention: Option<usize>,
+    /// Hide the week-strip legend under the main view
+    pub hide_legend: bool,
+    /// Draw week-strip symbols with plain ASCII for terminals without Unicode fonts
+    pub ascii_glyphs: bool,
+    /// Day weeks begin on (Monday unless changed)
+    pub week_start: WeekStart,
+    /// Let Skipped days pass without breaking a streak (they still don't add to it)
+    pub skips_keep_streak: bool,
+    /// Move with h/j/k/l on the main view; habit management moves from h to m
+    pub vim_keys: bool,
+    /// Ring the terminal bell when a habit is marked Done
+    pub bell_on_complete: bool,
+    /// Offer to write down why after a habit is marked Skipped on the main view
+    pub ask_skip_reason: bool,
+    /// List Weekly habits on `weekly_day` only and As-needed habits only in their own panel
+    pub strict_frequency: bool,
+    /// Day Weekly habits are listed on in strict frequency mode (the week's first day if unset)
+    pub weekly_day: Option<Weekday>,
+}
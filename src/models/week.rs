@@ -1,32 +1,54 @@
-use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Which day a week begins on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeekStart {
+    /// Monday through Sunday (default)
+    #[default]
+    Monday,
+    /// Sunday through Saturday
+    Sunday,
+}
+
+impl WeekStart {
+    /// Get the weekday weeks begin on
+    pub fn weekday(&self) -> Weekday {
+        match self {
+            WeekStart::Monday => Weekday::Mon,
+            WeekStart::Sunday => Weekday::Sun,
+        }
+    }
+
+    /// Switch to the other start day
+    pub fn toggle(&self) -> Self {
+        match self {
+            WeekStart::Monday => WeekStart::Sunday,
+            WeekStart::Sunday => WeekStart::Monday,
+        }
+    }
+}
 
 /// Helper struct for working with weeks
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Week {
-    /// The Monday of this week
+    /// The first day of this week (a Monday unless weeks start on Sunday)
     pub start: NaiveDate,
 }
 
 impl Week {
-    /// Create a week containing the given date
+    /// Create a Monday-first week containing the given date
     pub fn containing(date: NaiveDate) -> Self {
-        let monday = Self::get_monday(date);
-        Self { start: monday }
+        Self::containing_on(date, WeekStart::Monday)
     }
 
-    /// Create a week for the current date
-    pub fn current() -> Self {
-        Self::containing(Local::now().date_naive())
+    /// Create a week containing the given date, beginning on `week_start`
+    pub fn containing_on(date: NaiveDate, week_start: WeekStart) -> Self {
+        let days_in = date.weekday().days_since(week_start.weekday());
+        Self { start: date - Duration::days(days_in as i64) }
     }
 
-    /// Get the Monday of the week containing the given date
-    fn get_monday(date: NaiveDate) -> NaiveDate {
-        let weekday = date.weekday();
-        let days_since_monday = weekday.num_days_from_monday();
-        date - Duration::days(days_since_monday as i64)
-    }
-
-    /// Get all 7 days of this week (Monday through Sunday)
+    /// Get all 7 days of this week, starting with `start`
     pub fn days(&self) -> [NaiveDate; 7] {
         [
             self.start,
@@ -39,7 +61,7 @@ impl Week {
         ]
     }
 
-    /// Get the day at a specific index (0 = Monday, 6 = Sunday)
+    /// Get the day at a specific index (0 = the week's first day, 6 = its last)
     pub fn day(&self, index: usize) -> Option<NaiveDate> {
         if index < 7 {
             Some(self.start + Duration::days(index as i64))
@@ -48,7 +70,7 @@ impl Week {
         }
     }
 
-    /// Get the Sunday of this week
+    /// Get the last day of this week
     pub fn end(&self) -> NaiveDate {
         self.start + Duration::days(6)
     }
@@ -90,17 +112,11 @@ impl Week {
         }
     }
 
-    /// Get the weekday name for a day index (0 = Mon, 6 = Sun)
-    pub fn weekday_name(index: usize) -> &'static str {
-        match index {
-            0 => "Mon",
-            1 => "Tue",
-            2 => "Wed",
-            3 => "Thu",
-            4 => "Fri",
-            5 => "Sat",
-            6 => "Sun",
-            _ => "???",
+    /// Get the short weekday name for a day index in this week ("Mon", "Sun", ...)
+    pub fn weekday_name(&self, index: usize) -> &'static str {
+        match self.day(index) {
+            Some(date) => &Self::full_weekday_name(date)[..3],
+            None => "???",
         }
     }
 
@@ -126,15 +142,15 @@ mod tests {
     fn test_get_monday() {
         // Test various days in a week
         let wed = NaiveDate::from_ymd_opt(2025, 10, 15).unwrap(); // Wednesday
-        let monday = Week::get_monday(wed);
+        let monday = Week::containing(wed).start;
         assert_eq!(monday, NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
 
         let sun = NaiveDate::from_ymd_opt(2025, 10, 19).unwrap(); // Sunday
-        let monday = Week::get_monday(sun);
+        let monday = Week::containing(sun).start;
         assert_eq!(monday, NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
 
         let mon = NaiveDate::from_ymd_opt(2025, 10, 13).unwrap(); // Monday
-        let monday = Week::get_monday(mon);
+        let monday = Week::containing(mon).start;
         assert_eq!(monday, NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
     }
 
@@ -204,9 +220,39 @@ mod tests {
 
     #[test]
     fn test_weekday_names() {
-        assert_eq!(Week::weekday_name(0), "Mon");
-        assert_eq!(Week::weekday_name(3), "Thu");
-        assert_eq!(Week::weekday_name(6), "Sun");
+        let week = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 15).unwrap());
+        assert_eq!(week.weekday_name(0), "Mon");
+        assert_eq!(week.weekday_name(3), "Thu");
+        assert_eq!(week.weekday_name(6), "Sun");
+        assert_eq!(week.weekday_name(7), "???");
+    }
+
+    #[test]
+    fn test_sunday_start_week() {
+        let wed = NaiveDate::from_ymd_opt(2025, 10, 15).unwrap();
+        let week = Week::containing_on(wed, WeekStart::Sunday);
+        assert_eq!(week.start, NaiveDate::from_ymd_opt(2025, 10, 12).unwrap());
+        assert_eq!(week.end(), NaiveDate::from_ymd_opt(2025, 10, 18).unwrap());
+        assert_eq!(week.weekday_name(0), "Sun");
+        assert_eq!(week.weekday_name(6), "Sat");
+        assert_eq!(week.days().iter().position(|&d| d == wed), Some(3));
+
+        // A Sunday begins its own week, and a Saturday ends one
+        let sun = NaiveDate::from_ymd_opt(2025, 10, 19).unwrap();
+        assert_eq!(Week::containing_on(sun, WeekStart::Sunday).start, sun);
+        let sat = NaiveDate::from_ymd_opt(2025, 10, 18).unwrap();
+        assert_eq!(Week::containing_on(sat, WeekStart::Sunday).start, week.start);
+        assert_eq!(week.next().start, sun);
+    }
+
+    #[test]
+    fn test_sunday_start_format_cross_month() {
+        let week = Week::containing_on(NaiveDate::from_ymd_opt(2025, 10, 1).unwrap(), WeekStart::Sunday);
+        assert_eq!(week.start, NaiveDate::from_ymd_opt(2025, 9, 28).unwrap());
+        assert_eq!(week.format(), "Sep 28-Oct 4, 2025");
+
+        let week = Week::containing_on(NaiveDate::from_ymd_opt(2025, 10, 15).unwrap(), WeekStart::Sunday);
+        assert_eq!(week.format(), "Oct 12-18, 2025");
     }
 
     #[test]
@@ -0,0 +1,151 @@
+// Command palette: searchable list of app actions
+use anyhow::Result;
+
+use crate::app::{App, AppView};
+
+/// An action that can be run from the command palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    ExportWeek,
+    AddHabit,
+    ManageHabits,
+    ViewStats,
+    WeekActivity,
+    WeekGrid,
+    GoToToday,
+    PreviousWeek,
+    NextWeek,
+    FirstActivity,
+    LastActivity,
+    ResetWeek,
+    ToggleSkipNotes,
+    SearchNotes,
+    Help,
+    Quit,
+}
+
+impl Command {
+    /// All commands, in the order they're listed when the query is empty
+    pub const ALL: [Command; 16] = [
+        Command::ExportWeek,
+        Command::AddHabit,
+        Command::ManageHabits,
+        Command::ViewStats,
+        Command::WeekActivity,
+        Command::WeekGrid,
+        Command::GoToToday,
+        Command::PreviousWeek,
+        Command::NextWeek,
+        Command::FirstActivity,
+        Command::LastActivity,
+        Command::ResetWeek,
+        Command::ToggleSkipNotes,
+        Command::SearchNotes,
+        Command::Help,
+        Command::Quit,
+    ];
+
+    /// Get the label shown in the palette
+    pub fn label(&self) -> &'static str {
+        match self {
+            Command::ExportWeek => "Export week",
+            Command::AddHabit => "Add habit",
+            Command::ManageHabits => "Manage habits",
+            Command::ViewStats => "View stats",
+            Command::WeekActivity => "Week activity",
+            Command::WeekGrid => "Week grid",
+            Command::GoToToday => "Go to today",
+            Command::PreviousWeek => "Previous week",
+            Command::NextWeek => "Next week",
+            Command::FirstActivity => "Jump to first activity",
+            Command::LastActivity => "Jump to last activity",
+            Command::ResetWeek => "Reset week",
+            Command::ToggleSkipNotes => "Toggle notes required on skip",
+            Command::SearchNotes => "Search notes",
+            Command::Help => "Help",
+            Command::Quit => "Quit",
+        }
+    }
+}
+
+/// Score how well a query fuzzy-matches a label (lower is better)
+/// The query's characters must appear in order; tighter, earlier matches score better
+fn fuzzy_score(query: &str, label: &str) -> Option<usize> {
+    let label: Vec<char> = label.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    for (i, q) in query.to_lowercase().chars().filter(|c| !c.is_whitespace()).enumerate() {
+        let offset = label[pos..].iter().position(|&c| c == q)?;
+        // The first character's offset counts as its start position; later ones as gaps
+        score += if i == 0 { offset } else { offset * 2 };
+        pos += offset + 1;
+    }
+    Some(score)
+}
+
+/// Get the commands matching a query, best match first (ties keep list order)
+pub fn filter_commands(query: &str) -> Vec<Command> {
+    let mut matches: Vec<(usize, Command)> = Command::ALL
+        .iter()
+        .filter_map(|&cmd| fuzzy_score(query, cmd.label()).map(|score| (score, cmd)))
+        .collect();
+    matches.sort_by_key(|&(score, _)| score);
+    matches.into_iter().map(|(_, cmd)| cmd).collect()
+}
+
+/// Run a command against the app
+pub fn execute(app: &mut App, cmd: Command) -> Result<()> {
+    app.set_view(AppView::Main);
+    match cmd {
+        Command::ExportWeek => app.start_export_preview()?,
+        Command::AddHabit => {
+            app.enter_habit_management();
+            app.start_add_habit();
+        }
+        Command::ManageHabits => app.enter_habit_management(),
+        Command::ViewStats => app.set_view(AppView::Stats),
+        Command::WeekActivity => app.set_view(AppView::Activity),
+        Command::WeekGrid => app.enter_grid_view()?,
+        Command::GoToToday => app.go_to_today(),
+        Command::PreviousWeek => app.prev_week()?,
+        Command::NextWeek => app.next_week()?,
+        Command::FirstActivity => app.jump_to_first_activity()?,
+        Command::LastActivity => app.jump_to_last_activity()?,
+        Command::ResetWeek => app.start_reset_week()?,
+        Command::ToggleSkipNotes => app.toggle_require_skip_note()?,
+        Command::SearchNotes => app.open_search()?,
+        Command::Help => app.set_view(AppView::Help),
+        Command::Quit => app.quit()?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_query_lists_everything() {
+        assert_eq!(filter_commands(""), Command::ALL.to_vec());
+    }
+
+    #[test]
+    fn test_fuzzy_filter_ordering() {
+        // "week" is contiguous in these, earliest match first
+        assert_eq!(
+            filter_commands("week"),
+            vec![
+                Command::WeekActivity,
+                Command::WeekGrid,
+                Command::NextWeek,
+                Command::ResetWeek,
+                Command::ExportWeek,
+                Command::PreviousWeek,
+            ]
+        );
+        // Subsequence matches across words, tighter match first
+        assert_eq!(filter_commands("exw"), vec![Command::NextWeek, Command::ExportWeek]);
+        assert_eq!(filter_commands("EXP"), vec![Command::ExportWeek]);
+        assert!(filter_commands("zzz").is_empty());
+    }
+}
@@ -1,47 +1,563 @@
 use anyhow::{Context, Result};
-use chrono::NaiveDate;
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{btree_map::Entry, BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::models::{Frequency, Habit, HabitLog, HabitStatus};
+use crate::models::{Frequency, Habit, HabitLog, HabitStatus, Settings, Template, Week};
+
+/// Percentage of `part` out of `total`, rounded half-up (0 when `total` is 0)
+/// Every completion rate shown on screen or exported goes through this.
+pub fn round_rate(part: usize, total: usize) -> usize {
+    if total == 0 {
+        return 0;
+    }
+    (part * 200 + total) / (total * 2)
+}
+
+/// Source of the current local date and time (swap it out in tests to pin "now")
+pub type Clock = fn() -> NaiveDateTime;
+
+/// The real clock
+pub fn system_clock() -> NaiveDateTime {
+    Local::now().naive_local()
+}
+
+/// Reject dates outside 1970-01-01 ..= one year from `today`
+/// Keeps a typo like year 20255 from creating logs that break navigation and stats.
+pub fn validate_log_date(date: NaiveDate, today: NaiveDate) -> Result<()> {
+    let earliest = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    let latest = today + Duration::days(365);
+    if date < earliest || date > latest {
+        anyhow::bail!(
+            "Date {} is out of range ({} to {})",
+            date,
+            earliest,
+            latest
+        );
+    }
+    Ok(())
+}
+
+/// Places older versions kept `habits.json`, checked in order when the current data file is missing
+pub fn legacy_data_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        paths.push(home.join(".healing-habits").join("habits.json"));
+    }
+    if let Ok(cwd) = std::env::current_dir() {
+        paths.push(cwd.join(".data").join("healing-habits").join("habits.json"));
+    }
+    paths
+}
+
+/// The legacy file to copy into `data_path`, if any
+/// Only applies when `data_path` is missing or empty, so existing data is never overwritten.
+pub fn legacy_data_to_migrate(data_path: &Path, legacy: &[PathBuf]) -> Option<PathBuf> {
+    let has_data = fs::metadata(data_path).is_ok_and(|meta| meta.len() > 0);
+    if has_data {
+        return None;
+    }
+    legacy
+        .iter()
+        .find(|path| path.as_path() != data_path && fs::metadata(path).is_ok_and(|meta| meta.len() > 0))
+        .cloned()
+}
+
+/// Copy a legacy data file into `data_path` when it's due, returning where it came from
+/// The legacy file is left in place.
+pub fn migrate_legacy_data(data_path: &Path, legacy: &[PathBuf]) -> Result<Option<PathBuf>> {
+    let Some(source) = legacy_data_to_migrate(data_path, legacy) else {
+        return Ok(None);
+    };
+    if let Some(parent) = data_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(&source, data_path)
+        .with_context(|| format!("Failed to copy {} to {}", source.display(), data_path.display()))?;
+    Ok(Some(source))
+}
+
+/// Version of the data file format this build writes
+/// Files without a version are from before it was recorded and count as 1.
+pub const DATA_SCHEMA: u32 = 1;
+
+fn default_schema_version() -> u32 {
+    1
+}
 
 /// Storage container for all habit tracking data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HabitData {
+    /// Format version the file was written with, for migrations to branch on
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
     /// All habits being tracked
     pub habits: Vec<Habit>,
-    /// Log entries for habits on specific dates
-    pub logs: Vec<HabitLog>,
+    /// Log entries for habits on specific dates, keyed by (habit, date)
+    /// Stored on disk as a plain list
+    #[serde(with = "logs_as_vec")]
+    pub logs: BTreeMap<(Uuid, NaiveDate), HabitLog>,
+    /// User preferences
+    #[serde(default)]
+    pub settings: Settings,
+    /// Reflection notes for whole weeks, keyed by the week's first day
+    #[serde(default)]
+    pub week_notes: Vec<(NaiveDate, String)>,
+    /// Vacations (first and last day, inclusive), kept sorted and non-overlapping
+    /// Unmarked and Skipped days inside one don't count against completion rates.
+    #[serde(default)]
+    pub vacations: Vec<(NaiveDate, NaiveDate)>,
+    /// First day of the week on screen when the app last quit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_week_start: Option<NaiveDate>,
+    /// Selected day (0 = first day of the week) when the app last quit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_selected_day: Option<usize>,
+}
+
+impl HabitData {
+    /// Move old-format single notes into each log's note history
+    /// Returns whether any log was in the old format.
+    pub fn migrate_legacy_notes(&mut self) -> bool {
+        let mut migrated = false;
+        for log in self.logs.values_mut() {
+            migrated |= log.migrate_legacy_note();
+        }
+        migrated
+    }
+
+    /// Give habits saved without a creation date the date of their earliest log,
+    /// or `today` if they have none. Returns whether any habit changed.
+    pub fn backfill_created_at(&mut self, today: NaiveDate) -> bool {
+        let unknown = crate::models::habit::unknown_created_at();
+        let mut changed = false;
+        for habit in self.habits.iter_mut().filter(|h| h.created_at == unknown) {
+            // The first entry in the habit's key range has its earliest date
+            habit.created_at = self.logs
+                .range((habit.id, NaiveDate::MIN)..=(habit.id, NaiveDate::MAX))
+                .next()
+                .map(|(&(_, date), _)| date)
+                .unwrap_or(today);
+            changed = true;
+        }
+        changed
+    }
 }
 
 impl Default for HabitData {
     fn default() -> Self {
         Self {
+            schema_version: DATA_SCHEMA,
             habits: crate::models::habit::default_habits(),
-            logs: Vec::new(),
+            logs: BTreeMap::new(),
+            settings: Settings::default(),
+            week_notes: Vec::new(),
+            vacations: Vec::new(),
+            last_week_start: None,
+            last_selected_day: None,
         }
     }
 }
 
+/// (De)serialize the log map as a list of log entries, keeping the file format unchanged
+mod logs_as_vec {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        logs: &BTreeMap<(Uuid, NaiveDate), HabitLog>,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(logs.values())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<BTreeMap<(Uuid, NaiveDate), HabitLog>, D::Error> {
+        // Later duplicates of the same habit and date replace earlier ones
+        let logs = Vec::<HabitLog>::deserialize(deserializer)?;
+        Ok(logs.into_iter().map(|l| ((l.habit_id, l.date), l)).collect())
+    }
+}
+
+/// Which of two statuses logged on the same day survives a merge (higher wins)
+fn merge_rank(status: HabitStatus) -> u8 {
+    match status {
+        HabitStatus::Done => 4,
+        HabitStatus::Partial => 3,
+        HabitStatus::Skipped => 2,
+        HabitStatus::NotApplicable => 1,
+        HabitStatus::Unmarked => 0,
+    }
+}
+
+/// How many days of a date range a habit spent in each status, from `get_stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HabitStats {
+    pub done: usize,
+    pub partial: usize,
+    pub skipped: usize,
+    pub unmarked: usize,
+}
+
+impl HabitStats {
+    /// Days that count toward the completion rate (everything but N/A)
+    pub fn trackable(&self) -> usize {
+        self.done + self.partial + self.skipped + self.unmarked
+    }
+
+    /// Days given a status (Done, Partial or Skipped), for the rate over marked days only
+    /// Shown next to the completion rate, which also counts unmarked days against it.
+    pub fn marked(&self) -> usize {
+        self.done + self.partial + self.skipped
+    }
+}
+
+/// Read a data file or backup, failing with a clear error if it isn't one
+/// (including one written by a newer version, or with logs dated out of range as of `today`)
+pub fn read_data_file(path: &Path, today: NaiveDate) -> Result<HabitData> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut data: HabitData = serde_json::from_str(&contents)
+        .with_context(|| format!("{} is not a valid habit data file", path.display()))?;
+    if data.schema_version > DATA_SCHEMA {
+        anyhow::bail!(
+            "{} is from a newer version of the app (format {})",
+            path.display(),
+            data.schema_version
+        );
+    }
+    // Held to the same rules as data entered here, and normalized like a loaded file
+    for &(_, date) in data.logs.keys() {
+        validate_log_date(date, today)
+            .with_context(|| format!("{} has a log that can't be kept", path.display()))?;
+    }
+    data.migrate_legacy_notes();
+    data.backfill_created_at(today);
+    Ok(data)
+}
+
+/// Where an unreadable data file is moved, e.g. `habits.corrupt-20251015-093000.json`
+pub fn corrupt_path(data_path: &Path, at: chrono::NaiveDateTime) -> PathBuf {
+    let stem = data_path.file_stem().unwrap_or_default().to_string_lossy();
+    data_path.with_file_name(format!("{}.corrupt-{}.json", stem, at.format("%Y%m%d-%H%M%S")))
+}
+
+/// Lock file marking a data file as in use, e.g. `habits.json.lock`
+pub fn lock_path(data_path: &Path) -> PathBuf {
+    let mut name = data_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    data_path.with_file_name(name)
+}
+
+/// Who, if anyone, holds a data file's lock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockState {
+    /// No lock file
+    Free,
+    /// A lock left by a process that's no longer running (or unreadable); safe to reclaim
+    Stale,
+    /// Another running process holds the lock
+    Held(u32),
+}
+
+/// Check the lock file at `path`, using `is_running` to tell whether its PID is alive
+pub fn check_lock(path: &Path, is_running: impl Fn(u32) -> bool) -> LockState {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return LockState::Free;
+    };
+    match contents.trim().parse::<u32>() {
+        Ok(pid) if pid != std::process::id() && is_running(pid) => LockState::Held(pid),
+        _ => LockState::Stale,
+    }
+}
+
+/// Whether a process with this PID is running
+pub fn process_running(pid: u32) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        Path::new("/proc").join(pid.to_string()).exists()
+    }
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        std::process::Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+    #[cfg(windows)]
+    {
+        std::process::Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .output()
+            .is_ok_and(|out| String::from_utf8_lossy(&out.stdout).contains(&pid.to_string()))
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = pid;
+        false
+    }
+}
+
+/// This process's claim on a data file; the lock file is removed when it's dropped
+/// (on a clean exit or while unwinding from a panic)
+#[derive(Debug)]
+pub struct DataLock {
+    path: PathBuf,
+}
+
+impl DataLock {
+    /// Write our PID to the lock file, replacing whatever was there
+    pub fn claim(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        fs::write(&path, std::process::id().to_string())
+            .with_context(|| format!("Failed to write lock file {}", path.display()))?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for DataLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// What merging another data file would change, shown before anything is applied
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportDiff {
+    /// Names of habits that don't exist here yet
+    pub habits_added: Vec<String>,
+    /// Existing habits whose settings differ
+    pub habits_updated: Vec<HabitChange>,
+    /// Logs for days with nothing recorded here
+    pub logs_added: usize,
+    /// Days where the import disagrees with what's recorded here, as (habit name, date)
+    pub logs_overwritten: Vec<(String, NaiveDate)>,
+}
+
+impl ImportDiff {
+    /// Whether applying the import would change nothing
+    pub fn is_empty(&self) -> bool {
+        self.habits_added.is_empty()
+            && self.habits_updated.is_empty()
+            && self.logs_added == 0
+            && self.logs_overwritten.is_empty()
+    }
+}
+
+/// An existing habit's fields that an import would change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HabitChange {
+    /// Habit being changed
+    pub habit_id: Uuid,
+    /// Its name before the import
+    pub name: String,
+    /// (field, current value, imported value) for each field that differs
+    pub fields: Vec<(&'static str, String, String)>,
+}
+
+/// Compare the user-editable fields of two versions of a habit
+fn habit_field_changes(current: &Habit, imported: &Habit) -> Vec<(&'static str, String, String)> {
+    let fields = [
+        ("name", current.name.clone(), imported.name.clone()),
+        ("label", current.label.clone().unwrap_or_default(), imported.label.clone().unwrap_or_default()),
+        ("description", current.description.clone().unwrap_or_default(), imported.description.clone().unwrap_or_default()),
+        ("frequency", current.frequency.description(), imported.frequency.description()),
+        ("tags", current.tags.join(", "), imported.tags.join(", ")),
+        ("weekends off", current.skip_weekends.to_string(), imported.skip_weekends.to_string()),
+        ("auto-skip", current.auto_fill_week.to_string(), imported.auto_fill_week.to_string()),
+        ("archived", current.archived.to_string(), imported.archived.to_string()),
+        ("reminder", reminder_text(current.remind_at), reminder_text(imported.remind_at)),
+        ("weekly goal", goal_text(current.weekly_goal), goal_text(imported.weekly_goal)),
+        ("color", color_text(&current.color), color_text(&imported.color)),
+    ];
+    fields.into_iter().filter(|(_, from, to)| from != to).collect()
+}
+
+/// A reminder time as shown in the import preview ("none" when there isn't one)
+fn reminder_text(remind_at: Option<NaiveTime>) -> String {
+    remind_at.map_or("none".to_string(), |time| time.format("%H:%M").to_string())
+}
+
+/// A habit color as shown in the import preview ("none" when there isn't one)
+fn color_text(color: &Option<String>) -> String {
+    color.clone().unwrap_or_else(|| "none".to_string())
+}
+
+/// A weekly goal as shown in the import preview ("none" when there isn't one)
+fn goal_text(weekly_goal: Option<u32>) -> String {
+    weekly_goal.map_or("none".to_string(), |goal| format!("{} per week", goal))
+}
+
+/// A note being typed, kept beside the data file so it survives a crash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteDraft {
+    /// Habit the note belongs to
+    pub habit_id: Uuid,
+    /// Day the note belongs to
+    pub date: NaiveDate,
+    /// Text typed so far
+    pub text: String,
+}
+
+/// Habits, logs, vacations and week notes as they stood at one moment, for checking undo in tests
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    habits: Vec<Habit>,
+    logs: BTreeMap<(Uuid, NaiveDate), HabitLog>,
+    vacations: Vacations,
+    week_notes: WeekNotes,
+}
+
+/// How things stood before the action in progress touched them (see `begin_change`)
+#[derive(Debug)]
+struct Journal {
+    /// Habits, vacations and week notes are short lists, so they're copied whole
+    habits: Vec<Habit>,
+    vacations: Vacations,
+    week_notes: WeekNotes,
+    /// Logs grow with history, so only those the action touches are kept, as first seen
+    logs: BTreeMap<LogKey, Option<HabitLog>>,
+}
+
+/// A log's key: which habit, on which day
+type LogKey = (Uuid, NaiveDate);
+
+/// Every vacation as (first day, last day)
+type Vacations = Vec<(NaiveDate, NaiveDate)>;
+
+/// Every week note as (week start, note)
+type WeekNotes = Vec<(NaiveDate, String)>;
+
+/// What one action changed, as (before, after) values, so it can be undone and redone
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Change {
+    habits: Vec<(Uuid, Option<Habit>, Option<Habit>)>,
+    logs: Vec<(LogKey, Option<HabitLog>, Option<HabitLog>)>,
+    /// The whole vacation list before and after, if it changed
+    vacations: Option<(Vacations, Vacations)>,
+    /// The whole week note list before and after, if it changed
+    week_notes: Option<(WeekNotes, WeekNotes)>,
+}
+
+impl Change {
+    /// Whether the action left habits, logs, vacations and week notes as they were
+    pub fn is_empty(&self) -> bool {
+        self.habits.is_empty() && self.logs.is_empty() && self.vacations.is_none() && self.week_notes.is_none()
+    }
+}
+
 /// Manages persistence of habit data to/from JSON
 pub struct Storage {
     file_path: PathBuf,
     data: HabitData,
+    template: Template,
+    /// In-memory changes not yet written to disk
+    dirty: bool,
+    /// Why the last write failed, until someone reports it
+    save_error: Option<String>,
+    /// Never write to disk (another instance owns the file)
+    read_only: bool,
+    /// Something about the data file the user should hear about once the app is up
+    load_warning: Option<String>,
+    /// Why the data file couldn't be read; it's left alone until `set_aside_unreadable`
+    unreadable: Option<String>,
+    /// Where "now" comes from for dating new habits and checking log dates
+    clock: Clock,
+    /// What the action being recorded for undo has touched so far
+    journal: Option<Journal>,
+    /// Times pending changes were written out
+    #[cfg(test)]
+    writes: usize,
 }
 
 impl Storage {
     /// Create a new storage instance with the given file path
     pub fn new(file_path: impl Into<PathBuf>) -> Self {
+        Self::with_template(file_path, Template::default())
+    }
+
+    /// Create a storage instance that starts a fresh data file with `template`'s habits
+    /// (an existing data file is loaded as-is)
+    pub fn with_template(file_path: impl Into<PathBuf>, template: Template) -> Self {
         Self {
             file_path: file_path.into(),
-            data: HabitData::default(),
+            data: HabitData {
+                habits: template.habits(),
+                ..HabitData::default()
+            },
+            template,
+            dirty: false,
+            save_error: None,
+            read_only: false,
+            load_warning: None,
+            unreadable: None,
+            clock: system_clock,
+            journal: None,
+            #[cfg(test)]
+            writes: 0,
+        }
+    }
+
+    /// Use `clock` for "now" from here on
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    /// Current local date and time, according to the storage's clock
+    pub fn now(&self) -> NaiveDateTime {
+        (self.clock)()
+    }
+
+    /// Today's date, according to the storage's clock
+    pub fn today(&self) -> NaiveDate {
+        self.now().date()
+    }
+
+    /// Data for a brand-new store
+    /// The default template gives way to a `defaults.json` next to the data file, if there is one.
+    fn fresh_data(&mut self) -> HabitData {
+        let mut habits = self.template.habits();
+        let defaults = crate::models::habit::defaults_path(&self.file_path);
+        if self.template == Template::Default {
+            if let Ok(contents) = fs::read_to_string(&defaults) {
+                match crate::models::habit::habits_from_json(&contents) {
+                    Ok(seeded) => habits = seeded,
+                    Err(problem) => self.warn(format!(
+                        "Ignoring {} ({}) - starting with the built-in habits",
+                        defaults.display(),
+                        problem
+                    )),
+                }
+            }
         }
+        let today = self.today();
+        for habit in &mut habits {
+            habit.created_at = today;
+        }
+        HabitData {
+            habits,
+            ..HabitData::default()
+        }
+    }
+
+    /// Add to the warning shown once the app is up
+    fn warn(&mut self, message: String) {
+        self.load_warning = Some(match self.load_warning.take() {
+            Some(earlier) => format!("{} - {}", earlier, message),
+            None => message,
+        });
     }
 
     /// Load data from disk, or create new data if file doesn't exist
+    /// A file that isn't valid habit data is left in place, with fresh data in memory, until
+    /// `set_aside_unreadable` (called once the lock is held); one from a newer version is opened
+    /// read-only. Either way `take_load_warning` explains.
     pub fn load(&mut self) -> Result<()> {
         if self.file_path.exists() {
             let contents = fs::read_to_string(&self.file_path)
@@ -49,15 +565,33 @@ impl Storage {
 
             // Handle empty file (treat as new)
             if contents.trim().is_empty() {
-                self.data = HabitData::default();
+                self.data = self.fresh_data();
                 self.save()?;
             } else {
-                self.data = serde_json::from_str(&contents)
-                    .context("Failed to parse habit data JSON")?;
+                self.data = match serde_json::from_str(&contents) {
+                    Ok(data) => data,
+                    Err(err) => {
+                        self.unreadable = Some(err.to_string());
+                        self.data = self.fresh_data();
+                        return Ok(());
+                    }
+                };
+                if self.data.schema_version > DATA_SCHEMA {
+                    self.read_only = true;
+                    self.warn(format!(
+                        "This data file is from a newer version of the app (format {}), so it's open read-only",
+                        self.data.schema_version
+                    ));
+                    return Ok(());
+                }
+                let migrated = self.data.migrate_legacy_notes();
+                if self.data.backfill_created_at(self.today()) | migrated {
+                    self.save()?;
+                }
             }
         } else {
-            // File doesn't exist, use default data
-            self.data = HabitData::default();
+            // File doesn't exist, start from the template
+            self.data = self.fresh_data();
             // Create parent directory if needed
             if let Some(parent) = self.file_path.parent() {
                 fs::create_dir_all(parent)
@@ -69,197 +603,1787 @@ impl Storage {
         Ok(())
     }
 
+    /// Move a data file `load` couldn't read aside and start the fresh one in its place
+    /// Only call this while holding the lock, so another running copy's file is never moved.
+    pub fn set_aside_unreadable(&mut self) -> Result<()> {
+        let Some(err) = self.unreadable.take() else {
+            return Ok(());
+        };
+        let aside = corrupt_path(&self.file_path, self.now());
+        fs::rename(&self.file_path, &aside)
+            .context("Failed to move the unreadable habit data file aside")?;
+        self.warn(format!(
+            "Your data file couldn't be read ({}), so it was moved to {} and a fresh one started",
+            err,
+            aside.display()
+        ));
+        self.mark_dirty()?;
+        self.flush()
+    }
+
+    /// Why the data file couldn't be read, while it's still in place
+    pub fn unreadable(&self) -> Option<&str> {
+        self.unreadable.as_deref()
+    }
+
     /// Save current data to disk
+    /// The data goes to a temporary file that then replaces the data file, so nothing reading
+    /// it (another running copy, say) ever sees it half-written.
     pub fn save(&self) -> Result<()> {
         let json = serde_json::to_string_pretty(&self.data)
             .context("Failed to serialize habit data")?;
-        fs::write(&self.file_path, json)
+        let mut temp_name = std::ffi::OsString::from(".");
+        temp_name.push(self.file_path.file_name().unwrap_or_default());
+        temp_name.push(".tmp");
+        let temp_path = self.file_path.with_file_name(temp_name);
+        fs::write(&temp_path, json)
             .context("Failed to write habit data file")?;
+        fs::rename(&temp_path, &self.file_path)
+            .context("Failed to replace habit data file")?;
         Ok(())
     }
 
-    /// Get all habits, sorted by order
-    pub fn habits(&self) -> Vec<&Habit> {
-        let mut habits: Vec<&Habit> = self.data.habits.iter().collect();
-        habits.sort_by_key(|h| h.order);
-        habits
+    /// Record an in-memory change; it reaches disk on the next flush
+    /// Returns Ok so mutators can end with it.
+    fn mark_dirty(&mut self) -> Result<()> {
+        self.dirty = true;
+        Ok(())
     }
 
-    /// Get a habit by ID
-    pub fn get_habit(&self, id: Uuid) -> Option<&Habit> {
-        self.data.habits.iter().find(|h| h.id == id)
+    /// Flush pending changes, keeping a failure for `take_save_error` rather than returning it
+    /// A failed write keeps the change in memory and leaves the store dirty so the next flush retries.
+    pub fn flush_or_report(&mut self) {
+        if let Err(err) = self.flush() {
+            self.save_error = Some(format!("{:#}", err));
+        }
     }
 
-    /// Add a new habit
-    pub fn add_habit(&mut self, name: String) -> Result<()> {
-        let order = self.data.habits.len();
-        let mut habit = Habit::new(&name);
-        habit.order = order;
-        self.data.habits.push(habit);
-        self.save()
+    /// Take the warning from loading the data file, if there was one
+    pub fn take_load_warning(&mut self) -> Option<String> {
+        self.load_warning.take()
     }
 
-    /// Update an existing habit
-    pub fn update_habit(&mut self, habit: Habit) -> Result<()> {
-        if let Some(existing) = self.data.habits.iter_mut().find(|h| h.id == habit.id) {
-            *existing = habit;
-            self.save()
-        } else {
-            anyhow::bail!("Habit not found")
+    /// Take the reason the last write failed, if one hasn't been reported yet
+    pub fn take_save_error(&mut self) -> Option<String> {
+        self.save_error.take()
+    }
+
+    /// Write pending changes to disk, if there are any (never in read-only mode)
+    /// The store stays dirty if the write fails.
+    pub fn flush(&mut self) -> Result<()> {
+        // An unreadable file stays as it is until it's been moved aside
+        if self.dirty && !self.read_only && self.unreadable.is_none() {
+            self.save()?;
+            self.dirty = false;
+            #[cfg(test)]
+            {
+                self.writes += 1;
+            }
         }
+        Ok(())
     }
 
-    /// Delete a habit and all its logs
-    pub fn delete_habit(&mut self, id: Uuid) -> Result<()> {
-        self.data.habits.retain(|h| h.id != id);
-        self.data.logs.retain(|l| l.habit_id != id);
-        self.save()
+    /// Stop (or resume) writing changes to disk; changes still apply in memory
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
     }
 
-    /// Update a habit's name
-    pub fn update_habit_name(&mut self, id: Uuid, name: String) -> Result<()> {
-        if let Some(habit) = self.data.habits.iter_mut().find(|h| h.id == id) {
-            habit.name = name;
-            self.save()
-        } else {
-            anyhow::bail!("Habit not found")
-        }
+    /// Whether changes are kept out of the data file
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
     }
 
-    /// Update a habit's frequency
-    pub fn update_habit_frequency(&mut self, id: Uuid, frequency: Frequency) -> Result<()> {
-        if let Some(habit) = self.data.habits.iter_mut().find(|h| h.id == id) {
-            habit.frequency = frequency;
-            self.save()
-        } else {
-            anyhow::bail!("Habit not found")
+    /// Whether there are changes that haven't reached disk yet
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// How many times flushing has written the file
+    #[cfg(test)]
+    pub(crate) fn write_count(&self) -> usize {
+        self.writes
+    }
+
+    /// Capture the current habits, logs, vacations and week notes
+    #[cfg(test)]
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            habits: self.data.habits.clone(),
+            logs: self.data.logs.clone(),
+            vacations: self.data.vacations.clone(),
+            week_notes: self.data.week_notes.clone(),
         }
     }
 
-    /// Remove a habit (alias for delete_habit)
-    pub fn remove_habit(&mut self, id: Uuid) -> Result<()> {
-        self.delete_habit(id)
+    /// Start keeping track of what changes, for `finish_change`
+    pub fn begin_change(&mut self) {
+        self.journal = Some(Journal {
+            habits: self.data.habits.clone(),
+            vacations: self.data.vacations.clone(),
+            week_notes: self.data.week_notes.clone(),
+            logs: BTreeMap::new(),
+        });
     }
 
-    /// Reorder a habit to a new position
-    pub fn reorder_habit(&mut self, id: Uuid, new_order: usize) -> Result<()> {
-        // Find the habit's current position
-        if let Some(current_idx) = self.data.habits.iter().position(|h| h.id == id) {
-            let habit = self.data.habits.remove(current_idx);
-            let insert_idx = new_order.min(self.data.habits.len());
-            self.data.habits.insert(insert_idx, habit);
+    /// Note a log's value before it's first changed, while a change is being tracked
+    fn journal_log(&mut self, key: LogKey) {
+        if let Some(journal) = &mut self.journal {
+            journal.logs.entry(key).or_insert_with(|| self.data.logs.get(&key).cloned());
+        }
+    }
 
-            // Update all habit orders
-            for (idx, habit) in self.data.habits.iter_mut().enumerate() {
-                habit.order = idx;
+    /// Stop tracking and work out what changed in habits, logs, vacations and week notes since `begin_change`
+    pub fn finish_change(&mut self) -> Change {
+        let mut change = Change::default();
+        let Some(before) = self.journal.take() else {
+            return change;
+        };
+
+        for old in &before.habits {
+            let new = self.get_habit(old.id);
+            if new != Some(old) {
+                change.habits.push((old.id, Some(old.clone()), new.cloned()));
             }
+        }
+        for new in &self.data.habits {
+            if !before.habits.iter().any(|h| h.id == new.id) {
+                change.habits.push((new.id, None, Some(new.clone())));
+            }
+        }
 
-            self.save()
-        } else {
-            anyhow::bail!("Habit not found")
+        for (key, old) in before.logs {
+            let new = self.data.logs.get(&key);
+            if new != old.as_ref() {
+                change.logs.push((key, old, new.cloned()));
+            }
+        }
+
+        if before.vacations != self.data.vacations {
+            change.vacations = Some((before.vacations, self.data.vacations.clone()));
         }
+        if before.week_notes != self.data.week_notes {
+            change.week_notes = Some((before.week_notes, self.data.week_notes.clone()));
+        }
+
+        change
     }
 
-    /// Get a log entry for a specific habit and date
-    pub fn get_log(&self, habit_id: Uuid, date: NaiveDate) -> Option<&HabitLog> {
-        self.data.logs.iter()
-            .find(|l| l.habit_id == habit_id && l.date == date)
+    /// Put back the values a change replaced
+    pub fn undo_change(&mut self, change: &Change) -> Result<()> {
+        self.restore(
+            change.habits.iter().map(|(id, before, _)| (*id, before)),
+            change.logs.iter().map(|(key, before, _)| (*key, before)),
+            change.vacations.as_ref().map(|(before, _)| before),
+            change.week_notes.as_ref().map(|(before, _)| before),
+        )
     }
 
-    /// Get all logs for a specific date
-    pub fn get_logs_for_date(&self, date: NaiveDate) -> Vec<&HabitLog> {
-        self.data.logs.iter()
-            .filter(|l| l.date == date)
-            .collect()
+    /// Apply a change again after it was undone
+    pub fn redo_change(&mut self, change: &Change) -> Result<()> {
+        self.restore(
+            change.habits.iter().map(|(id, _, after)| (*id, after)),
+            change.logs.iter().map(|(key, _, after)| (*key, after)),
+            change.vacations.as_ref().map(|(_, after)| after),
+            change.week_notes.as_ref().map(|(_, after)| after),
+        )
     }
 
-    /// Get or create a log entry for a habit and date
-    pub fn get_or_create_log(&mut self, habit_id: Uuid, date: NaiveDate) -> &mut HabitLog {
-        // Check if log exists
-        if let Some(pos) = self.data.logs.iter().position(|l| l.habit_id == habit_id && l.date == date) {
-            &mut self.data.logs[pos]
-        } else {
-            // Create new log
-            let log = HabitLog::new(habit_id, date);
-            self.data.logs.push(log);
-            self.data.logs.last_mut().unwrap()
+    /// Set each habit and log to the given value, removing those given as None,
+    /// and the vacation and week note lists too if they're given
+    fn restore<'a>(
+        &mut self,
+        habits: impl Iterator<Item = (Uuid, &'a Option<Habit>)>,
+        logs: impl Iterator<Item = (LogKey, &'a Option<HabitLog>)>,
+        vacations: Option<&Vacations>,
+        week_notes: Option<&WeekNotes>,
+    ) -> Result<()> {
+        for (id, habit) in habits {
+            self.data.habits.retain(|h| h.id != id);
+            if let Some(habit) = habit {
+                self.data.habits.push(habit.clone());
+            }
         }
+        // Keep list positions in step with each habit's order
+        self.data.habits.sort_by_key(|h| (h.archived, h.order));
+
+        for (key, log) in logs {
+            match log {
+                Some(log) => self.data.logs.insert(key, log.clone()),
+                None => self.data.logs.remove(&key),
+            };
+        }
+        if let Some(vacations) = vacations {
+            self.data.vacations = vacations.clone();
+        }
+        if let Some(week_notes) = week_notes {
+            self.data.week_notes = week_notes.clone();
+        }
+        self.mark_dirty()
     }
 
-    /// Update a log entry status
-    pub fn update_log_status(&mut self, habit_id: Uuid, date: NaiveDate, status: HabitStatus) -> Result<()> {
-        let log = self.get_or_create_log(habit_id, date);
-        log.status = status;
-        self.save()
+    /// Sidecar file holding the in-progress note, next to the data file
+    fn draft_path(&self) -> PathBuf {
+        self.file_path.with_file_name("note-draft.json")
     }
 
-    /// Toggle a log entry status
-    pub fn toggle_log_status(&mut self, habit_id: Uuid, date: NaiveDate) -> Result<HabitStatus> {
-        let log = self.get_or_create_log(habit_id, date);
-        log.toggle_status();
-        let new_status = log.status;
-        self.save()?;
-        Ok(new_status)
+    /// Write the in-progress note draft
+    pub fn save_note_draft(&self, draft: &NoteDraft) -> Result<()> {
+        let json = serde_json::to_string(draft).context("Failed to serialize note draft")?;
+        fs::write(self.draft_path(), json).context("Failed to write note draft")
     }
 
-    /// Update a log entry note
-    pub fn update_log_note(&mut self, habit_id: Uuid, date: NaiveDate, note: Option<String>) -> Result<()> {
-        let log = self.get_or_create_log(habit_id, date);
-        log.set_note(note);
-        self.save()
+    /// Read the saved note draft, if there is a readable one
+    pub fn load_note_draft(&self) -> Option<NoteDraft> {
+        let contents = fs::read_to_string(self.draft_path()).ok()?;
+        serde_json::from_str(&contents).ok()
     }
 
-    /// Get completion statistics for a date range
-    pub fn get_stats(&self, start_date: NaiveDate, end_date: NaiveDate) -> HashMap<Uuid, (usize, usize, usize)> {
-        let mut stats: HashMap<Uuid, (usize, usize, usize)> = HashMap::new();
-
-        for habit in &self.data.habits {
-            let mut done = 0;
-            let mut skipped = 0;
-            let mut unmarked = 0;
-
-            let mut current = start_date;
-            while current <= end_date {
-                if let Some(log) = self.get_log(habit.id, current) {
-                    match log.status {
-                        HabitStatus::Done => done += 1,
-                        HabitStatus::Skipped => skipped += 1,
-                        HabitStatus::Unmarked => unmarked += 1,
-                    }
-                } else {
-                    unmarked += 1;
-                }
-                current = current.succ_opt().unwrap();
+    /// Remove the saved note draft
+    pub fn clear_note_draft(&self) -> Result<()> {
+        match fs::remove_file(self.draft_path()) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                Err(err).context("Failed to remove note draft")
             }
-
-            stats.insert(habit.id, (done, skipped, unmarked));
+            _ => Ok(()),
         }
-
-        stats
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::NamedTempFile;
+    /// The habit here that an imported habit lands on: the one with its ID, or else
+    /// one with the same name and label (a backup from another install has other IDs)
+    fn import_target(&self, habit: &Habit) -> Option<&Habit> {
+        self.get_habit(habit.id).or_else(|| {
+            self.data.habits.iter()
+                .find(|h| h.same_identity(&habit.name, habit.label.as_deref()))
+        })
+    }
 
-    #[test]
-    fn test_storage_new() {
-        let temp_file = NamedTempFile::new().unwrap();
-        let storage = Storage::new(temp_file.path());
-        assert_eq!(storage.data.habits.len(), 4); // Default habits
+    /// Map each imported habit's ID to the ID it will have here
+    fn import_ids(&self, imported: &HabitData) -> HashMap<Uuid, Uuid> {
+        imported.habits.iter()
+            .map(|h| (h.id, self.import_target(h).map_or(h.id, |t| t.id)))
+            .collect()
     }
 
-    #[test]
-    fn test_storage_save_and_load() {
-        let temp_file = NamedTempFile::new().unwrap();
-        let path = temp_file.path().to_path_buf();
+    /// Work out what merging `imported` into this store would change, without changing anything
+    /// Habits are matched as in `apply_import`; a log differing in status or note counts as an overwrite.
+    pub fn preview_import(&self, imported: &HabitData) -> ImportDiff {
+        let mut diff = ImportDiff::default();
+        for habit in &imported.habits {
+            match self.import_target(habit) {
+                None => diff.habits_added.push(habit.display_name()),
+                Some(current) => {
+                    let fields = habit_field_changes(current, habit);
+                    if !fields.is_empty() {
+                        diff.habits_updated.push(HabitChange {
+                            habit_id: current.id,
+                            name: current.display_name(),
+                            fields,
+                        });
+                    }
+                }
+            }
+        }
 
-        {
-            let mut storage = Storage::new(&path);
-            storage.load().unwrap();
-            assert_eq!(storage.data.habits.len(), 4);
+        let ids = self.import_ids(imported);
+        for log in imported.logs.values() {
+            // Logs for a habit the backup doesn't have are left out of the import
+            let Some(&habit_id) = ids.get(&log.habit_id) else { continue };
+            match self.data.logs.get(&(habit_id, log.date)) {
+                None => diff.logs_added += 1,
+                Some(current) if current.status != log.status || current.notes != log.notes => {
+                    let name = self.get_habit(habit_id)
+                        .or_else(|| imported.habits.iter().find(|h| h.id == habit_id))
+                        .map(|h| h.display_name())
+                        .unwrap_or_else(|| "Unknown".to_string());
+                    diff.logs_overwritten.push((name, log.date));
+                }
+                Some(_) => {}
+            }
+        }
+        diff
+    }
+
+    /// Merge `imported` into this store: new habits are appended, existing ones take the
+    /// imported settings (keeping their place in the list and their ID), and imported logs win
+    /// Habits are matched by ID, then by name and label. Logs for habits the backup
+    /// doesn't have are dropped, and a log dated out of range refuses the whole import.
+    pub fn apply_import(&mut self, imported: HabitData) -> Result<()> {
+        for &(_, date) in imported.logs.keys() {
+            validate_log_date(date, self.today())?;
+        }
+        let ids = self.import_ids(&imported);
+        for mut habit in imported.habits {
+            habit.id = ids[&habit.id];
+            match self.data.habits.iter_mut().find(|h| h.id == habit.id) {
+                Some(existing) => {
+                    habit.order = existing.order;
+                    *existing = habit;
+                }
+                None => {
+                    habit.order = self.data.habits.len();
+                    self.data.habits.push(habit);
+                }
+            }
+        }
+        for mut log in imported.logs.into_values() {
+            let Some(&habit_id) = ids.get(&log.habit_id) else { continue };
+            log.habit_id = habit_id;
+            self.journal_log((habit_id, log.date));
+            self.data.logs.insert((habit_id, log.date), log);
+        }
+        self.mark_dirty()
+    }
+
+    /// Write everything (habits, logs, settings and week notes) to a backup file
+    /// Missing parent directories are created.
+    pub fn export_all(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(&self.data)
+            .context("Failed to serialize habit data")?;
+        fs::write(path, json)
+            .with_context(|| format!("Failed to write backup to {}", path.display()))
+    }
+
+    /// Restore a backup written by `export_all` (or any data file)
+    /// With `merge` it's combined as in `apply_import`; otherwise it replaces everything.
+    /// The file is fully read and parsed first, so a bad file changes nothing.
+    pub fn import_all(&mut self, path: &Path, merge: bool) -> Result<()> {
+        let imported = read_data_file(path, self.today())?;
+        if merge {
+            self.apply_import(imported)
+        } else {
+            self.replace_data(imported)
+        }
+    }
+
+    /// Whether `other` has exactly the habits and logs held here
+    pub fn matches(&self, other: &HabitData) -> bool {
+        self.data.habits == other.habits && self.data.logs == other.logs
+    }
+
+    /// Replace all habits, logs, settings and week notes
+    pub fn replace_data(&mut self, data: HabitData) -> Result<()> {
+        self.data = data;
+        self.mark_dirty()
+    }
+
+    /// Get the week containing a date, beginning on the configured start day
+    pub fn week_of(&self, date: NaiveDate) -> Week {
+        Week::containing_on(date, self.data.settings.week_start)
+    }
+
+    /// Get the user preferences
+    pub fn settings(&self) -> &Settings {
+        &self.data.settings
+    }
+
+    /// Replace the user preferences
+    pub fn update_settings(&mut self, settings: Settings) -> Result<()> {
+        self.data.settings = settings;
+        self.mark_dirty()
+    }
+
+    /// Get all active (not archived) habits, sorted by order
+    pub fn habits(&self) -> Vec<&Habit> {
+        let mut habits: Vec<&Habit> = self.data.habits.iter().filter(|h| !h.archived).collect();
+        habits.sort_by_key(|h| h.order);
+        habits
+    }
+
+    /// Get a habit by ID
+    pub fn get_habit(&self, id: Uuid) -> Option<&Habit> {
+        self.data.habits.iter().find(|h| h.id == id)
+    }
+
+    /// Whether another active habit already has this name and label
+    /// `except` leaves one habit out, so renaming a habit to itself is fine. Archived
+    /// habits are out of the list for good, so their names are free to use again.
+    pub fn is_name_taken(&self, name: &str, label: Option<&str>, except: Option<Uuid>) -> bool {
+        self.data.habits.iter()
+            .any(|h| !h.archived && Some(h.id) != except && h.same_identity(name, label))
+    }
+
+    /// Add a new habit
+    pub fn add_habit(&mut self, name: String) -> Result<()> {
+        if self.is_name_taken(&name, None, None) {
+            anyhow::bail!("A habit named '{}' already exists", name);
+        }
+        let order = self.data.habits.len();
+        let mut habit = Habit::new(&name);
+        habit.order = order;
+        habit.created_at = self.today();
+        self.data.habits.push(habit);
+        self.mark_dirty()
+    }
+
+    /// Add a Daily habit for each non-blank line of `text`, returning how many were added
+    /// Names already in use (including ones earlier in the text) are skipped.
+    pub fn add_habits_from_text(&mut self, text: &str) -> Result<usize> {
+        let mut added = 0;
+        for name in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if !self.is_name_taken(name, None, None) {
+                self.add_habit(name.to_string())?;
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Duplicate a habit's metadata (not its logs) as a new habit at the end of the list
+    /// The copy is named "{name} copy", or "{name} copy 2" and so on if that's taken,
+    /// and starts today rather than when the original did.
+    pub fn duplicate_habit(&mut self, id: Uuid) -> Result<Uuid> {
+        let source = self.get_habit(id).context("Habit not found")?;
+        let mut habit = source.clone();
+        habit.id = Uuid::new_v4();
+        habit.created_at = self.today();
+        habit.name = (1..)
+            .map(|n| match n {
+                1 => format!("{} copy", source.name),
+                n => format!("{} copy {}", source.name, n),
+            })
+            .find(|name| !self.is_name_taken(name, source.label.as_deref(), None))
+            .expect("some copy number is free");
+        habit.order = self.data.habits.iter().map(|h| h.order + 1).max().unwrap_or(0);
+        let new_id = habit.id;
+        self.data.habits.push(habit);
+        self.mark_dirty()?;
+        Ok(new_id)
+    }
+
+    /// Update an existing habit
+    pub fn update_habit(&mut self, habit: Habit) -> Result<()> {
+        if let Some(existing) = self.data.habits.iter_mut().find(|h| h.id == habit.id) {
+            *existing = habit;
+            self.mark_dirty()
+        } else {
+            anyhow::bail!("Habit not found")
+        }
+    }
+
+    /// Delete a habit and all its logs
+    pub fn delete_habit(&mut self, id: Uuid) -> Result<()> {
+        let keys: Vec<LogKey> = self.habit_log_keys(id).collect();
+        keys.into_iter().for_each(|key| self.journal_log(key));
+        self.data.habits.retain(|h| h.id != id);
+        self.data.logs.retain(|&(habit_id, _), _| habit_id != id);
+        self.mark_dirty()
+    }
+
+    /// Fold one habit into another: its logs move to `keep_id` and it is deleted
+    /// On days both have a log the stronger status wins (Done, Partial, Skipped, N/A, then
+    /// Unmarked) and the notes of both are kept, oldest first.
+    pub fn merge_habits(&mut self, keep_id: Uuid, remove_id: Uuid) -> Result<()> {
+        if keep_id == remove_id {
+            anyhow::bail!("Can't merge a habit into itself");
+        }
+        self.get_habit(keep_id).context("Habit not found")?;
+        self.get_habit(remove_id).context("Habit not found")?;
+
+        let moved: Vec<LogKey> = self.habit_log_keys(remove_id).collect();
+        for key in moved {
+            self.journal_log(key);
+            self.journal_log((keep_id, key.1));
+            let Some(mut log) = self.data.logs.remove(&key) else { continue };
+            log.habit_id = keep_id;
+            match self.data.logs.entry((keep_id, log.date)) {
+                Entry::Vacant(entry) => {
+                    entry.insert(log);
+                }
+                Entry::Occupied(mut entry) => {
+                    let kept = entry.get_mut();
+                    if merge_rank(log.status) > merge_rank(kept.status) {
+                        kept.status = log.status;
+                    }
+                    kept.notes.extend(log.notes);
+                    kept.notes.sort_by_key(|&(at, _)| at);
+                }
+            }
+        }
+
+        self.data.habits.retain(|h| h.id != remove_id);
+        self.data.habits.sort_by_key(|h| (h.archived, h.order));
+        for (idx, habit) in self.data.habits.iter_mut().enumerate() {
+            habit.order = idx;
+        }
+        self.mark_dirty()
+    }
+
+    /// Update a habit's name
+    pub fn update_habit_name(&mut self, id: Uuid, name: String) -> Result<()> {
+        let label = self.get_habit(id).and_then(|h| h.label.clone());
+        if self.is_name_taken(&name, label.as_deref(), Some(id)) {
+            anyhow::bail!("A habit named '{}' already exists", name);
+        }
+        if let Some(habit) = self.data.habits.iter_mut().find(|h| h.id == id) {
+            habit.name = name;
+            self.mark_dirty()
+        } else {
+            anyhow::bail!("Habit not found")
+        }
+    }
+
+    /// Set or clear (with None) a habit's label
+    pub fn update_habit_label(&mut self, id: Uuid, label: Option<String>) -> Result<()> {
+        let habit = self.get_habit(id).context("Habit not found")?;
+        if self.is_name_taken(&habit.name, label.as_deref(), Some(id)) {
+            anyhow::bail!("A habit named '{}' with that label already exists", habit.name);
+        }
+        if let Some(habit) = self.data.habits.iter_mut().find(|h| h.id == id) {
+            habit.set_label(label);
+        }
+        self.mark_dirty()
+    }
+
+    /// Set or clear (with None) a habit's description
+    pub fn update_habit_description(&mut self, id: Uuid, description: Option<String>) -> Result<()> {
+        if let Some(habit) = self.data.habits.iter_mut().find(|h| h.id == id) {
+            habit.set_description(description);
+            self.mark_dirty()
+        } else {
+            anyhow::bail!("Habit not found")
+        }
+    }
+
+    /// Set or clear (with None) the text a habit's new notes start from
+    pub fn update_habit_note_template(&mut self, id: Uuid, template: Option<String>) -> Result<()> {
+        if let Some(habit) = self.data.habits.iter_mut().find(|h| h.id == id) {
+            habit.note_template = template;
+            self.mark_dirty()
+        } else {
+            anyhow::bail!("Habit not found")
+        }
+    }
+
+    /// Set or clear (with None) a habit's reminder time
+    pub fn update_habit_reminder(&mut self, id: Uuid, remind_at: Option<NaiveTime>) -> Result<()> {
+        if let Some(habit) = self.data.habits.iter_mut().find(|h| h.id == id) {
+            habit.remind_at = remind_at;
+            self.mark_dirty()
+        } else {
+            anyhow::bail!("Habit not found")
+        }
+    }
+
+    /// Set or clear (with None) a habit's weekly goal
+    pub fn update_habit_weekly_goal(&mut self, id: Uuid, weekly_goal: Option<u32>) -> Result<()> {
+        if let Some(habit) = self.data.habits.iter_mut().find(|h| h.id == id) {
+            habit.weekly_goal = weekly_goal;
+            self.mark_dirty()
+        } else {
+            anyhow::bail!("Habit not found")
+        }
+    }
+
+    /// Replace a habit's tags
+    pub fn update_habit_tags(&mut self, id: Uuid, tags: Vec<String>) -> Result<()> {
+        if let Some(habit) = self.data.habits.iter_mut().find(|h| h.id == id) {
+            habit.tags = tags;
+            self.mark_dirty()
+        } else {
+            anyhow::bail!("Habit not found")
+        }
+    }
+
+    /// Number of active habits carrying a tag
+    pub fn count_tagged(&self, tag: &str) -> usize {
+        self.habits().iter().filter(|h| h.has_tag(tag)).count()
+    }
+
+    /// Archive every active habit carrying a tag, keeping their logs
+    /// Returns how many habits were archived.
+    pub fn archive_by_tag(&mut self, tag: &str) -> Result<usize> {
+        let mut archived = 0;
+        for habit in self.data.habits.iter_mut().filter(|h| !h.archived && h.has_tag(tag)) {
+            habit.archived = true;
+            archived += 1;
+        }
+        if archived > 0 {
+            // Keep active habits first so list positions still match their order
+            self.data.habits.sort_by_key(|h| (h.archived, h.order));
+            for (idx, habit) in self.data.habits.iter_mut().enumerate() {
+                habit.order = idx;
+            }
+            self.mark_dirty()?;
+        }
+        Ok(archived)
+    }
+
+    /// Update a habit's frequency
+    pub fn update_habit_frequency(&mut self, id: Uuid, frequency: Frequency) -> Result<()> {
+        if let Some(habit) = self.data.habits.iter_mut().find(|h| h.id == id) {
+            habit.frequency = frequency;
+            self.mark_dirty()
+        } else {
+            anyhow::bail!("Habit not found")
+        }
+    }
+
+    /// Update whether a Weekly habit auto-fills earlier days as Skipped
+    pub fn update_habit_auto_fill_week(&mut self, id: Uuid, auto_fill_week: bool) -> Result<()> {
+        if let Some(habit) = self.data.habits.iter_mut().find(|h| h.id == id) {
+            habit.auto_fill_week = auto_fill_week;
+            self.mark_dirty()
+        } else {
+            anyhow::bail!("Habit not found")
+        }
+    }
+
+    /// Remove a habit (alias for delete_habit)
+    pub fn remove_habit(&mut self, id: Uuid) -> Result<()> {
+        self.delete_habit(id)
+    }
+
+    /// Reorder a habit to a new position
+    pub fn reorder_habit(&mut self, id: Uuid, new_order: usize) -> Result<()> {
+        // Find the habit's current position
+        if let Some(current_idx) = self.data.habits.iter().position(|h| h.id == id) {
+            let habit = self.data.habits.remove(current_idx);
+            let insert_idx = new_order.min(self.data.habits.len());
+            self.data.habits.insert(insert_idx, habit);
+
+            // Update all habit orders
+            for (idx, habit) in self.data.habits.iter_mut().enumerate() {
+                habit.order = idx;
+            }
+
+            self.mark_dirty()
+        } else {
+            anyhow::bail!("Habit not found")
+        }
+    }
+
+    /// Get a log entry for a specific habit and date
+    pub fn get_log(&self, habit_id: Uuid, date: NaiveDate) -> Option<&HabitLog> {
+        self.data.logs.get(&(habit_id, date))
+    }
+
+    /// Get all logs for a specific date
+    pub fn get_logs_for_date(&self, date: NaiveDate) -> Vec<&HabitLog> {
+        self.data.logs.values()
+            .filter(|l| l.date == date)
+            .collect()
+    }
+
+    /// Get all logs for a specific habit, sorted by date
+    pub fn logs_for_habit(&self, habit_id: Uuid) -> Vec<&HabitLog> {
+        self.data.logs
+            .range((habit_id, NaiveDate::MIN)..=(habit_id, NaiveDate::MAX))
+            .map(|(_, log)| log)
+            .collect()
+    }
+
+    /// A habit's status on each day from `start` to `end` inclusive, Unmarked where nothing was logged
+    pub fn statuses_in_range(&self, habit_id: Uuid, start: NaiveDate, end: NaiveDate) -> Vec<(NaiveDate, HabitStatus)> {
+        start.iter_days()
+            .take_while(|&date| date <= end)
+            .map(|date| (date, self.get_log(habit_id, date).map_or(HabitStatus::Unmarked, |log| log.status)))
+            .collect()
+    }
+
+    /// Every note containing `query` (ignoring case), newest day first
+    pub fn search_notes(&self, query: &str) -> Vec<(Uuid, NaiveDate, String)> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut matches: Vec<(Uuid, NaiveDate, String)> = self.data.logs.values()
+            .flat_map(|log| log.notes.iter().map(move |(_, text)| (log.habit_id, log.date, text)))
+            .filter(|(_, _, text)| text.to_lowercase().contains(&query))
+            .map(|(habit_id, date, text)| (habit_id, date, text.clone()))
+            .collect();
+        matches.sort_by_key(|&(_, date, _)| std::cmp::Reverse(date));
+        matches
+    }
+
+    /// Get the Done count for each week a habit has logs in, keyed by week start
+    fn weekly_done_counts(&self, habit_id: Uuid) -> BTreeMap<NaiveDate, usize> {
+        let mut weeks: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        for log in self.logs_for_habit(habit_id) {
+            let count = weeks.entry(self.week_of(log.date).start).or_insert(0);
+            if log.status == HabitStatus::Done {
+                *count += 1;
+            }
+        }
+        weeks
+    }
+
+    /// Completion rate (0.0 to 1.0) for each of the `weeks` weeks ending with the one
+    /// containing `as_of`, oldest first
+    /// Uses the same Done / trackable days as the exports; a week with nothing trackable is 0.0.
+    pub fn weekly_completion_series(&self, habit_id: Uuid, weeks: usize, as_of: NaiveDate) -> Vec<(Week, f64)> {
+        let Some(habit) = self.get_habit(habit_id) else {
+            return Vec::new();
+        };
+        let mut week = self.week_of(as_of);
+        for _ in 1..weeks {
+            week = week.prev();
+        }
+        (0..weeks).map(|_| {
+            let stats = self.habit_stats(habit, week.start, week.end());
+            let trackable = stats.trackable();
+            let rate = if trackable == 0 { 0.0 } else { stats.done as f64 / trackable as f64 };
+            let entry = (week, rate);
+            week = week.next();
+            entry
+        }).collect()
+    }
+
+    /// Get the week with the most Done days for a habit (ties go to the earliest week)
+    pub fn best_week(&self, habit_id: Uuid) -> Option<(Week, usize)> {
+        let mut best: Option<(Week, usize)> = None;
+        for (start, done) in self.weekly_done_counts(habit_id) {
+            if done > 0 && best.is_none_or(|(_, best_done)| done > best_done) {
+                best = Some((Week { start }, done));
+            }
+        }
+        best
+    }
+
+    /// How a status affects a streak: `Some(true)` extends it, `Some(false)` breaks it,
+    /// `None` leaves it alone (N/A and Partial days, vacation days not Done, and Skipped days
+    /// when `skips_keep_streak` is set)
+    fn streak_effect(&self, status: HabitStatus, date: NaiveDate) -> Option<bool> {
+        match status {
+            HabitStatus::Done => Some(true),
+            HabitStatus::Skipped | HabitStatus::Unmarked if self.is_vacation(date) => None,
+            HabitStatus::NotApplicable | HabitStatus::Partial => None,
+            HabitStatus::Skipped if self.data.settings.skips_keep_streak => None,
+            HabitStatus::Skipped | HabitStatus::Unmarked => Some(false),
+        }
+    }
+
+    /// Get the start date and length of a habit's longest run of consecutive Done days
+    /// (ties go to the earliest run; days with no log break the run unless on vacation)
+    pub fn best_streak(&self, habit_id: Uuid) -> Option<(NaiveDate, usize)> {
+        let mut best: Option<(NaiveDate, usize)> = None;
+        // Start, length and last covered day of the run in progress
+        let mut current: Option<(NaiveDate, usize, NaiveDate)> = None;
+
+        for log in self.logs_for_habit(habit_id) {
+            let contiguous = current.filter(|&(_, _, last)| {
+                (last + Duration::days(1)).iter_days()
+                    .take_while(|&date| date < log.date)
+                    .all(|date| self.is_vacation(date))
+            });
+            current = match self.streak_effect(log.status, log.date) {
+                Some(false) => None,
+                None => contiguous.map(|(start, len, _)| (start, len, log.date)),
+                Some(true) => match contiguous {
+                    Some((start, len, _)) => Some((start, len + 1, log.date)),
+                    None => Some((log.date, 1, log.date)),
+                },
+            };
+            if let Some((start, len, _)) = current {
+                if best.is_none_or(|(_, best_len)| len > best_len) {
+                    best = Some((start, len));
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Get the length of a habit's longest run of consecutive Done days (0 if none)
+    pub fn longest_streak(&self, habit_id: Uuid) -> usize {
+        self.best_streak(habit_id).map_or(0, |(_, len)| len)
+    }
+
+    /// Get habits ranked by completion rate (Done / tracked days, as a percentage), lowest first
+    /// Habits with nothing tracked in the range are excluded; ties keep display order
+    pub fn ranked_habits_by_completion(&self, start_date: NaiveDate, end_date: NaiveDate) -> Vec<(Uuid, u8)> {
+        let stats = self.get_stats(start_date, end_date);
+        let mut ranked: Vec<(Uuid, u8)> = self.habits().into_iter()
+            .filter_map(|habit| {
+                let stats = stats.get(&habit.id)?;
+                if stats.unmarked == stats.trackable() {
+                    return None;
+                }
+                Some((habit.id, round_rate(stats.done, stats.trackable()) as u8))
+            })
+            .collect();
+        ranked.sort_by_key(|&(_, rate)| rate);
+        ranked
+    }
+
+    /// Get the number of consecutive Done days ending on `as_of`; later marks are ignored
+    /// An unmarked `as_of` doesn't break the streak, since the day isn't over yet
+    pub fn current_streak(&self, habit_id: Uuid, as_of: NaiveDate) -> usize {
+        let status_on = |date: NaiveDate| {
+            self.get_log(habit_id, date).map(|l| l.status).unwrap_or(HabitStatus::Unmarked)
+        };
+
+        let mut day = as_of;
+        if status_on(day) == HabitStatus::Unmarked {
+            day -= Duration::days(1);
+        }
+        let mut streak = 0;
+        loop {
+            match self.streak_effect(status_on(day), day) {
+                Some(true) => streak += 1,
+                None => {}
+                Some(false) => break,
+            }
+            day -= Duration::days(1);
+        }
+        streak
+    }
+
+    /// Get the 1-based rank of a week among all of a habit's weeks by Done count
+    /// Returns None if the week has no Done days
+    pub fn week_rank(&self, habit_id: Uuid, week: Week) -> Option<usize> {
+        let weeks = self.weekly_done_counts(habit_id);
+        let done = weeks.get(&week.start).copied().filter(|&d| d > 0)?;
+        Some(1 + weeks.values().filter(|&&d| d > done).count())
+    }
+
+    /// Get the earliest date with any recorded activity (a status or a note)
+    pub fn earliest_log_date(&self) -> Option<NaiveDate> {
+        self.active_logs().map(|l| l.date).min()
+    }
+
+    /// Get the most recent date with any recorded activity (a status or a note)
+    pub fn most_recent_log_date(&self) -> Option<NaiveDate> {
+        self.active_logs().map(|l| l.date).max()
+    }
+
+    /// Logs that record something (leftover Unmarked entries without notes don't count)
+    fn active_logs(&self) -> impl Iterator<Item = &HabitLog> {
+        self.data.logs.values().filter(|l| {
+            l.status != HabitStatus::Unmarked
+                || l.latest_note().is_some()
+        })
+    }
+
+    /// Keys of every log a habit has, earliest first
+    fn habit_log_keys(&self, habit_id: Uuid) -> impl Iterator<Item = LogKey> + '_ {
+        self.data.logs.range((habit_id, NaiveDate::MIN)..=(habit_id, NaiveDate::MAX)).map(|(&key, _)| key)
+    }
+
+    /// Get or create a log entry for a habit and date
+    pub fn get_or_create_log(&mut self, habit_id: Uuid, date: NaiveDate) -> &mut HabitLog {
+        self.journal_log((habit_id, date));
+        self.data.logs
+            .entry((habit_id, date))
+            .or_insert_with(|| HabitLog::new(habit_id, date))
+    }
+
+    /// Update a log entry status
+    pub fn update_log_status(&mut self, habit_id: Uuid, date: NaiveDate, status: HabitStatus) -> Result<()> {
+        validate_log_date(date, self.today())?;
+        let log = self.get_or_create_log(habit_id, date);
+        log.status = status;
+        self.mark_dirty()
+    }
+
+    /// Toggle a log entry status
+    pub fn toggle_log_status(&mut self, habit_id: Uuid, date: NaiveDate) -> Result<HabitStatus> {
+        validate_log_date(date, self.today())?;
+        let log = self.get_or_create_log(habit_id, date);
+        log.toggle_status();
+        let new_status = log.status;
+        self.mark_dirty()?;
+        Ok(new_status)
+    }
+
+    /// Add a note written at `at` to a log entry's note history
+    pub fn add_log_note(
+        &mut self,
+        habit_id: Uuid,
+        date: NaiveDate,
+        at: DateTime<Local>,
+        note: impl Into<String>,
+    ) -> Result<()> {
+        validate_log_date(date, self.today())?;
+        let log = self.get_or_create_log(habit_id, date);
+        log.add_note(at, note);
+        self.mark_dirty()
+    }
+
+    /// Remove all logs dated within the given week, returning how many were removed
+    pub fn clear_week(&mut self, week: &Week) -> Result<usize> {
+        let habit_ids: Vec<Uuid> = self.data.habits.iter().map(|h| h.id).collect();
+        for habit_id in habit_ids {
+            for date in week.days() {
+                if self.data.logs.contains_key(&(habit_id, date)) {
+                    self.journal_log((habit_id, date));
+                }
+            }
+        }
+        let before = self.data.logs.len();
+        self.data.logs.retain(|&(_, date), _| date < week.start || date > week.end());
+        let removed = before - self.data.logs.len();
+        self.mark_dirty()?;
+        Ok(removed)
+    }
+
+    /// The week and selected day on screen when the app last quit, if it was recorded
+    /// The week comes back in the current week-start setting, so it still lines up if that changed.
+    pub fn last_view(&self) -> Option<(Week, usize)> {
+        let start = self.data.last_week_start?;
+        let day = self.data.last_selected_day.unwrap_or(0).min(6);
+        Some((self.week_of(start), day))
+    }
+
+    /// Remember the week and selected day on screen, for the next launch
+    pub fn set_last_view(&mut self, week: Week, day_idx: usize) -> Result<()> {
+        if self.data.last_week_start == Some(week.start) && self.data.last_selected_day == Some(day_idx) {
+            return Ok(());
+        }
+        self.data.last_week_start = Some(week.start);
+        self.data.last_selected_day = Some(day_idx);
+        self.mark_dirty()
+    }
+
+    /// Get the reflection note for a week
+    pub fn get_week_note(&self, week: &Week) -> Option<&str> {
+        self.data.week_notes.iter()
+            .find(|(start, _)| *start == week.start)
+            .map(|(_, note)| note.as_str())
+    }
+
+    /// Set or clear (with None) the reflection note for a week
+    pub fn set_week_note(&mut self, week: &Week, note: Option<String>) -> Result<()> {
+        self.data.week_notes.retain(|(start, _)| *start != week.start);
+        if let Some(note) = note {
+            self.data.week_notes.push((week.start, note));
+        }
+        self.mark_dirty()
+    }
+
+    /// Every vacation, earliest first
+    pub fn vacations(&self) -> &[(NaiveDate, NaiveDate)] {
+        &self.data.vacations
+    }
+
+    /// The vacation covering a day, if any
+    pub fn vacation_on(&self, date: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        self.data.vacations.iter().copied().find(|&(start, end)| start <= date && date <= end)
+    }
+
+    /// Whether a day falls inside a vacation
+    pub fn is_vacation(&self, date: NaiveDate) -> bool {
+        self.vacation_on(date).is_some()
+    }
+
+    /// Add a vacation, merging it with any it overlaps or touches
+    pub fn add_vacation(&mut self, start: NaiveDate, end: NaiveDate) -> Result<()> {
+        let (mut start, mut end) = (start.min(end), start.max(end));
+        self.data.vacations.retain(|&(other_start, other_end)| {
+            let joins = other_start <= end + Duration::days(1) && start <= other_end + Duration::days(1);
+            if joins {
+                start = start.min(other_start);
+                end = end.max(other_end);
+            }
+            !joins
+        });
+        self.data.vacations.push((start, end));
+        self.data.vacations.sort();
+        self.mark_dirty()
+    }
+
+    /// Remove the vacation covering a day, returning it if there was one
+    pub fn remove_vacation(&mut self, date: NaiveDate) -> Result<Option<(NaiveDate, NaiveDate)>> {
+        let Some(vacation) = self.vacation_on(date) else {
+            return Ok(None);
+        };
+        self.data.vacations.retain(|&v| v != vacation);
+        self.mark_dirty()?;
+        Ok(Some(vacation))
+    }
+
+    /// Make the latest vacation starting on or before `date` end on it (longer or shorter)
+    /// Returns the vacation as it now stands, or None if no vacation starts by then.
+    pub fn end_vacation(&mut self, date: NaiveDate) -> Result<Option<(NaiveDate, NaiveDate)>> {
+        let Some(&(start, end)) = self.data.vacations.iter().rev().find(|&&(start, _)| start <= date) else {
+            return Ok(None);
+        };
+        self.data.vacations.retain(|&v| v != (start, end));
+        self.add_vacation(start, date)?;
+        Ok(self.vacation_on(date))
+    }
+
+    /// Get completion statistics for a date range
+    pub fn get_stats(&self, start_date: NaiveDate, end_date: NaiveDate) -> HashMap<Uuid, HabitStats> {
+        self.data.habits.iter()
+            .map(|habit| (habit.id, self.habit_stats(habit, start_date, end_date)))
+            .collect()
+    }
+
+    /// Count each status for one habit over a date range
+    /// Unmarked only counts scheduled days on or after the habit was created;
+    /// unscheduled days only count if something was logged on them. N/A days never count,
+    /// and vacation days only count when Done or Partial.
+    fn habit_stats(&self, habit: &Habit, start_date: NaiveDate, end_date: NaiveDate) -> HabitStats {
+        let mut stats = HabitStats::default();
+
+        let mut current = start_date;
+        while current <= end_date {
+            let status = self.get_log(habit.id, current).map(|log| log.status);
+            let vacation = self.is_vacation(current);
+            match status {
+                Some(HabitStatus::Skipped) | None if vacation => {}
+                Some(HabitStatus::Done) => stats.done += 1,
+                Some(HabitStatus::Partial) => stats.partial += 1,
+                Some(HabitStatus::Skipped) => stats.skipped += 1,
+                Some(HabitStatus::NotApplicable) => {}
+                _ if current >= habit.created_at && habit.is_scheduled_on(current) => stats.unmarked += 1,
+                _ => {}
+            }
+            current = current.succ_opt().unwrap();
+        }
+
+        stats
+    }
+
+    /// Number of days in a range that count toward a habit's completion rate
+    /// This is the denominator every completion rate should use.
+    pub fn trackable_days(&self, habit_id: Uuid, start_date: NaiveDate, end_date: NaiveDate) -> usize {
+        self.get_habit(habit_id)
+            .map(|habit| self.habit_stats(habit, start_date, end_date).trackable())
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_round_rate() {
+        assert_eq!(round_rate(5, 7), 71);
+        assert_eq!(round_rate(1, 3), 33);
+        assert_eq!(round_rate(2, 3), 67);
+        assert_eq!(round_rate(1, 8), 13); // 12.5 rounds up
+        assert_eq!(round_rate(7, 7), 100);
+        assert_eq!(round_rate(0, 0), 0);
+    }
+
+    #[test]
+    fn test_legacy_data_migrates_only_into_empty_path() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let data_path = temp_dir.path().join("new").join("habits.json");
+        let missing = temp_dir.path().join("missing.json");
+        let legacy = temp_dir.path().join("legacy.json");
+        let candidates = vec![missing.clone(), legacy.clone()];
+
+        // No legacy file yet
+        assert_eq!(legacy_data_to_migrate(&data_path, &candidates), None);
+
+        fs::write(&legacy, r#"{"habits":[],"logs":[]}"#).unwrap();
+        assert_eq!(legacy_data_to_migrate(&data_path, &candidates), Some(legacy.clone()));
+
+        // An empty file at the new path still counts as no data
+        fs::create_dir_all(data_path.parent().unwrap()).unwrap();
+        fs::write(&data_path, "").unwrap();
+        assert_eq!(migrate_legacy_data(&data_path, &candidates).unwrap(), Some(legacy.clone()));
+        assert_eq!(fs::read_to_string(&data_path).unwrap(), r#"{"habits":[],"logs":[]}"#);
+        assert!(legacy.exists());
+
+        // Once the new path has data it's left alone
+        assert_eq!(legacy_data_to_migrate(&data_path, &candidates), None);
+        assert_eq!(migrate_legacy_data(&data_path, &candidates).unwrap(), None);
+    }
+
+    #[test]
+    fn test_add_habits_from_text() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let before = storage.habits().len();
+
+        let text = "Stretch\r\n\r\n  Journal  \nShower\n\t\nStretch\r\nWalk outside";
+        assert_eq!(storage.add_habits_from_text(text).unwrap(), 3);
+        let names: Vec<&str> = storage.habits()[before..].iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, ["Stretch", "Journal", "Walk outside"]);
+        assert!(storage.habits()[before..].iter().all(|h| h.frequency == Frequency::Daily));
+
+        // Running it again adds nothing
+        assert_eq!(storage.add_habits_from_text(text).unwrap(), 0);
+        assert_eq!(storage.habits().len(), before + 3);
+    }
+
+    #[test]
+    fn test_weekly_completion_series() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let shower = storage.habits()[0].id;
+        storage.data.habits[0].created_at = NaiveDate::from_ymd_opt(2025, 9, 29).unwrap();
+
+        // Three weeks from Sep 29: 7, then 0, then 3 of 7 done; the last also has a skip
+        let first = Week::containing(NaiveDate::from_ymd_opt(2025, 9, 29).unwrap());
+        for &day in &first.days() {
+            storage.update_log_status(shower, day, HabitStatus::Done).unwrap();
+        }
+        let third = first.next().next();
+        for &day in &third.days()[..3] {
+            storage.update_log_status(shower, day, HabitStatus::Done).unwrap();
+        }
+        storage.update_log_status(shower, third.days()[3], HabitStatus::Skipped).unwrap();
+
+        let series = storage.weekly_completion_series(shower, 4, third.days()[4]);
+        let weeks: Vec<Week> = series.iter().map(|&(week, _)| week).collect();
+        assert_eq!(weeks, vec![first.prev(), first, first.next(), third]);
+        let rates: Vec<f64> = series.iter().map(|&(_, rate)| rate).collect();
+        // The week before the habit existed has nothing trackable
+        assert_eq!(rates[..3], [0.0, 1.0, 0.0]);
+        assert!((rates[3] - 3.0 / 7.0).abs() < 1e-9);
+
+        assert!(storage.weekly_completion_series(Uuid::new_v4(), 4, third.start).is_empty());
+    }
+
+    #[test]
+    fn test_vacation_days_leave_rate_alone() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let week = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
+        let days = week.days();
+        let ids: Vec<Uuid> = storage.habits().iter().map(|h| h.id).collect();
+        for habit in storage.data.habits.iter_mut() {
+            habit.created_at = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        }
+        for &day in &days[..4] {
+            storage.update_log_status(ids[0], day, HabitStatus::Done).unwrap();
+        }
+        storage.update_log_status(ids[0], days[4], HabitStatus::Skipped).unwrap();
+        assert_eq!(storage.trackable_days(ids[0], week.start, week.end()), 7);
+
+        // Away Friday to Sunday: the skip and the unmarked days drop out
+        storage.add_vacation(days[4], days[6]).unwrap();
+        let stats = &storage.get_stats(week.start, week.end())[&ids[0]];
+        assert_eq!((stats.done, stats.skipped, stats.unmarked), (4, 0, 0));
+        assert_eq!(storage.trackable_days(ids[0], week.start, week.end()), 4);
+        assert_eq!(round_rate(stats.done, stats.trackable()), 100);
+
+        // Something done on vacation still counts
+        storage.update_log_status(ids[1], days[5], HabitStatus::Done).unwrap();
+        assert_eq!(storage.get_stats(week.start, week.end())[&ids[1]].done, 1);
+        assert_eq!(storage.trackable_days(ids[1], week.start, week.end()), 5);
+    }
+
+    #[test]
+    fn test_vacations_merge_and_end() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 10, d).unwrap();
+
+        storage.add_vacation(day(10), day(12)).unwrap();
+        storage.add_vacation(day(13), day(13)).unwrap();
+        storage.add_vacation(day(20), day(20)).unwrap();
+        assert_eq!(storage.vacations(), &[(day(10), day(13)), (day(20), day(20))]);
+        assert!(storage.is_vacation(day(11)));
+        assert!(!storage.is_vacation(day(14)));
+
+        // Ending picks the latest vacation started by then, and can shorten it
+        assert_eq!(storage.end_vacation(day(22)).unwrap(), Some((day(20), day(22))));
+        assert_eq!(storage.end_vacation(day(11)).unwrap(), Some((day(10), day(11))));
+        assert_eq!(storage.end_vacation(day(1)).unwrap(), None);
+
+        assert_eq!(storage.remove_vacation(day(21)).unwrap(), Some((day(20), day(22))));
+        assert_eq!(storage.vacations(), &[(day(10), day(11))]);
+
+        // Vacations survive a reload
+        storage.flush().unwrap();
+        let mut reloaded = Storage::new(temp_file.path());
+        reloaded.load().unwrap();
+        assert_eq!(reloaded.vacations(), &[(day(10), day(11))]);
+    }
+
+    #[test]
+    fn test_not_applicable_days_leave_rate_alone() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let week = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
+        let ids: Vec<Uuid> = storage.habits().iter().map(|h| h.id).collect();
+        for habit in storage.data.habits.iter_mut() {
+            habit.created_at = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        }
+        let rate = |storage: &Storage, id: Uuid| {
+            let done = storage.get_stats(week.start, week.end())[&id].done;
+            round_rate(done, storage.trackable_days(id, week.start, week.end()))
+        };
+
+        // Shower: done 5 of 7, then the other two marked N/A
+        for &day in &week.days()[..5] {
+            storage.update_log_status(ids[0], day, HabitStatus::Done).unwrap();
+        }
+        assert_eq!(rate(&storage, ids[0]), 71);
+        for &day in &week.days()[5..] {
+            storage.update_log_status(ids[0], day, HabitStatus::NotApplicable).unwrap();
+        }
+        assert_eq!(storage.trackable_days(ids[0], week.start, week.end()), 5);
+        assert_eq!(rate(&storage, ids[0]), 100);
+
+        // Meds: the same two days skipped still count against it
+        for &day in &week.days()[..5] {
+            storage.update_log_status(ids[3], day, HabitStatus::Done).unwrap();
+        }
+        for &day in &week.days()[5..] {
+            storage.update_log_status(ids[3], day, HabitStatus::Skipped).unwrap();
+        }
+        assert_eq!(storage.trackable_days(ids[3], week.start, week.end()), 7);
+        assert_eq!(rate(&storage, ids[3]), 71);
+    }
+
+    #[test]
+    fn test_preview_import_classifies_changes() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 10, 14).unwrap();
+        let ids: Vec<Uuid> = storage.habits().iter().map(|h| h.id).collect();
+        storage.update_log_status(ids[0], date, HabitStatus::Done).unwrap();
+        storage.update_log_status(ids[3], date, HabitStatus::Done).unwrap();
+
+        let mut imported = storage.data.clone();
+        imported.habits[1].name = "Floss".to_string();
+        let stretch = Habit::new("Stretch");
+        imported.habits.push(stretch.clone());
+        // Conflicting log, identical log, and a brand new one
+        imported.logs.get_mut(&(ids[0], date)).unwrap().status = HabitStatus::Skipped;
+        imported.logs.insert((stretch.id, date), HabitLog::with_status(stretch.id, date, HabitStatus::Done));
+
+        let diff = storage.preview_import(&imported);
+        assert_eq!(diff.habits_added, vec!["Stretch".to_string()]);
+        assert_eq!(diff.habits_updated, vec![HabitChange {
+            habit_id: ids[1],
+            name: "Brush teeth".to_string(),
+            fields: vec![("name", "Brush teeth".to_string(), "Floss".to_string())],
+        }]);
+        assert_eq!(diff.logs_added, 1);
+        assert_eq!(diff.logs_overwritten, vec![("Shower".to_string(), date)]);
+
+        // Previewing changes nothing; applying merges
+        assert_eq!(storage.habits()[1].name, "Brush teeth");
+        storage.apply_import(imported.clone()).unwrap();
+        assert_eq!(storage.habits()[1].name, "Floss");
+        assert_eq!(storage.habits().len(), 5);
+        assert_eq!(storage.get_log(ids[0], date).unwrap().status, HabitStatus::Skipped);
+        assert!(storage.preview_import(&imported).is_empty());
+    }
+
+    #[test]
+    fn test_import_from_another_install_matches_by_name() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let shower_id = storage.habits()[0].id;
+        let date = NaiveDate::from_ymd_opt(2025, 10, 14).unwrap();
+
+        // Same habit under another ID, plus a log for a habit the backup doesn't have
+        let other = Habit::new(" shower");
+        let orphan = Uuid::new_v4();
+        let mut imported = HabitData { habits: vec![other.clone()], ..HabitData::default() };
+        imported.logs.insert((other.id, date), HabitLog::with_status(other.id, date, HabitStatus::Done));
+        imported.logs.insert((orphan, date), HabitLog::with_status(orphan, date, HabitStatus::Done));
+
+        let diff = storage.preview_import(&imported);
+        assert!(diff.habits_added.is_empty());
+        assert_eq!(diff.logs_added, 1);
+
+        storage.apply_import(imported).unwrap();
+        assert_eq!(storage.habits().len(), 4);
+        assert_eq!(storage.habits()[0].id, shower_id);
+        assert_eq!(storage.get_log(shower_id, date).unwrap().status, HabitStatus::Done);
+        assert!(storage.get_log(orphan, date).is_none());
+        assert!(storage.data.logs.keys().all(|&(id, _)| storage.get_habit(id).is_some()));
+    }
+
+    #[test]
+    fn test_apply_import_rejects_out_of_range_logs() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let mut imported = storage.data.clone();
+        let id = imported.habits[0].id;
+        let date = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+        imported.logs.insert((id, date), HabitLog::new(id, date));
+        imported.habits[0].name = "Bath".to_string();
+
+        assert!(storage.apply_import(imported).is_err());
+        assert_eq!(storage.habits()[0].name, "Shower");
+    }
+
+    #[test]
+    fn test_stale_lock_is_reclaimed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let data_path = temp_dir.path().join("habits.json");
+        let path = lock_path(&data_path);
+        assert_eq!(path, temp_dir.path().join("habits.json.lock"));
+        assert_eq!(check_lock(&path, |_| true), LockState::Free);
+
+        fs::write(&path, "424242").unwrap();
+        assert_eq!(check_lock(&path, |pid| pid == 424242), LockState::Held(424242));
+        // Lock present but its process is gone: proceed and take it over
+        assert_eq!(check_lock(&path, |_| false), LockState::Stale);
+        let lock = DataLock::claim(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), std::process::id().to_string());
+        // Our own lock never counts as held by someone else
+        assert_eq!(check_lock(&path, |_| true), LockState::Stale);
+
+        drop(lock);
+        assert!(!path.exists());
+        fs::write(&path, "garbage").unwrap();
+        assert_eq!(check_lock(&path, |_| true), LockState::Stale);
+    }
+
+    #[test]
+    fn test_read_only_never_writes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("habits.json");
+        let mut storage = Storage::new(&path);
+        storage.load().unwrap();
+        storage.set_read_only(true);
+        storage.add_habit("Stretch".to_string()).unwrap();
+        storage.flush().unwrap();
+        assert!(storage.is_dirty());
+
+        let mut reloaded = Storage::new(&path);
+        reloaded.load().unwrap();
+        assert_eq!(reloaded.habits().len(), 4);
+    }
+
+    #[test]
+    fn test_change_undo_and_redo() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let shower = storage.data.habits[0].id;
+        let date = NaiveDate::from_ymd_opt(2025, 10, 15).unwrap();
+        storage.update_log_status(shower, date, HabitStatus::Skipped).unwrap();
+
+        let before = storage.snapshot();
+        storage.begin_change();
+        storage.update_log_status(shower, date, HabitStatus::Done).unwrap();
+        storage.update_log_status(shower, date + Duration::days(1), HabitStatus::Done).unwrap();
+        storage.add_habit("Stretch".to_string()).unwrap();
+        let change = storage.finish_change();
+        let after = storage.snapshot();
+        // Only what the action touched is kept: two logs and one habit
+        assert_eq!((change.habits.len(), change.logs.len()), (1, 2));
+
+        storage.undo_change(&change).unwrap();
+        assert_eq!(storage.snapshot(), before);
+        storage.redo_change(&change).unwrap();
+        assert_eq!(storage.snapshot(), after);
+        storage.begin_change();
+        assert!(storage.finish_change().is_empty());
+    }
+
+    #[test]
+    fn test_export_and_import_all() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let shower = storage.data.habits[0].id;
+        let date = NaiveDate::from_ymd_opt(2025, 10, 15).unwrap();
+        storage.update_log_status(shower, date, HabitStatus::Done).unwrap();
+        storage.update_log_status(shower, date - Duration::days(1), HabitStatus::Done).unwrap();
+
+        let backup = NamedTempFile::new().unwrap();
+        storage.export_all(backup.path()).unwrap();
+
+        // Diverge from the backup
+        storage.add_habit("Stretch".to_string()).unwrap();
+        storage.update_log_status(shower, date, HabitStatus::Skipped).unwrap();
+        storage.update_log_status(shower, date + Duration::days(1), HabitStatus::Done).unwrap();
+
+        // Merge keeps local-only habits and logs, and the backup wins on conflicts
+        storage.import_all(backup.path(), true).unwrap();
+        assert_eq!(storage.data.habits.len(), 5);
+        assert_eq!(storage.data.logs.len(), 3);
+        assert_eq!(storage.get_log(shower, date).unwrap().status, HabitStatus::Done);
+
+        // Replace drops everything the backup doesn't have
+        storage.import_all(backup.path(), false).unwrap();
+        assert_eq!(storage.data.habits.len(), 4);
+        assert_eq!(storage.data.logs.len(), 2);
+        assert!(storage.get_log(shower, date + Duration::days(1)).is_none());
+
+        // Reloading from disk sees the restored data
+        storage.flush().unwrap();
+        let mut reloaded = Storage::new(temp_file.path());
+        reloaded.load().unwrap();
+        assert_eq!(reloaded.data.logs.len(), 2);
+    }
+
+    #[test]
+    fn test_import_malformed_backup_changes_nothing() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        storage.add_habit("Stretch".to_string()).unwrap();
+        let on_disk = fs::read_to_string(temp_file.path()).unwrap();
+
+        let bad = NamedTempFile::new().unwrap();
+        for contents in ["{ not json", "{}", r#"{"habits": "nope", "logs": []}"#] {
+            fs::write(bad.path(), contents).unwrap();
+            for merge in [true, false] {
+                let err = storage.import_all(bad.path(), merge).unwrap_err();
+                assert!(format!("{:#}", err).contains("is not a valid habit data file"));
+            }
+        }
+        assert_eq!(storage.data.habits.len(), 5);
+        assert_eq!(fs::read_to_string(temp_file.path()).unwrap(), on_disk);
+    }
+
+    #[test]
+    fn test_update_habit_description() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let shower = storage.data.habits[0].id;
+
+        storage.update_habit_description(shower, Some("Warm, not hot".to_string())).unwrap();
+        storage.flush().unwrap();
+        let mut reloaded = Storage::new(temp_file.path());
+        reloaded.load().unwrap();
+        assert_eq!(reloaded.get_habit(shower).unwrap().description.as_deref(), Some("Warm, not hot"));
+
+        storage.update_habit_description(shower, None).unwrap();
+        assert_eq!(storage.get_habit(shower).unwrap().description, None);
+        assert!(storage.update_habit_description(Uuid::new_v4(), None).is_err());
+    }
+
+    #[test]
+    fn test_get_log_with_thousands_of_logs() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let ids: Vec<Uuid> = storage.data.habits.iter().map(|h| h.id).collect();
+        let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+
+        // Three years of logs for every habit, written straight into the map to keep this quick
+        let status_for = |habit_idx: usize, day: i64| match (habit_idx as i64 + day) % 3 {
+            0 => HabitStatus::Done,
+            1 => HabitStatus::Skipped,
+            _ => HabitStatus::NotApplicable,
+        };
+        for (habit_idx, &id) in ids.iter().enumerate() {
+            for day in 0..1095 {
+                let date = start + Duration::days(day);
+                let log = storage.get_or_create_log(id, date);
+                log.status = status_for(habit_idx, day);
+            }
+        }
+        assert_eq!(storage.data.logs.len(), ids.len() * 1095);
+
+        for (habit_idx, &id) in ids.iter().enumerate() {
+            for day in [0, 1, 500, 1094] {
+                let log = storage.get_log(id, start + Duration::days(day)).unwrap();
+                assert_eq!((log.habit_id, log.date), (id, start + Duration::days(day)));
+                assert_eq!(log.status, status_for(habit_idx, day));
+            }
+        }
+
+        // Lookups stay right after updates and deleting a habit
+        storage.update_log_status(ids[0], start + Duration::days(500), HabitStatus::Unmarked).unwrap();
+        storage.delete_habit(ids[1]).unwrap();
+        assert_eq!(storage.get_log(ids[0], start + Duration::days(500)).unwrap().status, HabitStatus::Unmarked);
+        assert!(storage.get_log(ids[1], start).is_none());
+        assert_eq!(storage.get_log(ids[2], start + Duration::days(1094)).unwrap().status, status_for(2, 1094));
+        assert!(storage.get_log(ids[2], start + Duration::days(1095)).is_none());
+        assert_eq!(storage.logs_for_habit(ids[3]).len(), 1095);
+
+        // And after a round trip through the file
+        storage.flush().unwrap();
+        let mut reloaded = Storage::new(temp_file.path());
+        reloaded.load().unwrap();
+        assert_eq!(reloaded.data.logs.len(), 3 * 1095);
+        assert_eq!(reloaded.get_log(ids[3], start + Duration::days(7)).unwrap().status, status_for(3, 7));
+    }
+
+    #[test]
+    fn test_storage_new() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = Storage::new(temp_file.path());
+        assert_eq!(storage.data.habits.len(), 4); // Default habits
+    }
+
+    #[test]
+    fn test_template_only_applies_to_fresh_store() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("habits.json");
+
+        let mut storage = Storage::with_template(&path, Template::Minimal);
+        storage.load().unwrap();
+        let names: Vec<&str> = storage.habits().iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, vec!["Meds", "Drink water"]);
+
+        // An existing file keeps its habits whatever template is asked for
+        let mut reopened = Storage::with_template(&path, Template::MentalHealth);
+        reopened.load().unwrap();
+        assert_eq!(reopened.habits().len(), 2);
+    }
+
+    #[test]
+    fn test_mutations_write_once_on_flush() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let habit_id = storage.data.habits[0].id;
+        let date = NaiveDate::from_ymd_opt(2025, 10, 14).unwrap();
+        let on_disk = fs::read_to_string(temp_file.path()).unwrap();
+
+        storage.update_log_status(habit_id, date, HabitStatus::Done).unwrap();
+        storage.add_log_note(habit_id, date, Local::now(), "Felt good").unwrap();
+        storage.add_habit("Stretch".to_string()).unwrap();
+        assert_eq!(storage.write_count(), 0);
+        assert_eq!(fs::read_to_string(temp_file.path()).unwrap(), on_disk);
+
+        storage.flush().unwrap();
+        storage.flush().unwrap();
+        assert_eq!(storage.write_count(), 1);
+        let mut reloaded = Storage::new(temp_file.path());
+        reloaded.load().unwrap();
+        assert_eq!(reloaded.habits().len(), 5);
+        assert_eq!(reloaded.get_log(habit_id, date).unwrap().latest_note(), Some("Felt good"));
+    }
+
+    #[test]
+    fn test_defaults_file_seeds_new_data() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let data_path = temp_dir.path().join("habits.json");
+        fs::write(
+            temp_dir.path().join("defaults.json"),
+            r#"[{"name": "Stretch"}, {"name": "Long run", "frequency": "Weekly"}]"#,
+        ).unwrap();
+
+        let mut storage = Storage::new(&data_path);
+        storage.load().unwrap();
+        let names: Vec<&str> = storage.habits().iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, ["Stretch", "Long run"]);
+        assert_eq!(storage.habits()[1].frequency, Frequency::Weekly);
+        assert_eq!(storage.take_load_warning(), None);
+
+        // A template picked by name still gets its own habits
+        let mut minimal = Storage::with_template(temp_dir.path().join("other.json"), Template::Minimal);
+        minimal.load().unwrap();
+        assert_eq!(minimal.habits()[1].name, "Drink water");
+    }
+
+    #[test]
+    fn test_unusable_defaults_file_falls_back() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("defaults.json"), "[]").unwrap();
+
+        let mut storage = Storage::new(temp_dir.path().join("habits.json"));
+        storage.load().unwrap();
+        assert_eq!(storage.habits()[0].name, "Shower");
+        assert!(storage.take_load_warning().unwrap().contains("no habits listed"));
+    }
+
+    #[test]
+    fn test_corrupt_file_is_moved_aside() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let data_path = temp_dir.path().join("habits.json");
+        fs::write(&data_path, "{\"habits\": [").unwrap();
+
+        let mut storage = Storage::new(&data_path);
+        storage.load().unwrap();
+        assert_eq!(storage.habits().len(), 4);
+
+        // Until the lock is held the bad file stays put, even through a flush
+        storage.add_habit("Stretch".to_string()).unwrap();
+        storage.flush().unwrap();
+        assert_eq!(fs::read_to_string(&data_path).unwrap(), "{\"habits\": [");
+        assert!(storage.unreadable().is_some());
+
+        storage.set_aside_unreadable().unwrap();
+        assert!(storage.take_load_warning().unwrap().contains("moved to"));
+        assert_eq!(storage.take_load_warning(), None);
+
+        // The bad file is kept next to the fresh one, untouched
+        let aside: Vec<_> = fs::read_dir(temp_dir.path()).unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.starts_with("habits.corrupt-") && name.ends_with(".json"))
+            .collect();
+        assert_eq!(aside.len(), 1);
+        assert_eq!(fs::read_to_string(temp_dir.path().join(&aside[0])).unwrap(), "{\"habits\": [");
+        assert!(fs::read_to_string(&data_path).unwrap().contains("Shower"));
+    }
+
+    #[test]
+    fn test_corrupt_path_name() {
+        let at = NaiveDate::from_ymd_opt(2025, 10, 15).unwrap().and_hms_opt(9, 30, 0).unwrap();
+        assert_eq!(
+            corrupt_path(Path::new("/data/habits.json"), at),
+            PathBuf::from("/data/habits.corrupt-20251015-093000.json")
+        );
+    }
+
+    #[test]
+    fn test_read_data_file_rejects_newer_schema() {
+        let backup = NamedTempFile::new().unwrap();
+        fs::write(backup.path(), r#"{"schema_version":99,"habits":[],"logs":[]}"#).unwrap();
+        let err = read_data_file(backup.path(), NaiveDate::from_ymd_opt(2025, 10, 15).unwrap()).unwrap_err();
+        assert!(err.to_string().contains("newer version"));
+    }
+
+    #[test]
+    fn test_read_data_file_rejects_out_of_range_logs() {
+        let mut data = HabitData::default();
+        let habit_id = data.habits[0].id;
+        let date = NaiveDate::from_ymd_opt(9999, 1, 1).unwrap();
+        data.logs.insert((habit_id, date), HabitLog::new(habit_id, date));
+        let backup = NamedTempFile::new().unwrap();
+        fs::write(backup.path(), serde_json::to_string(&data).unwrap()).unwrap();
+
+        let err = read_data_file(backup.path(), NaiveDate::from_ymd_opt(2025, 10, 15).unwrap()).unwrap_err();
+        assert!(format!("{:#}", err).contains("out of range"));
+    }
+
+    #[test]
+    fn test_read_data_file_backfills_created_at() {
+        let mut data = HabitData::default();
+        let habit_id = data.habits[0].id;
+        data.habits[0].set_created_at(crate::models::habit::unknown_created_at());
+        let date = NaiveDate::from_ymd_opt(2025, 3, 4).unwrap();
+        data.logs.insert((habit_id, date), HabitLog::new(habit_id, date));
+        let backup = NamedTempFile::new().unwrap();
+        fs::write(backup.path(), serde_json::to_string(&data).unwrap()).unwrap();
+
+        let read = read_data_file(backup.path(), NaiveDate::from_ymd_opt(2025, 10, 15).unwrap()).unwrap();
+        assert_eq!(read.habits[0].created_at, date);
+    }
+
+    #[test]
+    fn test_save_replaces_the_file_whole() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let data_path = temp_dir.path().join("habits.json");
+        let mut storage = Storage::new(&data_path);
+        storage.load().unwrap();
+        storage.add_habit("Stretch".to_string()).unwrap();
+        storage.flush().unwrap();
+
+        // Only the data file is left; the temporary one was renamed over it
+        let names: Vec<_> = fs::read_dir(temp_dir.path()).unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["habits.json"]);
+        assert!(read_data_file(&data_path, storage.today()).unwrap().habits.iter().any(|h| h.name == "Stretch"));
+    }
+
+    #[test]
+    fn test_schema_version_defaults_to_one() {
+        let data: HabitData = serde_json::from_str(r#"{"habits":[],"logs":[]}"#).unwrap();
+        assert_eq!(data.schema_version, 1);
+        assert_eq!(HabitData::default().schema_version, DATA_SCHEMA);
+    }
+
+    #[test]
+    fn test_newer_schema_opens_read_only() {
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(temp_file.path(), r#"{"schema_version":99,"habits":[],"logs":[]}"#).unwrap();
+
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        assert!(storage.is_read_only());
+        assert!(storage.take_load_warning().unwrap().contains("newer version"));
+        storage.add_habit("Stretch".to_string()).unwrap();
+        storage.flush().unwrap();
+        assert!(fs::read_to_string(temp_file.path()).unwrap().contains("\"schema_version\":99"));
+    }
+
+    #[test]
+    fn test_dirty_until_flushed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let data_dir = temp_dir.path().join("data");
+        let mut storage = Storage::new(data_dir.join("habits.json"));
+        storage.load().unwrap();
+        assert!(!storage.is_dirty());
+
+        // With the directory gone the write fails, leaving the change pending
+        std::fs::remove_dir_all(&data_dir).unwrap();
+        storage.add_habit("Stretch".to_string()).unwrap();
+        assert!(storage.is_dirty());
+        assert_eq!(storage.habits().len(), 5);
+        storage.flush_or_report();
+        assert!(storage.is_dirty());
+        assert!(storage.take_save_error().unwrap().contains("Failed to write habit data file"));
+        assert_eq!(storage.take_save_error(), None);
+        assert!(storage.flush().is_err());
+
+        std::fs::create_dir_all(&data_dir).unwrap();
+        storage.flush().unwrap();
+        assert!(!storage.is_dirty());
+
+        let mut reloaded = Storage::new(data_dir.join("habits.json"));
+        reloaded.load().unwrap();
+        assert_eq!(reloaded.habits().len(), 5);
+    }
+
+    #[test]
+    fn test_duplicate_check_includes_label() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let meds = storage.data.habits[3].id;
+        assert!(storage.add_habit("meds".to_string()).is_err());
+
+        storage.update_habit_label(meds, Some("AM".to_string())).unwrap();
+        storage.add_habit("Meds".to_string()).unwrap();
+        let pm = storage.data.habits.last().unwrap().id;
+        assert!(storage.update_habit_label(pm, Some("am".to_string())).is_err());
+        storage.update_habit_label(pm, Some("PM".to_string())).unwrap();
+
+        assert!(storage.is_name_taken("Meds", Some("AM"), None));
+        assert!(storage.is_name_taken("Meds", Some("PM"), None));
+        assert!(!storage.is_name_taken("Meds", None, None));
+        // Renaming a habit to its own name is not a clash
+        storage.update_habit_name(pm, "Meds".to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_archive_by_tag_keeps_logs() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let ids: Vec<Uuid> = storage.habits().iter().map(|h| h.id).collect();
+        let date = NaiveDate::from_ymd_opt(2025, 10, 14).unwrap();
+        storage.update_habit_tags(ids[0], vec!["Hygiene".to_string()]).unwrap();
+        storage.update_habit_tags(ids[1], vec!["hygiene".to_string(), "morning".to_string()]).unwrap();
+        storage.update_habit_tags(ids[3], vec!["health".to_string()]).unwrap();
+        storage.update_log_status(ids[0], date, HabitStatus::Done).unwrap();
+        storage.update_log_status(ids[3], date, HabitStatus::Done).unwrap();
+
+        assert_eq!(storage.count_tagged("HYGIENE"), 2);
+        assert_eq!(storage.archive_by_tag("hygiene").unwrap(), 2);
+        let remaining: Vec<Uuid> = storage.habits().iter().map(|h| h.id).collect();
+        assert_eq!(remaining, vec![ids[2], ids[3]]);
+
+        // History of archived habits is untouched
+        assert_eq!(storage.get_log(ids[0], date).unwrap().status, HabitStatus::Done);
+        assert!(storage.get_habit(ids[0]).unwrap().archived);
+        assert_eq!(storage.archive_by_tag("hygiene").unwrap(), 0);
+
+        // An archived habit's name can be used for a new one
+        assert!(!storage.is_name_taken("Shower", None, None));
+        storage.add_habit("Shower".to_string()).unwrap();
+        assert_eq!(storage.habits().last().unwrap().name, "Shower");
+    }
+
+    #[test]
+    fn test_storage_save_and_load() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        {
+            let mut storage = Storage::new(&path);
+            storage.load().unwrap();
+            assert_eq!(storage.data.habits.len(), 4);
         }
 
         // Load again to verify persistence
@@ -297,17 +2421,25 @@ mod tests {
         let status = storage.toggle_log_status(habit_id, date).unwrap();
         assert_eq!(status, HabitStatus::Done);
 
-        // Second toggle: Done -> Skipped
+        // Second toggle: Done -> Partial
+        let status = storage.toggle_log_status(habit_id, date).unwrap();
+        assert_eq!(status, HabitStatus::Partial);
+
+        // Third toggle: Partial -> Skipped
         let status = storage.toggle_log_status(habit_id, date).unwrap();
         assert_eq!(status, HabitStatus::Skipped);
 
-        // Third toggle: Skipped -> Unmarked
+        // Fourth toggle: Skipped -> N/A
+        let status = storage.toggle_log_status(habit_id, date).unwrap();
+        assert_eq!(status, HabitStatus::NotApplicable);
+
+        // Fifth toggle: N/A -> Unmarked
         let status = storage.toggle_log_status(habit_id, date).unwrap();
         assert_eq!(status, HabitStatus::Unmarked);
     }
 
     #[test]
-    fn test_update_log_note() {
+    fn test_add_log_note() {
         let temp_file = NamedTempFile::new().unwrap();
         let mut storage = Storage::new(temp_file.path());
         storage.load().unwrap();
@@ -315,10 +2447,10 @@ mod tests {
         let habit_id = storage.data.habits[0].id;
         let date = NaiveDate::from_ymd_opt(2025, 10, 14).unwrap();
 
-        storage.update_log_note(habit_id, date, Some("Test note".to_string())).unwrap();
+        storage.add_log_note(habit_id, date, Local::now(), "Test note").unwrap();
 
         let log = storage.get_log(habit_id, date).unwrap();
-        assert_eq!(log.note, Some("Test note".to_string()));
+        assert_eq!(log.latest_note(), Some("Test note"));
     }
 
     #[test]
@@ -330,16 +2462,535 @@ mod tests {
         let habit_id = storage.data.habits[0].id;
         let start = NaiveDate::from_ymd_opt(2025, 10, 14).unwrap();
         let end = NaiveDate::from_ymd_opt(2025, 10, 20).unwrap(); // 7 days
+        storage.data.habits[0].created_at = start;
 
         // Mark some days
         storage.update_log_status(habit_id, start, HabitStatus::Done).unwrap();
         storage.update_log_status(habit_id, start.succ_opt().unwrap(), HabitStatus::Done).unwrap();
         storage.update_log_status(habit_id, start.succ_opt().unwrap().succ_opt().unwrap(), HabitStatus::Skipped).unwrap();
+        storage.update_log_status(habit_id, end, HabitStatus::Partial).unwrap();
 
         let stats = storage.get_stats(start, end);
-        let (done, skipped, unmarked) = stats.get(&habit_id).unwrap();
-        assert_eq!(*done, 2);
-        assert_eq!(*skipped, 1);
-        assert_eq!(*unmarked, 4);
+        let stats = stats.get(&habit_id).unwrap();
+        assert_eq!(stats.done, 2);
+        assert_eq!(stats.partial, 1);
+        assert_eq!(stats.skipped, 1);
+        assert_eq!(stats.unmarked, 3);
+        assert_eq!(stats.trackable(), 7);
+    }
+
+    #[test]
+    fn test_best_week_clear_peak() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let habit_id = storage.data.habits[0].id;
+        let week1 = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 6).unwrap());
+        let week2 = week1.next();
+
+        // One Done in the first week, three in the second
+        storage.update_log_status(habit_id, week1.days()[0], HabitStatus::Done).unwrap();
+        for &day in &week2.days()[1..4] {
+            storage.update_log_status(habit_id, day, HabitStatus::Done).unwrap();
+        }
+
+        assert_eq!(storage.best_week(habit_id), Some((week2, 3)));
+        assert_eq!(storage.best_streak(habit_id), Some((week2.days()[1], 3)));
+        assert_eq!(storage.week_rank(habit_id, week2), Some(1));
+        assert_eq!(storage.week_rank(habit_id, week1), Some(2));
+        assert_eq!(storage.week_rank(habit_id, week2.next()), None);
+    }
+
+    #[test]
+    fn test_best_week_ties_pick_earliest() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let habit_id = storage.data.habits[0].id;
+        let week1 = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 6).unwrap());
+        let week2 = week1.next();
+
+        // Two-day runs in both weeks, with the later week inserted first
+        for week in [week2, week1] {
+            storage.update_log_status(habit_id, week.days()[2], HabitStatus::Done).unwrap();
+            storage.update_log_status(habit_id, week.days()[3], HabitStatus::Done).unwrap();
+        }
+
+        assert_eq!(storage.best_week(habit_id), Some((week1, 2)));
+        assert_eq!(storage.best_streak(habit_id), Some((week1.days()[2], 2)));
+        assert_eq!(storage.week_rank(habit_id, week1), Some(1));
+        assert_eq!(storage.week_rank(habit_id, week2), Some(1));
+    }
+
+    #[test]
+    fn test_clear_week_only_affects_target_week() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let habit_id = storage.data.habits[0].id;
+        let other_id = storage.data.habits[1].id;
+        let week = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
+        let prev = week.prev();
+
+        storage.update_log_status(habit_id, week.start, HabitStatus::Done).unwrap();
+        storage.update_log_status(other_id, week.end(), HabitStatus::Skipped).unwrap();
+        storage.update_log_status(habit_id, prev.end(), HabitStatus::Done).unwrap();
+        storage.add_log_note(other_id, prev.start, Local::now(), "Keep me").unwrap();
+
+        let removed = storage.clear_week(&week).unwrap();
+        assert_eq!(removed, 2);
+        assert!(storage.get_log(habit_id, week.start).is_none());
+        assert!(storage.get_log(other_id, week.end()).is_none());
+        assert!(storage.get_log(habit_id, prev.end()).is_some());
+        assert!(storage.get_log(other_id, prev.start).is_some());
+    }
+
+    #[test]
+    fn test_merge_habits_keeps_stronger_status() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        storage.add_habit("Pills".to_string()).unwrap();
+        let meds = storage.data.habits[3].id;
+        let pills = storage.data.habits[4].id;
+        let day = |d| NaiveDate::from_ymd_opt(2025, 10, d).unwrap();
+        let at = |h| Local::now() - Duration::hours(h);
+
+        // Overlapping days: Done beats Skipped either way round, Partial beats Unmarked
+        storage.update_log_status(meds, day(13), HabitStatus::Skipped).unwrap();
+        storage.update_log_status(pills, day(13), HabitStatus::Done).unwrap();
+        storage.update_log_status(meds, day(14), HabitStatus::Done).unwrap();
+        storage.update_log_status(pills, day(14), HabitStatus::Skipped).unwrap();
+        storage.update_log_status(pills, day(15), HabitStatus::Partial).unwrap();
+        storage.add_log_note(meds, day(15), at(1), "Evening").unwrap();
+        storage.add_log_note(pills, day(15), at(12), "Morning").unwrap();
+        // Only the removed habit has this day
+        storage.update_log_status(pills, day(16), HabitStatus::NotApplicable).unwrap();
+
+        storage.merge_habits(meds, pills).unwrap();
+        let status = |d| storage.get_log(meds, day(d)).unwrap().status;
+        assert_eq!(status(13), HabitStatus::Done);
+        assert_eq!(status(14), HabitStatus::Done);
+        assert_eq!(status(15), HabitStatus::Partial);
+        assert_eq!(status(16), HabitStatus::NotApplicable);
+        let notes: Vec<&str> = storage.get_log(meds, day(15)).unwrap().notes.iter().map(|(_, n)| n.as_str()).collect();
+        assert_eq!(notes, ["Morning", "Evening"]);
+
+        // The removed habit and every log under its id are gone, and orders are contiguous
+        assert!(storage.get_habit(pills).is_none());
+        assert!(storage.logs_for_habit(pills).is_empty());
+        assert!(storage.data.logs.keys().all(|&(habit_id, _)| habit_id != pills));
+        let orders: Vec<usize> = storage.data.habits.iter().map(|h| h.order).collect();
+        assert_eq!(orders, [0, 1, 2, 3]);
+
+        assert!(storage.merge_habits(meds, meds).is_err());
+        assert!(storage.merge_habits(meds, pills).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_habit() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        // Trim nails has a description and a Weekly frequency
+        let source = storage.data.habits[2].clone();
+        let date = NaiveDate::from_ymd_opt(2025, 10, 14).unwrap();
+        storage.update_log_status(source.id, date, HabitStatus::Done).unwrap();
+
+        storage.set_clock(|| NaiveDate::from_ymd_opt(2025, 10, 15).unwrap().and_hms_opt(12, 0, 0).unwrap());
+        let today = storage.today();
+        storage.data.habits[2].set_created_at(NaiveDate::from_ymd_opt(2025, 1, 6).unwrap());
+        let new_id = storage.duplicate_habit(source.id).unwrap();
+        let copy = storage.get_habit(new_id).unwrap();
+
+        assert_ne!(copy.id, source.id);
+        assert_eq!(copy.created_at, today);
+        assert_eq!(copy.name, "Trim nails copy");
+        assert_eq!(copy.description, source.description);
+        assert_eq!(copy.frequency, source.frequency);
+        assert_eq!(copy.order, 4);
+        assert!(storage.logs_for_habit(new_id).is_empty());
+        assert_eq!(storage.logs_for_habit(source.id).len(), 1);
+
+        // Copying again doesn't reuse a taken name
+        let again = storage.duplicate_habit(source.id).unwrap();
+        assert_eq!(storage.get_habit(again).unwrap().name, "Trim nails copy 2");
+        let copy_of_copy = storage.duplicate_habit(new_id).unwrap();
+        assert_eq!(storage.get_habit(copy_of_copy).unwrap().name, "Trim nails copy copy");
+    }
+
+    #[test]
+    fn test_ranked_habits_by_completion() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let ids: Vec<Uuid> = storage.habits().iter().map(|h| h.id).collect();
+        let week = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
+        let days = week.days();
+
+        // Shower: 2 done, 2 skipped (50%)
+        for (i, status) in [HabitStatus::Done, HabitStatus::Done, HabitStatus::Skipped, HabitStatus::Skipped].iter().enumerate() {
+            storage.update_log_status(ids[0], days[i], *status).unwrap();
+        }
+        // Brush teeth: 3 done (100%)
+        for &day in &days[..3] {
+            storage.update_log_status(ids[1], day, HabitStatus::Done).unwrap();
+        }
+        // Trim nails: nothing tracked (excluded)
+        // Meds: 1 done, 3 skipped (25%)
+        storage.update_log_status(ids[3], days[0], HabitStatus::Done).unwrap();
+        for &day in &days[1..4] {
+            storage.update_log_status(ids[3], day, HabitStatus::Skipped).unwrap();
+        }
+
+        let ranked = storage.ranked_habits_by_completion(week.start, week.end());
+        assert_eq!(ranked, vec![(ids[3], 25), (ids[0], 50), (ids[1], 100)]);
+    }
+
+    #[test]
+    fn test_vacation_days_do_not_break_streaks() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let id = storage.habits()[0].id;
+        let day = |d| NaiveDate::from_ymd_opt(2025, 10, d).unwrap();
+        for d in [1, 2, 6, 7] {
+            storage.update_log_status(id, day(d), HabitStatus::Done).unwrap();
+        }
+        storage.update_log_status(id, day(4), HabitStatus::Skipped).unwrap();
+        assert_eq!(storage.current_streak(id, day(7)), 2);
+        assert_eq!(storage.best_streak(id), Some((day(1), 2)));
+
+        // Away on the 3rd to the 5th: the unmarked and skipped days there are set aside
+        storage.add_vacation(day(3), day(5)).unwrap();
+        assert_eq!(storage.current_streak(id, day(7)), 4);
+        assert_eq!(storage.best_streak(id), Some((day(1), 4)));
+    }
+
+    #[test]
+    fn test_backfill_created_at_uses_earliest_log() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let unknown = crate::models::habit::unknown_created_at();
+        let today = NaiveDate::from_ymd_opt(2025, 10, 20).unwrap();
+        let with_logs = storage.data.habits[0].id;
+        let without_logs = storage.data.habits[1].id;
+        storage.data.habits[0].created_at = unknown;
+        storage.data.habits[1].created_at = unknown;
+
+        storage.update_log_status(with_logs, NaiveDate::from_ymd_opt(2025, 10, 14).unwrap(), HabitStatus::Done).unwrap();
+        storage.update_log_status(with_logs, NaiveDate::from_ymd_opt(2025, 9, 2).unwrap(), HabitStatus::Skipped).unwrap();
+
+        assert!(storage.data.backfill_created_at(today));
+        assert_eq!(storage.get_habit(with_logs).unwrap().created_at, NaiveDate::from_ymd_opt(2025, 9, 2).unwrap());
+        assert_eq!(storage.get_habit(without_logs).unwrap().created_at, today);
+
+        // Nothing left to backfill
+        assert!(!storage.data.backfill_created_at(today));
+    }
+
+    #[test]
+    fn test_statuses_in_range_fills_every_day() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let habit_id = storage.data.habits[0].id;
+        let start = NaiveDate::from_ymd_opt(2025, 10, 13).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 10, 15).unwrap();
+        storage.update_log_status(habit_id, start + Duration::days(1), HabitStatus::Done).unwrap();
+        storage.update_log_status(habit_id, end + Duration::days(1), HabitStatus::Done).unwrap();
+
+        assert_eq!(storage.statuses_in_range(habit_id, start, end), vec![
+            (start, HabitStatus::Unmarked),
+            (start + Duration::days(1), HabitStatus::Done),
+            (end, HabitStatus::Unmarked),
+        ]);
+        assert!(storage.statuses_in_range(habit_id, end, start).is_empty());
+    }
+
+    #[test]
+    fn test_search_notes_ignores_case() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let shower = storage.data.habits[0].id;
+        let meds = storage.data.habits[3].id;
+        let early = NaiveDate::from_ymd_opt(2025, 10, 13).unwrap();
+        let late = NaiveDate::from_ymd_opt(2025, 10, 15).unwrap();
+
+        storage.add_log_note(shower, early, Local::now(), "Bad sleep again").unwrap();
+        storage.add_log_note(meds, late, Local::now(), "took them late, BAD SLEEP").unwrap();
+        storage.add_log_note(meds, early, Local::now(), "fine day").unwrap();
+
+        let results = storage.search_notes("bad sleep");
+        assert_eq!(results, vec![
+            (meds, late, "took them late, BAD SLEEP".to_string()),
+            (shower, early, "Bad sleep again".to_string()),
+        ]);
+        assert!(storage.search_notes("nightmare").is_empty());
+        assert!(storage.search_notes("  ").is_empty());
+    }
+
+    #[test]
+    fn test_load_migrates_single_notes() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let habit_id = storage.data.habits[0].id;
+        let date = NaiveDate::from_ymd_opt(2025, 10, 14).unwrap();
+
+        // A file from before note history, with one note on the log
+        let mut old: serde_json::Value = serde_json::to_value(&storage.data).unwrap();
+        old["logs"] = serde_json::json!([
+            {"habit_id": habit_id, "date": "2025-10-14", "status": "Skipped", "note": "Rough day"}
+        ]);
+        fs::write(temp_file.path(), old.to_string()).unwrap();
+
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let log = storage.get_log(habit_id, date).unwrap();
+        assert_eq!(log.status, HabitStatus::Skipped);
+        assert_eq!(log.notes.len(), 1);
+        assert_eq!(log.latest_note(), Some("Rough day"));
+
+        // Rewritten in the new format, which loads the same
+        let saved = fs::read_to_string(temp_file.path()).unwrap();
+        assert!(saved.contains("\"notes\""));
+        assert!(!saved.contains("\"note\""));
+        let mut reloaded = Storage::new(temp_file.path());
+        reloaded.load().unwrap();
+        assert_eq!(reloaded.get_log(habit_id, date), storage.get_log(habit_id, date));
+    }
+
+    #[test]
+    fn test_earliest_and_most_recent_log_date() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        assert_eq!(storage.earliest_log_date(), None);
+        assert_eq!(storage.most_recent_log_date(), None);
+
+        let habit_id = storage.data.habits[0].id;
+        let other_id = storage.data.habits[1].id;
+        let early = NaiveDate::from_ymd_opt(2025, 9, 3).unwrap();
+        let middle = NaiveDate::from_ymd_opt(2025, 10, 1).unwrap();
+        let late = NaiveDate::from_ymd_opt(2025, 10, 14).unwrap();
+
+        storage.update_log_status(habit_id, middle, HabitStatus::Done).unwrap();
+        storage.add_log_note(other_id, early, Local::now(), "First note").unwrap();
+        storage.update_log_status(other_id, late, HabitStatus::Skipped).unwrap();
+        // An entry toggled back to Unmarked isn't activity
+        storage.update_log_status(habit_id, late.succ_opt().unwrap(), HabitStatus::Unmarked).unwrap();
+
+        assert_eq!(storage.earliest_log_date(), Some(early));
+        assert_eq!(storage.most_recent_log_date(), Some(late));
+    }
+
+    #[test]
+    fn test_week_notes() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let week = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 15).unwrap());
+        assert_eq!(storage.get_week_note(&week), None);
+
+        storage.set_week_note(&week, Some("A hard week, but I kept going.".to_string())).unwrap();
+        storage.set_week_note(&week.next(), Some("Better".to_string())).unwrap();
+        assert_eq!(storage.get_week_note(&week), Some("A hard week, but I kept going."));
+        assert_eq!(storage.get_week_note(&week.next()), Some("Better"));
+
+        // Replacing and clearing only touch the given week
+        storage.set_week_note(&week, Some("Rewritten".to_string())).unwrap();
+        assert_eq!(storage.get_week_note(&week), Some("Rewritten"));
+        storage.set_week_note(&week, None).unwrap();
+        assert_eq!(storage.get_week_note(&week), None);
+        assert_eq!(storage.get_week_note(&week.next()), Some("Better"));
+    }
+
+    #[test]
+    fn test_current_streak() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let habit_id = storage.data.habits[0].id;
+        let today = NaiveDate::from_ymd_opt(2025, 10, 15).unwrap();
+        assert_eq!(storage.current_streak(habit_id, today), 0);
+
+        for offset in 1..=3 {
+            storage.update_log_status(habit_id, today - Duration::days(offset), HabitStatus::Done).unwrap();
+        }
+        storage.update_log_status(habit_id, today - Duration::days(4), HabitStatus::Skipped).unwrap();
+        storage.update_log_status(habit_id, today - Duration::days(5), HabitStatus::Done).unwrap();
+
+        // Today not yet marked: the streak through yesterday still counts
+        assert_eq!(storage.current_streak(habit_id, today), 3);
+        storage.update_log_status(habit_id, today, HabitStatus::Done).unwrap();
+        assert_eq!(storage.current_streak(habit_id, today), 4);
+        storage.update_log_status(habit_id, today, HabitStatus::Skipped).unwrap();
+        assert_eq!(storage.current_streak(habit_id, today), 0);
+    }
+
+    /// Seed Shower with: Oct 1-3 Done, Oct 4 Skipped, Oct 5-6 Done, Oct 7 N/A,
+    /// Oct 8 Done, (Oct 9 no log), Oct 10-11 Done, Oct 13 Done
+    fn seed_streak_logs(storage: &mut Storage) -> Uuid {
+        let habit_id = storage.data.habits[0].id;
+        let day = |d| NaiveDate::from_ymd_opt(2025, 10, d).unwrap();
+        let seeded = [
+            (1, HabitStatus::Done), (2, HabitStatus::Done), (3, HabitStatus::Done),
+            (4, HabitStatus::Skipped), (5, HabitStatus::Done), (6, HabitStatus::Done),
+            (7, HabitStatus::NotApplicable), (8, HabitStatus::Done),
+            (10, HabitStatus::Done), (11, HabitStatus::Done), (13, HabitStatus::Done),
+        ];
+        for (d, status) in seeded {
+            storage.update_log_status(habit_id, day(d), status).unwrap();
+        }
+        habit_id
+    }
+
+    #[test]
+    fn test_streaks_over_seeded_logs() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let habit_id = seed_streak_logs(&mut storage);
+        let day = |d| NaiveDate::from_ymd_opt(2025, 10, d).unwrap();
+
+        // N/A on Oct 7 is passed over, so Oct 5-8 is a 3-day run; the Skip breaks at Oct 4
+        assert_eq!(storage.current_streak(habit_id, day(8)), 3);
+        assert_eq!(storage.current_streak(habit_id, day(3)), 3);
+        // The Oct 9 gap with no log breaks the run
+        assert_eq!(storage.current_streak(habit_id, day(11)), 2);
+        // Oct 12 has no log and the day is over by Oct 13
+        assert_eq!(storage.current_streak(habit_id, day(13)), 1);
+        // Done marks after `as_of` aren't counted
+        assert_eq!(storage.current_streak(habit_id, day(10)), 1);
+        assert_eq!(storage.current_streak(habit_id, day(1)), 1);
+
+        assert_eq!(storage.longest_streak(habit_id), 3);
+        assert_eq!(storage.best_streak(habit_id), Some((day(1), 3)));
+    }
+
+    #[test]
+    fn test_skips_keep_streak_setting() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let habit_id = seed_streak_logs(&mut storage);
+        let day = |d| NaiveDate::from_ymd_opt(2025, 10, d).unwrap();
+
+        let mut settings = storage.settings().clone();
+        settings.skips_keep_streak = true;
+        storage.update_settings(settings).unwrap();
+
+        // Oct 1-8 is one run of six Done days; the Skip and N/A don't count toward it
+        assert_eq!(storage.current_streak(habit_id, day(8)), 6);
+        assert_eq!(storage.current_streak(habit_id, day(4)), 3);
+        assert_eq!(storage.best_streak(habit_id), Some((day(1), 6)));
+        // Gaps with no log still break it
+        assert_eq!(storage.current_streak(habit_id, day(11)), 2);
+    }
+
+    #[test]
+    fn test_streak_never_starts_on_neutral_day() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+        let habit_id = storage.data.habits[0].id;
+        let day = |d| NaiveDate::from_ymd_opt(2025, 10, d).unwrap();
+
+        storage.update_log_status(habit_id, day(1), HabitStatus::NotApplicable).unwrap();
+        assert_eq!(storage.longest_streak(habit_id), 0);
+        assert_eq!(storage.current_streak(habit_id, day(1)), 0);
+
+        storage.update_log_status(habit_id, day(2), HabitStatus::Done).unwrap();
+        assert_eq!(storage.best_streak(habit_id), Some((day(2), 1)));
+    }
+
+    #[test]
+    fn test_logs_round_trip_as_list() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let habit_id = storage.data.habits[0].id;
+        let date = NaiveDate::from_ymd_opt(2025, 10, 14).unwrap();
+        storage.update_log_status(habit_id, date, HabitStatus::Done).unwrap();
+        storage.add_log_note(habit_id, date, Local::now(), "Felt good").unwrap();
+        storage.flush().unwrap();
+
+        // On disk, logs are still a plain list of entries
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(temp_file.path()).unwrap()).unwrap();
+        let logs = json["logs"].as_array().unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0]["date"], "2025-10-14");
+        assert_eq!(logs[0]["status"], "Done");
+
+        let mut reloaded = Storage::new(temp_file.path());
+        reloaded.load().unwrap();
+        assert_eq!(reloaded.data.logs, storage.data.logs);
+    }
+
+    #[test]
+    fn test_logs_for_habit_iterates_in_date_order() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let habit_id = storage.data.habits[0].id;
+        let other_id = storage.data.habits[1].id;
+        let dates: Vec<NaiveDate> = [20, 3, 14, 9]
+            .iter()
+            .map(|&d| NaiveDate::from_ymd_opt(2025, 10, d).unwrap())
+            .collect();
+        for &date in &dates {
+            storage.update_log_status(habit_id, date, HabitStatus::Done).unwrap();
+            storage.update_log_status(other_id, date, HabitStatus::Skipped).unwrap();
+        }
+
+        let mut sorted = dates.clone();
+        sorted.sort();
+        let logged: Vec<NaiveDate> = storage.logs_for_habit(habit_id).iter().map(|l| l.date).collect();
+        assert_eq!(logged, sorted);
+        assert!(storage.logs_for_habit(habit_id).iter().all(|l| l.habit_id == habit_id));
+    }
+
+    #[test]
+    fn test_far_future_log_date_is_rejected() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_file.path());
+        storage.load().unwrap();
+
+        let habit_id = storage.data.habits[0].id;
+        let typo = NaiveDate::from_ymd_opt(20255, 10, 13).unwrap();
+        let err = storage.update_log_status(habit_id, typo, HabitStatus::Done).unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+        assert!(storage.add_log_note(habit_id, typo, Local::now(), "oops").is_err());
+        assert!(storage.toggle_log_status(habit_id, typo).is_err());
+        assert!(storage.get_log(habit_id, typo).is_none());
+        assert!(storage.data.logs.is_empty());
+
+        let before_epoch = NaiveDate::from_ymd_opt(1969, 12, 31).unwrap();
+        assert!(storage.update_log_status(habit_id, before_epoch, HabitStatus::Done).is_err());
+        let today = storage.today();
+        assert!(storage.update_log_status(habit_id, today, HabitStatus::Done).is_ok());
+
+        // "A year from today" follows the storage's clock, not the system's
+        storage.set_clock(|| NaiveDate::from_ymd_opt(2025, 10, 15).unwrap().and_hms_opt(12, 0, 0).unwrap());
+        let year_out = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        assert!(storage.update_log_status(habit_id, year_out, HabitStatus::Done).is_ok());
+        let past_it = year_out + Duration::days(1);
+        assert!(storage.update_log_status(habit_id, past_it, HabitStatus::Done).is_err());
+        storage.add_habit("Stretch".to_string()).unwrap();
+        assert_eq!(storage.habits().last().unwrap().created_at, NaiveDate::from_ymd_opt(2025, 10, 15).unwrap());
     }
 }
@@ -0,0 +1,197 @@
+// Color theme for the TUI, loaded from theme.json next to the data file
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::models::Habit;
+
+/// Colors the views are drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Titles and headers
+    pub header: Color,
+    /// Selected rows, input boxes and key hints
+    pub selected: Color,
+    /// Done statuses and confirming keys
+    pub done: Color,
+    /// Partial statuses
+    pub partial: Color,
+    /// Skipped statuses, cancelling keys and warnings
+    pub skipped: Color,
+    /// Unmarked statuses and placeholder text
+    pub unmarked: Color,
+    /// Flash messages and things needing attention
+    pub accent: Color,
+    /// N/A statuses
+    pub not_applicable: Color,
+    /// De-emphasised text such as the legend and empty bar segments
+    pub muted: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            header: Color::Cyan,
+            selected: Color::Yellow,
+            done: Color::Green,
+            partial: Color::LightGreen,
+            skipped: Color::Red,
+            unmarked: Color::Gray,
+            accent: Color::Magenta,
+            not_applicable: Color::Blue,
+            muted: Color::DarkGray,
+        }
+    }
+}
+
+/// Colors habit management cycles a habit through, before going back to none
+pub const HABIT_COLORS: [&str; 6] = ["red", "green", "yellow", "blue", "magenta", "cyan"];
+
+/// Parse a color by name (`"light-blue"`), hex value (`"#ff8800"`) or palette index (`"208"`)
+pub fn parse_color(value: &str) -> Option<Color> {
+    Color::from_str(value.trim()).ok()
+}
+
+/// A habit's own color, if it has one that parses
+pub fn habit_color(habit: &Habit) -> Option<Color> {
+    habit.color.as_deref().and_then(parse_color)
+}
+
+/// Contents of theme.json: a preset, with any colors overridden by name
+/// (`"red"`, `"light-blue"`, `"#ff8800"`, or a 0-255 palette index)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ThemeFile {
+    preset: Option<String>,
+    header: Option<String>,
+    selected: Option<String>,
+    done: Option<String>,
+    partial: Option<String>,
+    skipped: Option<String>,
+    unmarked: Option<String>,
+    accent: Option<String>,
+    not_applicable: Option<String>,
+    muted: Option<String>,
+}
+
+impl Theme {
+    /// Names accepted for `"preset"` in theme.json
+    pub const PRESETS: [&'static str; 2] = ["default", "high-contrast"];
+
+    /// Bright colors on the terminal background, for displays where the defaults are hard to read
+    pub fn high_contrast() -> Self {
+        Self {
+            header: Color::White,
+            selected: Color::LightYellow,
+            done: Color::LightGreen,
+            partial: Color::LightBlue,
+            skipped: Color::LightRed,
+            unmarked: Color::White,
+            accent: Color::LightMagenta,
+            not_applicable: Color::LightCyan,
+            muted: Color::Gray,
+        }
+    }
+
+    /// Look up a built-in theme by name
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Load a theme file, falling back to the default theme if it's missing or invalid
+    /// An unknown preset or unparseable color keeps the default for that part only.
+    pub fn load(path: &Path) -> Self {
+        let Some(file) = fs::read_to_string(path).ok()
+            .and_then(|contents| serde_json::from_str::<ThemeFile>(&contents).ok())
+        else {
+            return Self::default();
+        };
+
+        let mut theme = file.preset.as_deref().and_then(Self::preset).unwrap_or_default();
+        for (slot, value) in [
+            (&mut theme.header, &file.header),
+            (&mut theme.selected, &file.selected),
+            (&mut theme.done, &file.done),
+            (&mut theme.partial, &file.partial),
+            (&mut theme.skipped, &file.skipped),
+            (&mut theme.unmarked, &file.unmarked),
+            (&mut theme.accent, &file.accent),
+            (&mut theme.not_applicable, &file.not_applicable),
+            (&mut theme.muted, &file.muted),
+        ] {
+            if let Some(color) = value.as_deref().and_then(parse_color) {
+                *slot = color;
+            }
+        }
+        theme
+    }
+}
+
+/// Theme file kept alongside a data file, e.g. `theme.json` next to `habits.json`
+pub fn theme_path(data_path: &Path) -> PathBuf {
+    data_path.with_file_name("theme.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_falls_back_to_default() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("theme.json");
+
+        // Missing file
+        assert_eq!(Theme::load(&path), Theme::default());
+
+        // Not JSON
+        fs::write(&path, "not a theme").unwrap();
+        assert_eq!(Theme::load(&path), Theme::default());
+
+        // Unknown preset and bad color are ignored
+        fs::write(&path, r#"{"preset": "neon", "done": "not-a-color"}"#).unwrap();
+        assert_eq!(Theme::load(&path), Theme::default());
+    }
+
+    #[test]
+    fn test_load_preset_and_overrides() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("theme.json");
+
+        fs::write(&path, r#"{"preset": "high-contrast"}"#).unwrap();
+        assert_eq!(Theme::load(&path), Theme::high_contrast());
+
+        fs::write(&path, r##"{"preset": "high-contrast", "done": "#00ff00", "header": "blue"}"##).unwrap();
+        let theme = Theme::load(&path);
+        assert_eq!(theme.done, Color::Rgb(0, 255, 0));
+        assert_eq!(theme.header, Color::Blue);
+        assert_eq!(theme.skipped, Theme::high_contrast().skipped);
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("cyan"), Some(Color::Cyan));
+        assert_eq!(parse_color("light-blue"), Some(Color::LightBlue));
+        assert_eq!(parse_color(" #ff8800 "), Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(parse_color("208"), Some(Color::Indexed(208)));
+        assert_eq!(parse_color("not-a-color"), None);
+        assert_eq!(parse_color("#ff88"), None);
+        assert_eq!(parse_color(""), None);
+        assert!(HABIT_COLORS.iter().all(|name| parse_color(name).is_some()));
+    }
+
+    #[test]
+    fn test_presets_resolve() {
+        for name in Theme::PRESETS {
+            assert!(Theme::preset(name).is_some());
+        }
+        assert_eq!(theme_path(Path::new("/data/habits.json")), Path::new("/data/theme.json"));
+    }
+}
@@ -0,0 +1,59 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+
+use crate::app::App;
+use crate::models::{HabitStatus, Week};
+
+/// Draw the week's activity feed, newest first
+pub fn draw(f: &mut Frame, app: &App) {
+    let mut items = vec![
+        ListItem::new(Line::from(Span::styled(
+            format!("Activity - {}", app.current_week.format()),
+            Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD),
+        ))),
+        ListItem::new(Line::from("")),
+    ];
+
+    let activity = app.week_activity();
+    if activity.is_empty() {
+        items.push(ListItem::new("No activity recorded this week."));
+    } else {
+        for item in activity {
+            let (status_str, style) = match item.status {
+                HabitStatus::Done => ("Done", Style::default().fg(app.theme.done)),
+                HabitStatus::Partial => ("Partial", Style::default().fg(app.theme.partial)),
+                HabitStatus::Skipped => ("Skipped", Style::default().fg(app.theme.skipped)),
+                HabitStatus::NotApplicable => ("N/A", Style::default().fg(app.theme.not_applicable)),
+                HabitStatus::Unmarked => ("Unmarked", Style::default().fg(app.theme.unmarked)),
+            };
+
+            let weekday = &Week::full_weekday_name(item.date)[..3];
+            let mut spans = vec![
+                Span::styled(format!("{} – ", weekday), Style::default().fg(app.theme.selected)),
+                Span::raw(format!("{}: ", item.habit_name)),
+                Span::styled(status_str, style),
+            ];
+            if let Some(note) = item.note {
+                spans.push(Span::styled(format!(" ({})", note), Style::default().fg(app.theme.header)));
+            }
+            items.push(ListItem::new(Line::from(spans)));
+        }
+    }
+
+    items.push(ListItem::new(Line::from("")));
+    items.push(ListItem::new(Line::from(Span::styled(
+        "Press 'q' or Esc to return",
+        Style::default().fg(app.theme.done),
+    ))));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Week Activity")
+        .style(Style::default());
+    let list = List::new(items).block(block);
+    f.render_widget(list, f.area());
+}
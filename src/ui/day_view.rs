@@ -1,28 +1,24 @@
+use std::rc::Rc;
+
 use chrono::Datelike;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 
-use crate::app::App;
+use crate::app::{App, EmptyDay};
+use crate::keymap::Action;
 use crate::models::{HabitStatus, Week};
+use crate::theme::habit_color;
 
 /// Draw the day detail view showing habits for the selected day
 pub fn draw(f: &mut Frame, area: Rect, app: &App) {
     let selected_date = app.selected_date();
     let day_name = Week::full_weekday_name(selected_date);
-
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),  // Date header
-            Constraint::Min(5),     // Habits list
-            Constraint::Length(4),  // Notes section
-        ])
-        .split(area);
+    let chunks = layout(area, app);
 
     // Draw date header
     let title = format!("{}, {} {}", day_name, selected_date.format("%b"), selected_date.day());
@@ -31,7 +27,7 @@ pub fn draw(f: &mut Frame, area: Rect, app: &App) {
         .style(Style::default());
     let header = Paragraph::new(title)
         .block(header_block)
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        .style(Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD));
     f.render_widget(header, chunks[0]);
 
     // Draw habits list
@@ -41,37 +37,70 @@ pub fn draw(f: &mut Frame, area: Rect, app: &App) {
     draw_note_section(f, chunks[2], app);
 }
 
+/// Split the day view into date header, habits list and note section
+fn layout(area: Rect, app: &App) -> Rc<[Rect]> {
+    let has_description = app.selected_habit().is_some_and(|h| h.description.is_some());
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Date header
+            Constraint::Min(5),     // Habits list
+            Constraint::Length(if has_description { 5 } else { 4 }),  // Description and note
+        ])
+        .split(area)
+}
+
+/// Rows of the habits list (inside its border) when the day view is drawn in `area`
+pub fn habit_rows_area(area: Rect, app: &App) -> Rect {
+    layout(area, app)[1].inner(Margin::new(1, 1))
+}
+
 /// Draw the habits list for the selected day
 fn draw_habits_list(f: &mut Frame, area: Rect, app: &App) {
     let selected_date = app.selected_date();
     let habits = app.habits_for_date(selected_date);
 
-    if habits.is_empty() {
+    if let Some(reason) = app.empty_day_reason(selected_date) {
+        let message = match reason {
+            EmptyDay::NoHabits => "No habits configured. Press 'h' to add habits.",
+            EmptyDay::NoneTagged => "No habits have this tag any more. Press '#' to change the filter.",
+            EmptyDay::NoneScheduled => "No habits scheduled today. Press '*' to show all.",
+            EmptyDay::AllMarked => "Everything is marked for this day. Press 'o' to show all.",
+        };
         let block = Block::default()
             .borders(Borders::ALL)
             .title("Habits for this day");
-        let text = Paragraph::new("No habits configured. Press 'h' to add habits.")
+        let text = Paragraph::new(message)
             .block(block)
-            .style(Style::default().fg(Color::Yellow));
+            .style(Style::default().fg(app.theme.selected));
         f.render_widget(text, area);
         return;
     }
 
+    // Weekly habits left undone last week carry over with a marker
+    let overdue = app.overdue_weekly_habits(selected_date);
+    let ascii = app.storage.settings().ascii_glyphs;
+    let overdue_marker = if ascii { "! overdue" } else { "↻ overdue" };
+    let note_marker = if ascii { "[n] " } else { "📝 " };
+
     let items: Vec<ListItem> = habits
         .iter()
         .enumerate()
         .map(|(idx, habit)| {
             let status = app.get_habit_status(habit.id, selected_date);
             let status_str = status.display_str();
+            let streak = app.storage.current_streak(habit.id, selected_date);
 
             // Highlight the selected habit
             let is_selected = idx == app.selected_habit_idx;
             let prefix = if is_selected { "► " } else { "  " };
 
             let style = match status {
-                HabitStatus::Done => Style::default().fg(Color::Green),
-                HabitStatus::Skipped => Style::default().fg(Color::Red),
-                HabitStatus::Unmarked => Style::default().fg(Color::Gray),
+                HabitStatus::Done => Style::default().fg(app.theme.done),
+                HabitStatus::Partial => Style::default().fg(app.theme.partial),
+                HabitStatus::Skipped => Style::default().fg(app.theme.skipped),
+                HabitStatus::NotApplicable => Style::default().fg(app.theme.not_applicable),
+                HabitStatus::Unmarked => Style::default().fg(app.theme.unmarked),
             };
 
             let selected_style = if is_selected {
@@ -80,33 +109,108 @@ fn draw_habits_list(f: &mut Frame, area: Rect, app: &App) {
                 style
             };
 
-            let line = Line::from(vec![
+            let mut spans = vec![
                 Span::styled(prefix, selected_style),
-                Span::styled(format!("{:<20}", habit.name), selected_style),
+                Span::styled(
+                    format!("{:<20}", habit.display_name()),
+                    habit_color(habit).map_or(selected_style, |color| selected_style.fg(color)),
+                ),
+                Span::styled(format!("{:>4}", format!("{}d", streak)), Style::default().fg(app.theme.selected)),
                 Span::raw("  "),
-                Span::styled(status_str, selected_style),
-            ]);
-
-            ListItem::new(line)
+                Span::styled(format!("{:<10}", status_str), selected_style),
+            ];
+            if app.habit_has_note(habit.id, selected_date) {
+                spans.push(Span::styled(note_marker, Style::default().fg(app.theme.header)));
+            }
+            if overdue.contains(&habit.id) {
+                spans.push(Span::styled(
+                    format!("{} from last week  ", overdue_marker),
+                    Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD),
+                ));
+            }
+            if let Some((done, goal)) = app.weekly_goal_progress(habit.id) {
+                spans.extend(goal_progress(app, done, goal));
+            }
+
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
+    let mut filters = Vec::new();
+    if let Some(tag) = &app.tag_filter {
+        filters.push(format!("#{}", tag));
+    }
+    if app.show_all_habits {
+        filters.push("showing all".to_string());
+    }
+    if app.only_unmarked {
+        filters.push("unmarked only".to_string());
+    }
+    if app.as_needed_panel {
+        filters.push("as needed".to_string());
+    }
+    let title = if filters.is_empty() {
+        "Habits for this day".to_string()
+    } else {
+        format!("Habits for this day ({})", filters.join(", "))
+    };
     let block = Block::default()
         .borders(Borders::ALL)
-        .title("Habits for this day");
+        .title(title);
     let list = List::new(items).block(block);
-    f.render_widget(list, area);
+    // Start from where the list was last drawn; rendering scrolls just enough to show the selection
+    let mut state = ListState::default()
+        .with_offset(app.habit_list_scroll.offset())
+        .with_selected(Some(app.selected_habit_idx));
+    f.render_stateful_widget(list, area, &mut state);
+    app.habit_list_scroll.remember(state.offset(), area.height.saturating_sub(2) as usize);
+}
+
+/// Bar and count toward a weekly goal, e.g. "▰▰▱▱ 2/4 this week", with a star once it's met
+fn goal_progress(app: &App, done: usize, goal: u32) -> Vec<Span<'static>> {
+    let goal = goal as usize;
+    let filled = done.min(goal);
+    let (full, empty, star) = if app.storage.settings().ascii_glyphs { ("#", "-", "*") } else { ("▰", "▱", "★") };
+    let mut spans = vec![
+        Span::styled(full.repeat(filled), Style::default().fg(app.theme.done)),
+        Span::styled(empty.repeat(goal - filled), Style::default().fg(app.theme.muted)),
+        Span::raw(format!(" {}/{} this week", done, goal)),
+    ];
+    if done >= goal {
+        spans.push(Span::styled(
+            format!(" {} goal met", star),
+            Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD),
+        ));
+    }
+    spans
 }
 
-/// Draw the note section for the selected habit
+/// Draw the note section for the selected habit, under its description if it has one
 fn draw_note_section(f: &mut Frame, area: Rect, app: &App) {
     let note = app.get_current_note();
 
-    let text = if let Some(note_text) = note {
-        format!("Note: {}", note_text)
-    } else {
-        "No note for this habit. Press 'n' to add one.".to_string()
-    };
+    let mut text = Vec::new();
+    if let Some(description) = app.selected_habit().and_then(|h| h.description.as_deref()) {
+        text.push(Line::from(Span::styled(
+            description.to_string(),
+            Style::default().fg(app.theme.unmarked).add_modifier(Modifier::ITALIC),
+        )));
+    }
+    let vim = app.storage.settings().vim_keys;
+    let count = app.current_notes().len();
+    text.push(Line::from(match note {
+        Some(note_text) if count > 1 => format!(
+            "Note: {}  (+{} earlier, '{}' to see all)",
+            note_text,
+            count - 1,
+            app.keymap.short_label(Action::ViewNotes, vim)
+        ),
+        Some(note_text) => format!("Note: {}", note_text),
+        None => format!(
+            "No note for this habit. Press '{}' to add one.",
+            app.keymap.short_label(Action::EditNote, vim)
+        ),
+    }));
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -114,6 +218,6 @@ fn draw_note_section(f: &mut Frame, area: Rect, app: &App) {
     let paragraph = Paragraph::new(text)
         .block(block)
         .wrap(Wrap { trim: true })
-        .style(Style::default().fg(Color::Cyan));
+        .style(Style::default().fg(app.theme.header));
     f.render_widget(paragraph, area);
 }
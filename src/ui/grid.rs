@@ -0,0 +1,99 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::app::App;
+use crate::keymap::Action;
+use crate::models::HabitStatus;
+use crate::theme::habit_color;
+
+/// Draw the week grid: habits as rows, the week's days as columns
+pub fn draw(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Header
+            Constraint::Min(5),     // Grid
+            Constraint::Length(3),  // Instructions
+        ])
+        .split(f.area());
+
+    // Header
+    let header = Paragraph::new(format!("Week Grid - {}", app.current_week.format()))
+        .block(Block::default().borders(Borders::ALL))
+        .style(Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD));
+    f.render_widget(header, chunks[0]);
+
+    // Grid
+    let days = app.current_week.days();
+    let mut header_cells = vec![Cell::from("Habit")];
+    header_cells.extend((0..7).map(|idx| Cell::from(app.current_week.weekday_name(idx))));
+    let header_row = Row::new(header_cells)
+        .style(Style::default().fg(app.theme.selected).add_modifier(Modifier::BOLD));
+
+    let rows: Vec<Row> = app
+        .habits()
+        .iter()
+        .enumerate()
+        .map(|(row_idx, habit)| {
+            let name_style = habit_color(habit).map_or(Style::default(), |color| Style::default().fg(color));
+            let mut cells = vec![Cell::from(habit.display_name()).style(name_style)];
+            for (col_idx, &date) in days.iter().enumerate() {
+                let status = app.get_habit_status(habit.id, date);
+                let symbol = match status {
+                    HabitStatus::Done => '✓',
+                    HabitStatus::Partial => '◐',
+                    HabitStatus::Skipped => '✗',
+                    HabitStatus::NotApplicable => '∅',
+                    HabitStatus::Unmarked => ' ',
+                };
+
+                let mut style = match status {
+                    HabitStatus::Done => Style::default().fg(app.theme.done),
+                    HabitStatus::Partial => Style::default().fg(app.theme.partial),
+                    HabitStatus::Skipped => Style::default().fg(app.theme.skipped),
+                    HabitStatus::NotApplicable => Style::default().fg(app.theme.not_applicable),
+                    HabitStatus::Unmarked => Style::default().fg(app.theme.unmarked),
+                };
+                if app.is_future(date) {
+                    style = style.fg(app.theme.muted);
+                }
+                if row_idx == app.grid_row && col_idx == app.grid_col {
+                    style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+                }
+
+                cells.push(Cell::from(format!("[{}]", symbol)).style(style));
+            }
+            Row::new(cells)
+        })
+        .collect();
+
+    let mut widths = vec![Constraint::Min(20)];
+    widths.extend([Constraint::Length(5); 7]);
+    let table = Table::new(rows, widths)
+        .header(header_row)
+        .block(Block::default().borders(Borders::ALL).title("Habits"));
+    f.render_widget(table, chunks[1]);
+
+    // Instructions
+    let vim = app.storage.settings().vim_keys;
+    let key = |action| app.keymap.short_label(action, vim);
+    let moves = [Action::PrevDay, Action::NextDay, Action::PrevHabit, Action::NextHabit].map(key).concat();
+    let instructions = vec![
+        Span::styled(moves, Style::default().fg(app.theme.selected)),
+        Span::raw(" Move  "),
+        Span::styled(key(Action::ToggleStatus), Style::default().fg(app.theme.selected)),
+        Span::raw(" Toggle  "),
+        Span::styled(key(Action::Cancel), Style::default().fg(app.theme.skipped)),
+        Span::raw(" Cancel  "),
+        Span::styled(key(Action::Quit), Style::default().fg(app.theme.done)),
+        Span::raw(" Return"),
+    ];
+    let instructions_widget = Paragraph::new(Line::from(instructions))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(instructions_widget, chunks[2]);
+}
@@ -0,0 +1,60 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Draw one habit's settings and all-time stats
+pub fn draw(f: &mut Frame, app: &App) {
+    let Some(detail) = app.detail_habit.and_then(|id| app.habit_detail(id)) else {
+        let paragraph = Paragraph::new("Habit not found.")
+            .block(Block::default().borders(Borders::ALL).title("Habit Detail"));
+        f.render_widget(paragraph, f.area());
+        return;
+    };
+
+    let field = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<16}", name), Style::default().fg(app.theme.selected)),
+            Span::raw(value),
+        ])
+    };
+    let tags = if detail.tags.is_empty() {
+        "none".to_string()
+    } else {
+        detail.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")
+    };
+
+    let lines = vec![
+        Line::from(Span::styled(
+            detail.name,
+            Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(detail.description.unwrap_or_default()),
+        Line::from(""),
+        field("Frequency", detail.frequency),
+        field("Added", detail.created_at.format("%b %d, %Y").to_string()),
+        field("Tags", tags),
+        Line::from(""),
+        field("Current streak", format!("{}d", detail.current_streak)),
+        field("Best streak", format!("{}d", detail.best_streak)),
+        field("All-time rate", format!("{}%", detail.completion_rate)),
+        field(
+            "Last done",
+            detail.last_done.map_or("never".to_string(), |d| d.format("%b %d, %Y").to_string()),
+        ),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("q/Esc", Style::default().fg(app.theme.done)),
+            Span::raw(" Back"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Habit Detail"))
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, f.area());
+}
@@ -1,19 +1,35 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 
 use crate::app::{App, HabitMgmtMode};
+use crate::models::Frequency;
+use crate::theme::habit_color;
+
+/// Terminal width from which editing a habit shows its stats beside the form
+const PREVIEW_MIN_WIDTH: u16 = 100;
 
 /// Draw the habit management view
 pub fn draw(f: &mut Frame, app: &App) {
     match app.habit_mgmt_mode {
-        HabitMgmtMode::List => draw_habit_list(f, app),
-        HabitMgmtMode::AddHabit => draw_habit_input(f, app, "Add New Habit"),
-        HabitMgmtMode::EditHabit => draw_habit_input(f, app, "Edit Habit"),
+        HabitMgmtMode::List | HabitMgmtMode::MergeHabit => draw_habit_list(f, app),
+        HabitMgmtMode::AddHabit => draw_habit_input(f, app, "Add New Habit", "Habit Name"),
+        HabitMgmtMode::EditHabit => draw_habit_input(f, app, "Edit Habit", "Habit Name"),
+        HabitMgmtMode::EditLabel => draw_habit_input(f, app, "Edit Label (e.g. AM, PM)", "Label"),
+        HabitMgmtMode::EditTags => draw_habit_input(f, app, "Edit Tags (comma-separated)", "Tags"),
+        HabitMgmtMode::EditDescription => draw_habit_input(f, app, "Edit Description (empty to remove)", "Description"),
+        HabitMgmtMode::EditNoteTemplate => draw_habit_input(f, app, "Edit Note Template (empty to remove)", "New notes start with"),
+        HabitMgmtMode::EditReminder => draw_habit_input(f, app, "Edit Reminder (HH:MM, empty to remove)", "Remind at"),
+        HabitMgmtMode::EditGoal => draw_habit_input(f, app, "Edit Weekly Goal (1-7 times, empty to remove)", "Done per week"),
+        HabitMgmtMode::ArchiveTag => draw_habit_input(f, app, "Archive Habits by Tag", "Tag"),
+        HabitMgmtMode::BackupPath => draw_habit_input(f, app, "Back Up All Data", "Backup file"),
+        HabitMgmtMode::RestorePath => draw_habit_input(f, app, "Restore From Backup", "Backup file"),
+        HabitMgmtMode::ImportListPath => draw_habit_input(f, app, "Add Habits From a List", "Text file, one habit per line"),
+        HabitMgmtMode::ConfirmArchiveTag => draw_archive_confirmation(f, app),
     }
 }
 
@@ -29,29 +45,89 @@ fn draw_habit_list(f: &mut Frame, app: &App) {
         .split(f.area());
 
     // Header
-    let header = Paragraph::new("Habit Management")
-        .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let skip_note = if app.storage.settings().require_skip_note { "on" } else { "off" };
+    let week_start = format!("{:?}", app.storage.settings().week_start);
+    let skips_keep = if app.storage.settings().skips_keep_streak { "on" } else { "off" };
+    let vim_keys = if app.storage.settings().vim_keys { "on" } else { "off" };
+    let bell = if app.storage.settings().bell_on_complete { "on" } else { "off" };
+    let skip_reason = if app.storage.settings().ask_skip_reason { "on" } else { "off" };
+    let strict = if app.storage.settings().strict_frequency {
+        format!("on ({})", app.weekly_day())
+    } else {
+        "off".to_string()
+    };
+    let merging = app.merge_source
+        .filter(|_| app.habit_mgmt_mode == HabitMgmtMode::MergeHabit)
+        .and_then(|id| app.storage.get_habit(id));
+    let header = match (&app.flash_message, merging) {
+        (Some(message), _) => Paragraph::new(message.as_str())
+            .style(Style::default().fg(app.theme.accent)),
+        (None, Some(habit)) => Paragraph::new(format!(
+            "Merge {} into which habit? Its days and notes move there and it is deleted. ↑↓ pick, Enter merge, Esc cancel",
+            habit.display_name()
+        ))
+            .style(Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD)),
+        (None, None) => Paragraph::new(format!(
+            "Habit Management    Notes required on skip: {}    Week starts: {}    Skips keep streak: {}    Vim keys: {}    Bell: {}    Ask skip reason: {}    Strict frequency: {}",
+            skip_note, week_start, skips_keep, vim_keys, bell, skip_reason, strict
+        ))
+            .style(Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD)),
+    }
+    .block(Block::default().borders(Borders::ALL));
     f.render_widget(header, chunks[0]);
 
-    // Habit list
+    // Habit list: name, last 7 days, current streak, then settings
     let habits = app.habits();
+    let today = app.today();
     let items: Vec<ListItem> = habits
         .iter()
         .enumerate()
         .map(|(idx, habit)| {
             let prefix = if idx == app.habit_mgmt_selected_idx {
                 "► "
+            } else if merging.is_some_and(|source| source.id == habit.id) {
+                "» "
             } else {
                 "  "
             };
-            let content = format!("{}{:<30} [{}]", prefix, habit.name, habit.frequency.description());
+            let auto_fill = if habit.frequency == Frequency::Weekly && !habit.auto_fill_week {
+                "  (no auto-skip)"
+            } else {
+                ""
+            };
+            let weekends = if habit.skip_weekends { "  (weekdays only)" } else { "" };
+            let tags: String = habit.tags.iter().map(|t| format!("  #{}", t)).collect();
+            let reminder = habit.remind_at
+                .map(|time| format!("  (remind {})", time.format("%H:%M")))
+                .unwrap_or_default();
+            let goal = habit.weekly_goal
+                .map(|goal| format!("  (goal {}/wk)", goal))
+                .unwrap_or_default();
+            let streak = app.storage.current_streak(habit.id, today);
+            // The name is drawn in the habit's color, if it has one
+            let name = format!("{:<24}", truncate_name(&habit.display_name(), 24));
+            let name = match habit_color(habit) {
+                Some(color) => Span::styled(name, Style::default().fg(color)),
+                None => Span::raw(name),
+            };
+            let details = format!(
+                " {}  {:>3}d  [{}]  since {}{}{}{}{}{}",
+                app.recent_status_strip(habit.id, today),
+                streak,
+                habit.frequency.description(),
+                habit.created_at.format("%b %d, %Y"),
+                auto_fill,
+                weekends,
+                reminder,
+                goal,
+                tags
+            );
             let style = if idx == app.habit_mgmt_selected_idx {
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                Style::default().fg(app.theme.selected).add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
-            ListItem::new(content).style(style)
+            ListItem::new(Line::from(vec![Span::raw(prefix), name, Span::raw(details)])).style(style)
         })
         .collect();
 
@@ -59,26 +135,82 @@ fn draw_habit_list(f: &mut Frame, app: &App) {
         .block(Block::default()
             .borders(Borders::ALL)
             .title(format!("Habits ({})", habits.len())));
-    f.render_widget(list, chunks[1]);
+    let mut state = ListState::default()
+        .with_offset(app.habit_mgmt_scroll.offset())
+        .with_selected(Some(app.habit_mgmt_selected_idx));
+    f.render_stateful_widget(list, chunks[1], &mut state);
+    app.habit_mgmt_scroll.remember(state.offset(), chunks[1].height.saturating_sub(2) as usize);
 
     // Instructions
     let instructions = vec![
         Line::from(vec![
-            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+            Span::styled("↑↓", Style::default().fg(app.theme.selected)),
             Span::raw(" Select  "),
-            Span::styled("a", Style::default().fg(Color::Green)),
+            Span::styled("a", Style::default().fg(app.theme.done)),
             Span::raw(" Add  "),
-            Span::styled("e", Style::default().fg(Color::Yellow)),
+            Span::styled("e", Style::default().fg(app.theme.selected)),
             Span::raw(" Edit  "),
-            Span::styled("d", Style::default().fg(Color::Red)),
-            Span::raw(" Delete"),
+            Span::styled("d", Style::default().fg(app.theme.skipped)),
+            Span::raw(" Delete  "),
+            Span::styled("D", Style::default().fg(app.theme.done)),
+            Span::raw(" Duplicate  "),
+            Span::styled("M", Style::default().fg(app.theme.skipped)),
+            Span::raw(" Merge  "),
+            Span::styled("l", Style::default().fg(app.theme.selected)),
+            Span::raw(" Label  "),
+            Span::styled("E", Style::default().fg(app.theme.selected)),
+            Span::raw(" Description  "),
+            Span::styled("n", Style::default().fg(app.theme.selected)),
+            Span::raw(" Note Template  "),
+            Span::styled("t", Style::default().fg(app.theme.selected)),
+            Span::raw(" Tags  "),
+            Span::styled("r", Style::default().fg(app.theme.selected)),
+            Span::raw(" Reminder  "),
+            Span::styled("g", Style::default().fg(app.theme.selected)),
+            Span::raw(" Goal  "),
+            Span::styled("c", Style::default().fg(app.theme.selected)),
+            Span::raw(" Color  "),
+            Span::styled("X", Style::default().fg(app.theme.skipped)),
+            Span::raw(" Archive by Tag  "),
+            Span::styled("i", Style::default().fg(app.theme.header)),
+            Span::raw(" Details  "),
+            Span::styled("B", Style::default().fg(app.theme.done)),
+            Span::raw(" Back Up  "),
+            Span::styled("R", Style::default().fg(app.theme.selected)),
+            Span::raw(" Restore  "),
+            Span::styled("I", Style::default().fg(app.theme.selected)),
+            Span::raw(" Import List"),
         ]),
         Line::from(vec![
-            Span::styled("[]", Style::default().fg(Color::Yellow)),
+            Span::styled("[]", Style::default().fg(app.theme.selected)),
             Span::raw(" Move Up/Down  "),
-            Span::styled("f", Style::default().fg(Color::Cyan)),
+            Span::styled("f", Style::default().fg(app.theme.header)),
             Span::raw(" Change Frequency  "),
-            Span::styled("q/Esc", Style::default().fg(Color::Green)),
+            Span::styled("+-", Style::default().fg(app.theme.header)),
+            Span::raw(" Interval  "),
+            Span::styled("1-7", Style::default().fg(app.theme.header)),
+            Span::raw(" Days  "),
+            Span::styled("w", Style::default().fg(app.theme.header)),
+            Span::raw(" Auto-skip  "),
+            Span::styled("o", Style::default().fg(app.theme.header)),
+            Span::raw(" Weekends Off  "),
+            Span::styled("S", Style::default().fg(app.theme.header)),
+            Span::raw(" Skip Notes  "),
+            Span::styled("L", Style::default().fg(app.theme.header)),
+            Span::raw(" Legend  "),
+            Span::styled("W", Style::default().fg(app.theme.header)),
+            Span::raw(" Week Start  "),
+            Span::styled("K", Style::default().fg(app.theme.header)),
+            Span::raw(" Skips Keep Streak  "),
+            Span::styled("V", Style::default().fg(app.theme.header)),
+            Span::raw(" Vim Keys  "),
+            Span::styled("b", Style::default().fg(app.theme.header)),
+            Span::raw(" Bell  "),
+            Span::styled("A", Style::default().fg(app.theme.header)),
+            Span::raw(" Ask Skip Reason  "),
+            Span::styled("F", Style::default().fg(app.theme.header)),
+            Span::raw(" Strict Frequency  "),
+            Span::styled("q/Esc", Style::default().fg(app.theme.done)),
             Span::raw(" Return"),
         ]),
     ];
@@ -88,42 +220,163 @@ fn draw_habit_list(f: &mut Frame, app: &App) {
     f.render_widget(instructions_widget, chunks[2]);
 }
 
-/// Draw the habit input view (for both add and edit)
-fn draw_habit_input(f: &mut Frame, app: &App, title: &str) {
+/// Shorten a name to at most `max` characters, marking the cut with an ellipsis
+fn truncate_name(name: &str, max: usize) -> String {
+    if name.chars().count() <= max {
+        name.to_string()
+    } else {
+        let cut: String = name.chars().take(max - 1).collect();
+        format!("{}…", cut)
+    }
+}
+
+/// Draw the habit input view (for adding, editing and labelling)
+fn draw_habit_input(f: &mut Frame, app: &App, title: &str, field: &str) {
+    // Renaming shows the habit's stats beside the form when there's room
+    let mut area = f.area();
+    if app.habit_mgmt_mode == HabitMgmtMode::EditHabit && area.width >= PREVIEW_MIN_WIDTH {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(area);
+        area = columns[0];
+        draw_habit_preview(f, app, columns[1]);
+    }
+
+    // Grow the input box with longer text (plus the cursor cell), up to 6 lines
+    let inner_width = area.width.saturating_sub(2).max(1) as usize;
+    let input_lines = (app.input_buffer.chars().count() / inner_width + 1).min(6) as u16;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),  // Header
-            Constraint::Length(3),  // Input box
+            Constraint::Length(input_lines + 2),  // Input box
             Constraint::Length(3),  // Instructions
             Constraint::Min(0),     // Spacer
         ])
-        .split(f.area());
+        .split(area);
 
     // Header
     let header = Paragraph::new(title)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        .style(Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD));
     f.render_widget(header, chunks[0]);
 
-    // Input box
-    let input = Paragraph::new(app.input_buffer.as_str())
+    // Input box, with the cursor shown as a highlighted cell
+    let input = Paragraph::new(super::input_with_cursor(app))
         .block(Block::default()
             .borders(Borders::ALL)
-            .title("Habit Name")
-            .style(Style::default().fg(Color::Yellow)))
+            .title(field)
+            .style(Style::default().fg(app.theme.selected)))
         .wrap(Wrap { trim: false });
     f.render_widget(input, chunks[1]);
 
     // Instructions
     let instructions = vec![
-        Span::raw("Type the habit name. "),
-        Span::styled("Enter", Style::default().fg(Color::Green)),
+        Span::raw(format!("Type the {} (←→ Home End move the cursor). ", field.to_lowercase())),
+        Span::styled("Enter", Style::default().fg(app.theme.done)),
         Span::raw(" to save, "),
-        Span::styled("Esc", Style::default().fg(Color::Red)),
+        Span::styled("Esc", Style::default().fg(app.theme.skipped)),
         Span::raw(" to cancel."),
     ];
     let instructions_widget = Paragraph::new(Line::from(instructions))
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(instructions_widget, chunks[2]);
 }
+
+/// The selected habit's frequency and all-time counts, as it was before this edit
+fn draw_habit_preview(f: &mut Frame, app: &App, area: Rect) {
+    let Some(habit) = app.habits().get(app.habit_mgmt_selected_idx).copied() else {
+        return;
+    };
+    let today = app.today();
+    let stats = app.storage.get_stats(habit.created_at, today)
+        .remove(&habit.id)
+        .unwrap_or_default();
+    let label = |text: &str| Span::styled(format!("{:<12}", text), Style::default().fg(app.theme.muted));
+    let lines = vec![
+        Line::from(Span::styled(habit.display_name(), Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(vec![label("Frequency"), Span::raw(habit.frequency.description())]),
+        Line::from(vec![label("Since"), Span::raw(habit.created_at.format("%b %d, %Y").to_string())]),
+        Line::from(vec![label("Streak"), Span::raw(format!("{} days", app.storage.current_streak(habit.id, today)))]),
+        Line::from(""),
+        Line::from(vec![label("Done"), Span::styled(stats.done.to_string(), Style::default().fg(app.theme.done))]),
+        Line::from(vec![label("Partial"), Span::styled(stats.partial.to_string(), Style::default().fg(app.theme.partial))]),
+        Line::from(vec![label("Skipped"), Span::styled(stats.skipped.to_string(), Style::default().fg(app.theme.skipped))]),
+        Line::from(vec![label("Unmarked"), Span::raw(stats.unmarked.to_string())]),
+    ];
+    let preview = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("All Time"));
+    f.render_widget(preview, area);
+}
+
+/// Ask before archiving every habit with the typed tag
+fn draw_archive_confirmation(f: &mut Frame, app: &App) {
+    let tag = app.input_buffer.trim();
+    let count = app.storage.count_tagged(tag);
+    let text = vec![
+        Line::from(format!(
+            "Archive {} habit{} tagged '{}'?",
+            count,
+            if count == 1 { "" } else { "s" },
+            tag
+        )),
+        Line::from("They'll be hidden everywhere; their history is kept."),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("y", Style::default().fg(app.theme.done)),
+            Span::raw(" Archive  "),
+            Span::styled("n/Esc", Style::default().fg(app.theme.skipped)),
+            Span::raw(" Cancel"),
+        ]),
+    ];
+    let paragraph = Paragraph::new(text)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title("Archive Habits by Tag")
+            .style(Style::default().fg(app.theme.selected)));
+    f.render_widget(paragraph, f.area());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppView;
+    use crate::models::HabitStatus;
+    use ratatui::{backend::TestBackend, Terminal};
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_editing_a_habit_shows_its_stats_when_wide() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        let shower = app.habits()[0].id;
+        let today = app.today();
+        app.storage.update_log_status(shower, today, HabitStatus::Done).unwrap();
+        app.set_view(AppView::HabitManagement);
+        app.start_edit_habit();
+
+        let render = |app: &App, width: u16| -> String {
+            let mut terminal = Terminal::new(TestBackend::new(width, 24)).unwrap();
+            terminal.draw(|f| draw(f, app)).unwrap();
+            terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+        };
+
+        // Narrow terminals only get the form
+        let narrow = render(&app, PREVIEW_MIN_WIDTH - 1);
+        assert!(narrow.contains("Edit Habit"));
+        assert!(!narrow.contains("All Time"));
+
+        let wide = render(&app, PREVIEW_MIN_WIDTH);
+        assert!(wide.contains("All Time"));
+        assert!(wide.contains("Frequency   Daily"));
+        assert!(wide.contains("Done        1"));
+        assert!(wide.contains("Streak      1 days"));
+
+        // Other inputs stay full width
+        app.cancel_habit_input();
+        app.start_edit_label();
+        assert!(!render(&app, PREVIEW_MIN_WIDTH).contains("All Time"));
+    }
+}
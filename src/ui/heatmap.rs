@@ -0,0 +1,108 @@
+use chrono::NaiveDate;
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::models::HabitStatus;
+
+/// Width of the weekday labels down the left edge
+const LABEL_WIDTH: usize = 4;
+
+/// Draw a year of one habit as a grid: a column per week, a row per weekday
+pub fn draw(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Header
+            Constraint::Min(11),    // Heatmap
+            Constraint::Length(3),  // Instructions
+        ])
+        .split(f.area());
+
+    // Header
+    let habit_name = app.habits().get(app.heatmap_habit_idx)
+        .map(|h| h.display_name())
+        .unwrap_or_else(|| "No habits".to_string());
+    let header = Paragraph::new(format!("{} - {}", habit_name, app.heatmap_year))
+        .block(Block::default().borders(Borders::ALL).title("Year in Pixels"))
+        .style(Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD));
+    f.render_widget(header, chunks[0]);
+
+    // Two cells per week when there's room, one when there isn't
+    let weeks = app.heatmap_weeks();
+    let inner_width = chunks[1].width.saturating_sub(2) as usize;
+    let cell_width = if LABEL_WIDTH + weeks.len() * 2 <= inner_width { 2 } else { 1 };
+    let ascii = app.storage.settings().ascii_glyphs;
+    let (filled, blank) = if ascii { ("#", ".") } else { ("■", "·") };
+
+    // The first column is the week holding January 1st, which may start in December
+    let first_week = app.storage.week_of(
+        NaiveDate::from_ymd_opt(app.heatmap_year, 1, 1).unwrap_or_default()
+    );
+
+    // Month names over the week each month starts in
+    let mut months = " ".repeat(LABEL_WIDTH);
+    for first in (1..=12).filter_map(|month| NaiveDate::from_ymd_opt(app.heatmap_year, month, 1)) {
+        let column = (app.storage.week_of(first).start - first_week.start).num_weeks() as usize;
+        let at = LABEL_WIDTH + column * cell_width;
+        if months.chars().count() <= at {
+            months.push_str(&" ".repeat(at - months.chars().count()));
+            months.push_str(&first.format("%b").to_string());
+        }
+    }
+    let mut lines = vec![Line::from(Span::styled(months, Style::default().fg(app.theme.selected)))];
+
+    for row in 0..7 {
+        let mut spans = vec![Span::styled(
+            format!("{:<width$}", first_week.weekday_name(row), width = LABEL_WIDTH),
+            Style::default().fg(app.theme.selected),
+        )];
+        for days in &weeks {
+            let (glyph, style) = match days[row] {
+                None => (" ", Style::default()),
+                Some(HabitStatus::Done) => (filled, Style::default().fg(app.theme.done).add_modifier(Modifier::BOLD)),
+                Some(HabitStatus::Partial) => (filled, Style::default().fg(app.theme.partial)),
+                Some(HabitStatus::Skipped) => (filled, Style::default().fg(app.theme.skipped).add_modifier(Modifier::DIM)),
+                Some(HabitStatus::NotApplicable) => (filled, Style::default().fg(app.theme.not_applicable).add_modifier(Modifier::DIM)),
+                Some(HabitStatus::Unmarked) => (blank, Style::default().fg(app.theme.muted)),
+            };
+            spans.push(Span::styled(format!("{:<width$}", glyph, width = cell_width), style));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    // Year totals under the grid
+    let count = |status| weeks.iter().flatten().filter(|&&day| day == Some(status)).count();
+    let days_shown = weeks.iter().flatten().filter(|day| day.is_some()).count();
+    lines.push(Line::from(""));
+    lines.push(Line::from(format!(
+        "Done {}  Partial {}  Skipped {}  N/A {}  of {} days",
+        count(HabitStatus::Done),
+        count(HabitStatus::Partial),
+        count(HabitStatus::Skipped),
+        count(HabitStatus::NotApplicable),
+        days_shown,
+    )));
+
+    let heatmap = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(heatmap, chunks[1]);
+
+    // Instructions
+    let instructions = vec![
+        Span::styled("←→", Style::default().fg(app.theme.selected)),
+        Span::raw(" Habit  "),
+        Span::styled("↑↓", Style::default().fg(app.theme.selected)),
+        Span::raw(" Year  "),
+        Span::styled("q/Esc", Style::default().fg(app.theme.done)),
+        Span::raw(" Back"),
+    ];
+    let instructions_widget = Paragraph::new(Line::from(instructions))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(instructions_widget, chunks[2]);
+}
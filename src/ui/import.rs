@@ -0,0 +1,65 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Draw what an import would change, asking before anything is applied
+pub fn draw(f: &mut Frame, app: &App) {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Merge imported data, or replace everything with it?",
+            Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    if let Some((_, diff)) = &app.pending_import {
+        if !diff.habits_added.is_empty() {
+            lines.push(Line::from(Span::styled("New habits:", Style::default().fg(app.theme.done))));
+            for name in &diff.habits_added {
+                lines.push(Line::from(format!("  + {}", name)));
+            }
+        }
+        if !diff.habits_updated.is_empty() {
+            lines.push(Line::from(Span::styled("Changed habits:", Style::default().fg(app.theme.selected))));
+            for change in &diff.habits_updated {
+                lines.push(Line::from(format!("  ~ {}", change.name)));
+                for (field, from, to) in &change.fields {
+                    lines.push(Line::from(format!("      {}: '{}' → '{}'", field, from, to)));
+                }
+            }
+        }
+        if diff.is_empty() {
+            lines.push(Line::from("Merging changes nothing; replacing drops what only exists here."));
+        }
+        lines.push(Line::from(format!("Days added: {}", diff.logs_added)));
+        if !diff.logs_overwritten.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!("Days overwritten: {}", diff.logs_overwritten.len()),
+                Style::default().fg(app.theme.skipped),
+            )));
+            for (name, date) in &diff.logs_overwritten {
+                lines.push(Line::from(format!("  ! {} on {}", name, date.format("%b %d, %Y"))));
+            }
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("y", Style::default().fg(app.theme.done)),
+        Span::raw(" Merge  "),
+        Span::styled("r", Style::default().fg(app.theme.selected)),
+        Span::raw(" Replace all  "),
+        Span::styled("n/Esc", Style::default().fg(app.theme.skipped)),
+        Span::raw(" Cancel"),
+    ]));
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Import Preview"))
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, f.area());
+}
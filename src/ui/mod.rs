@@ -1,124 +1,393 @@
 // UI components for Healing-Habits TUI
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
 
 use crate::app::{App, AppView};
+use crate::keymap::Action;
+use crate::theme::Theme;
 
 pub mod week_strip;
 pub mod day_view;
 pub mod stats;
 pub mod habit_mgmt;
+pub mod grid;
+pub mod palette;
+pub mod activity;
+pub mod preview;
+pub mod review;
+pub mod habit_detail;
+pub mod import;
+pub mod notes;
+pub mod search;
+pub mod heatmap;
+
+thread_local! {
+    /// Whether a view is currently being drawn (panics are caught and reported on screen)
+    static DRAWING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Install a panic hook that stays quiet for panics caught while drawing,
+/// so they don't print over the alternate screen
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !DRAWING.with(Cell::get) {
+            default_hook(info);
+        }
+    }));
+}
 
 /// Main draw function - routes to appropriate view
-pub fn draw(f: &mut Frame, app: &App) {
+/// Returns an error message if the view panicked; an error panel is drawn instead
+pub fn draw(f: &mut Frame, app: &App) -> Option<String> {
+    let error = draw_guarded(f, |f| draw_view(f, app));
+    if let Some(message) = &error {
+        draw_error_panel(f, &app.theme, message);
+    }
+    error
+}
+
+/// Run a draw function, catching any panic and returning its message
+fn draw_guarded(f: &mut Frame, draw_fn: impl FnOnce(&mut Frame)) -> Option<String> {
+    DRAWING.with(|d| d.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| draw_fn(f)));
+    DRAWING.with(|d| d.set(false));
+
+    let payload = result.err()?;
+    let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown error".to_string());
+    Some(format!("Display error: {}", message))
+}
+
+/// Draw an error panel in place of a view that failed to render
+fn draw_error_panel(f: &mut Frame, theme: &Theme, message: &str) {
+    let text = vec![
+        Line::from(Span::styled("Something went wrong drawing this screen.", Style::default().fg(theme.skipped).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(message.to_string()),
+        Line::from(""),
+        Line::from("Your data is safe. Switch to another screen, or press Ctrl+C to quit."),
+    ];
+
+    let area = f.area();
+    f.render_widget(Clear, area);
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL).title("Error"))
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+/// Route to the view's draw function
+fn draw_view(f: &mut Frame, app: &App) {
     match app.view {
-        AppView::Main => draw_main_view(f, app),
+        AppView::Main | AppView::GoToDate => draw_main_view(f, app),
         AppView::Stats => stats::draw(f, app),
         AppView::Help => draw_help_view(f, app),
         AppView::HabitManagement => habit_mgmt::draw(f, app),
         AppView::NoteInput => draw_note_input(f, app),
         AppView::ExportConfirmation => draw_export_confirmation(f, app),
+        AppView::Grid => grid::draw(f, app),
+        AppView::ResetWeekConfirmation => draw_reset_week_confirmation(f, app),
+        AppView::CommandPalette => palette::draw(f, app),
+        AppView::Activity => activity::draw(f, app),
+        AppView::WeekNoteInput => draw_week_note_input(f, app),
+        AppView::ExportPreview | AppView::ExportRange => preview::draw(f, app),
+        AppView::Review => review::draw(f, app),
+        AppView::HabitDetail => habit_detail::draw(f, app),
+        AppView::ImportPreview => import::draw(f, app),
+        AppView::LockWarning => draw_lock_warning(f, app),
+        AppView::NotesHistory => notes::draw(f, app),
+        AppView::Search => search::draw(f, app),
+        AppView::Heatmap => heatmap::draw(f, app),
     }
 }
 
-/// Draw the main view (week strip + day details)
+/// Draw the main view (reminders + week strip + day details)
 fn draw_main_view(f: &mut Frame, app: &App) {
-    let chunks = Layout::default()
+    let layout = main_layout(f.area(), app);
+
+    // Draw reminders still due today
+    if !app.reminders_due.is_empty() {
+        let banner = Paragraph::new(format!("Reminder: {} still unmarked today", app.reminders_due.join(", ")))
+            .style(Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD));
+        f.render_widget(banner, layout.banner);
+    }
+
+    // Draw week header
+    draw_week_header(f, layout.header, app);
+
+    // Draw week strip, with the week's summary under it on wide terminals
+    week_strip::draw(f, layout.strip, app);
+    if let Some(area) = layout.summary {
+        week_strip::draw_summary(f, area, app);
+    }
+
+    // Draw day details
+    day_view::draw(f, layout.day, app);
+
+    // Draw footer
+    draw_footer(f, layout.footer, app);
+}
+
+/// Terminals at least this wide show the week and the day side by side
+pub const WIDE_LAYOUT_MIN_WIDTH: u16 = 140;
+
+/// Where each part of the main view goes
+struct MainLayout {
+    banner: Rect,
+    header: Rect,
+    strip: Rect,
+    /// Per-habit summary of the week, only in the wide layout
+    summary: Option<Rect>,
+    day: Rect,
+    footer: Rect,
+}
+
+/// Split the main view into reminder banner, week header, week strip, day details and footer
+/// From `WIDE_LAYOUT_MIN_WIDTH` columns the week (strip and summary) sits left of the day.
+fn main_layout(area: Rect, app: &App) -> MainLayout {
+    let footer_height = if app.storage.settings().hide_legend { 3 } else { 4 };
+    let banner_height = if app.reminders_due.is_empty() { 0 } else { 1 };
+    let wide = area.width >= WIDE_LAYOUT_MIN_WIDTH;
+    let rows = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(banner_height),  // Reminder banner
             Constraint::Length(3),  // Week header
-            Constraint::Length(3),  // Week strip
-            Constraint::Min(10),    // Day details
-            Constraint::Length(3),  // Footer with shortcuts
+            Constraint::Length(if wide { 0 } else { 3 }),  // Week strip
+            Constraint::Min(10),    // Day details (and the week, when wide)
+            Constraint::Length(footer_height),  // Footer with shortcuts and legend
         ])
-        .split(f.area());
+        .split(area);
 
-    // Draw week header
-    draw_week_header(f, chunks[0], app);
+    let (strip, summary, day) = if wide {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(rows[3]);
+        let week = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),  // Week strip
+                Constraint::Min(0),     // Week summary
+            ])
+            .split(columns[0]);
+        (week[0], Some(week[1]), columns[1])
+    } else {
+        (rows[2], None, rows[3])
+    };
 
-    // Draw week strip
-    week_strip::draw(f, chunks[1], app);
+    MainLayout { banner: rows[0], header: rows[1], strip, summary, day, footer: rows[4] }
+}
 
-    // Draw day details
-    day_view::draw(f, chunks[2], app);
+/// Something on the main view a mouse click selects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickTarget {
+    /// A day in the week strip (0 = first day of the week)
+    Day(usize),
+    /// A row of the day's habit list
+    Habit(usize),
+}
 
-    // Draw footer
-    draw_footer(f, chunks[3]);
+/// What a click at `column`, `row` selects on the main view drawn in `area`, if anything
+pub fn main_view_click(area: Rect, app: &App, column: u16, row: u16) -> Option<ClickTarget> {
+    let layout = main_layout(area, app);
+    let position = Position::new(column, row);
+
+    if let Some(day_idx) = week_strip::day_areas(layout.strip).iter().position(|day| day.contains(position)) {
+        return Some(ClickTarget::Day(day_idx));
+    }
+
+    let rows = day_view::habit_rows_area(layout.day, app);
+    let habit_idx = app.habit_list_scroll.offset() + row.checked_sub(rows.y)? as usize;
+    (rows.contains(position) && habit_idx < app.habits_for_date(app.selected_date()).len())
+        .then_some(ClickTarget::Habit(habit_idx))
 }
 
 /// Draw the week header showing the week range
 fn draw_week_header(f: &mut Frame, area: Rect, app: &App) {
-    let title = format!("Week of {}", app.current_week.format());
+    let mut title = vec![Span::styled(
+        format!("Week of {}", app.current_week.format()),
+        Style::default().fg(app.theme.header),
+    )];
+    if let Some(habit) = app.needs_attention() {
+        title.push(Span::raw("    "));
+        title.push(Span::styled(
+            format!("Needs attention: {}", habit.display_name()),
+            Style::default().fg(app.theme.accent),
+        ));
+    }
+    // The current date and time sit on the border, so they're there whichever week is shown
+    let now = app.now();
+    let clock = Line::from(Span::styled(
+        format!(" Today: {} ", now.format("%a %b %d, %H:%M")),
+        Style::default().fg(if app.today_index().is_some() { app.theme.selected } else { app.theme.muted }),
+    ));
     let block = Block::default()
         .borders(Borders::ALL)
+        .title(clock.right_aligned())
         .style(Style::default());
-    let paragraph = Paragraph::new(title)
+    let paragraph = Paragraph::new(Line::from(title))
         .block(block)
-        .style(Style::default().fg(Color::Cyan));
+        .style(Style::default().fg(app.theme.header));
     f.render_widget(paragraph, area);
 }
 
-/// Draw the footer with keyboard shortcuts
-fn draw_footer(f: &mut Frame, area: Rect) {
+/// Draw the footer with keyboard shortcuts (or the flash message, if any)
+fn draw_footer(f: &mut Frame, area: Rect, app: &App) {
+    if app.view == AppView::GoToDate {
+        // The prompt takes over the footer; a date it couldn't read shows as its title
+        let title = app.flash_message.as_deref()
+            .unwrap_or("Go to date (YYYY-MM-DD or -7) - Enter to go, Esc to cancel");
+        let input = Paragraph::new(input_with_cursor(app))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title(title.to_string())
+                .style(Style::default().fg(app.theme.selected)));
+        f.render_widget(input, area);
+        return;
+    }
+    if let Some(message) = &app.flash_message {
+        let paragraph = Paragraph::new(message.as_str())
+            .block(Block::default().borders(Borders::ALL))
+            .style(Style::default().fg(app.theme.accent));
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let vim = app.storage.settings().vim_keys;
+    let key = |action| app.keymap.short_label(action, vim);
     let shortcuts = vec![
         Span::raw("["),
-        Span::styled("←→", Style::default().fg(Color::Yellow)),
+        Span::styled(key(Action::PrevDay) + &key(Action::NextDay), Style::default().fg(app.theme.selected)),
         Span::raw("] Days  ["),
-        Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+        Span::styled(key(Action::PrevHabit) + &key(Action::NextHabit), Style::default().fg(app.theme.selected)),
         Span::raw("] Habits  ["),
-        Span::styled("Space", Style::default().fg(Color::Yellow)),
+        Span::styled(key(Action::ToggleStatus), Style::default().fg(app.theme.selected)),
         Span::raw("] Toggle  ["),
-        Span::styled("h", Style::default().fg(Color::Yellow)),
+        Span::styled(key(Action::ManageHabits), Style::default().fg(app.theme.selected)),
         Span::raw("] Manage  ["),
-        Span::styled("v", Style::default().fg(Color::Yellow)),
+        Span::styled(key(Action::ViewStats), Style::default().fg(app.theme.selected)),
         Span::raw("] Stats  ["),
-        Span::styled("?", Style::default().fg(Color::Yellow)),
+        Span::styled(key(Action::Help), Style::default().fg(app.theme.selected)),
         Span::raw("] Help  ["),
-        Span::styled("q", Style::default().fg(Color::Yellow)),
-        Span::raw("] Quit"),
+        Span::styled(key(Action::Quit), Style::default().fg(app.theme.selected)),
+        Span::raw("] Quit  "),
+        save_indicator(app),
     ];
 
+    let mut lines = vec![Line::from(shortcuts)];
+    if !app.storage.settings().hide_legend {
+        let glyphs = week_strip::GlyphSet::from_settings(app.storage.settings());
+        lines.push(Line::from(Span::styled(
+            glyphs.legend(app.strip_follows_habit),
+            Style::default().fg(app.theme.muted),
+        )));
+    }
+
     let block = Block::default()
         .borders(Borders::ALL);
-    let paragraph = Paragraph::new(Line::from(shortcuts))
+    let paragraph = Paragraph::new(lines)
         .block(block);
     f.render_widget(paragraph, area);
 }
 
+/// A help line listing the keys bound to `actions`, e.g. "  ← / → : Move between days"
+fn help_line(app: &App, actions: &[Action], extra: &str, text: &str) -> Line<'static> {
+    let vim = app.storage.settings().vim_keys;
+    let keys: Vec<String> = actions.iter().map(|action| app.keymap.label(*action, vim)).collect();
+    Line::from(format!("  {:<5}{} : {}", keys.join(" / "), extra, text))
+}
+
+/// The text input with the character under the cursor highlighted
+pub(crate) fn input_with_cursor(app: &App) -> Line<'static> {
+    let cursor = app.input_cursor();
+    let before: String = app.input_buffer.chars().take(cursor).collect();
+    let at: String = app.input_buffer.chars().nth(cursor).map_or(" ".to_string(), String::from);
+    let after: String = app.input_buffer.chars().skip(cursor + 1).collect();
+    Line::from(vec![
+        Span::raw(before),
+        Span::styled(at, Style::default().add_modifier(Modifier::REVERSED)),
+        Span::raw(after),
+    ])
+}
+
+/// "●" while changes are pending, "✓ saved" once everything is on disk, "read-only" if nothing will be
+fn save_indicator(app: &App) -> Span<'static> {
+    if app.storage.is_read_only() {
+        Span::styled("read-only", Style::default().fg(app.theme.accent))
+    } else if app.has_unsaved_changes() {
+        Span::styled("●", Style::default().fg(app.theme.skipped))
+    } else {
+        Span::styled("✓ saved", Style::default().fg(app.theme.done))
+    }
+}
+
 /// Draw the help view
-fn draw_help_view(f: &mut Frame, _app: &App) {
+fn draw_help_view(f: &mut Frame, app: &App) {
     let help_text = vec![
-        Line::from(Span::styled("Healing-Habits - Keyboard Shortcuts", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))),
+        Line::from(Span::styled("Healing-Habits - Keyboard Shortcuts", Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD))),
         Line::from(""),
-        Line::from(Span::styled("Navigation:", Style::default().fg(Color::Yellow))),
-        Line::from("  ← / → : Move between days"),
-        Line::from("  ↑ / ↓ : Select different habits"),
-        Line::from("  [ / ] : Previous/Next week"),
-        Line::from("  t     : Go to today"),
+        Line::from(Span::styled("Navigation:", Style::default().fg(app.theme.selected))),
+        help_line(app, &[Action::PrevDay, Action::NextDay], "", "Move between days"),
+        help_line(app, &[Action::PrevHabit, Action::NextHabit], "", "Select different habits"),
+        help_line(app, &[Action::PageUpHabits, Action::PageDownHabits], "", "Jump a screenful of habits up / down (long lists scroll)"),
+        help_line(app, &[Action::PrevWeek, Action::NextWeek], "", "Previous/Next week"),
+        help_line(app, &[Action::GoToToday], "", "Go to today"),
+        help_line(app, &[Action::FirstActivity, Action::LastActivity], "", "Jump to first/last day with activity"),
+        help_line(app, &[Action::GoToDate], "", "Go to a date (YYYY-MM-DD, or days from today like -7)"),
+        help_line(app, &[Action::ShowAllHabits], "", "Show all habits, including ones not scheduled today"),
+        help_line(app, &[Action::OnlyUnmarked], "", "Show only habits still unmarked for the selected day"),
+        help_line(app, &[Action::AsNeededPanel], "", "Show only As-needed habits (where they live in strict frequency mode)"),
+        help_line(app, &[Action::CycleTagFilter], "", "Show one tag's habits at a time (main view and stats), then all again"),
+        help_line(app, &[Action::StripFollowsHabit], "", "Week strip shows the selected habit instead of each day's total"),
         Line::from(""),
-        Line::from(Span::styled("Actions:", Style::default().fg(Color::Yellow))),
-        Line::from("  Enter / Space : Toggle habit status (cycles through without saving)"),
-        Line::from("  Esc           : Cancel staged status change"),
-        Line::from("  n     : Add/edit note for selected habit"),
+        Line::from(Span::styled("Actions:", Style::default().fg(app.theme.selected))),
+        help_line(app, &[Action::ToggleStatus], "", "Toggle habit status (cycles through without saving)"),
+        help_line(app, &[Action::ToggleStatusBack], "", "Toggle habit status backwards"),
+        help_line(app, &[Action::Cancel], "", "Cancel staged status change"),
+        help_line(
+            app,
+            &[Action::SetDone, Action::SetPartial, Action::SetSkipped, Action::SetUnmarked, Action::SetNotApplicable],
+            "",
+            "Set Done / Partial / Skipped / Unmarked / N/A and save right away",
+        ),
+        help_line(app, &[Action::MarkAllDone, Action::MarkAllSkipped], "", "Mark every habit listed for the day Done / Skipped (N/A ones are kept)"),
+        help_line(app, &[Action::StartVacation, Action::EndVacation], "", "Start (or remove) a vacation on the selected day / end it there"),
+        help_line(app, &[Action::EditNote], "", "Add a note for selected habit (Tab inserts a snippet, Ctrl+S saves one)"),
+        help_line(app, &[Action::ViewNotes], "", "Every note for the selected habit and day, with when it was written"),
+        help_line(app, &[Action::WeekNote], "", "Write a reflection for the whole week (included in exports)"),
+        help_line(app, &[Action::ResetWeek], "", "Reset (clear) every entry in the viewed week"),
+        help_line(app, &[Action::Review], "", "Review each unmarked past day this week, one at a time"),
+        help_line(app, &[Action::Undo], " / Ctrl+R", "Undo / redo the last saved change (statuses, notes, week resets, habit edits)"),
         Line::from(""),
         Line::from("  Status changes save automatically when you navigate away."),
         Line::from(""),
-        Line::from(Span::styled("Views:", Style::default().fg(Color::Yellow))),
-        Line::from("  v     : View weekly stats"),
-        Line::from("  h     : Manage habits (add/edit/delete/reorder)"),
-        Line::from("  x     : Export week to markdown"),
-        Line::from("  ?     : Show this help"),
+        Line::from(Span::styled("Views:", Style::default().fg(app.theme.selected))),
+        help_line(app, &[Action::ViewStats], "", "View stats (Tab switches week / month / all time)"),
+        help_line(app, &[Action::ViewActivity], "", "View this week's activity feed"),
+        help_line(app, &[Action::Grid], "", "Edit the whole week as a grid"),
+        help_line(app, &[Action::Heatmap], "", "A year of the selected habit, a column per week (←→ habit, ↑↓ year)"),
+        help_line(app, &[Action::ManageHabits], "", "Manage habits (add/edit/delete/reorder, V toggles vim keys)"),
+        help_line(app, &[Action::HabitDetail], "", "Details and all-time stats for the selected habit"),
+        help_line(app, &[Action::Export], "", "Preview export, then Enter to write the markdown file"),
+        help_line(app, &[Action::CommandPalette], " / Ctrl+P", "Command palette (search all actions)"),
+        help_line(app, &[Action::Search], "", "Search every note, then Enter to go to a result"),
+        help_line(app, &[Action::Help], "", "Show this help"),
         Line::from(""),
-        Line::from(Span::styled("Other:", Style::default().fg(Color::Yellow))),
+        Line::from(Span::styled("Other:", Style::default().fg(app.theme.selected))),
         Line::from("  q / Esc : Return to main view / Quit"),
         Line::from("  Ctrl+C  : Quit immediately"),
         Line::from(""),
-        Line::from(Span::styled("Press any key to return...", Style::default().fg(Color::Green))),
+        Line::from(Span::styled("Press any key to return...", Style::default().fg(app.theme.done))),
     ];
 
     let block = Block::default()
@@ -133,10 +402,11 @@ fn draw_help_view(f: &mut Frame, _app: &App) {
 
 /// Draw the note input view
 fn draw_note_input(f: &mut Frame, app: &App) {
-    let habit_name = app.selected_habit()
-        .map(|h| h.name.as_str())
-        .unwrap_or("Unknown");
-    let date = app.selected_date();
+    let (habit_id, date) = app.note_target()
+        .unwrap_or((uuid::Uuid::nil(), app.selected_date()));
+    let habit_name = app.storage.get_habit(habit_id)
+        .map(|h| h.display_name())
+        .unwrap_or_else(|| "Unknown".to_string());
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -149,28 +419,109 @@ fn draw_note_input(f: &mut Frame, app: &App) {
         .split(f.area());
 
     // Header
-    let header_text = format!("Edit Note for {} on {}", habit_name, date.format("%b %d, %Y"));
+    let header_text = if app.skip_reason_for.is_some() {
+        format!("Why was {} skipped on {}?", habit_name, date.format("%b %d, %Y"))
+    } else {
+        format!("Edit Note for {} on {}", habit_name, date.format("%b %d, %Y"))
+    };
     let header = Paragraph::new(header_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        .style(Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD));
     f.render_widget(header, chunks[0]);
 
-    // Input box
-    let input_text = format!("{}", app.input_buffer);
-    let input = Paragraph::new(input_text)
+    // Input box, with the cursor shown as a highlighted cell
+    let input = Paragraph::new(input_with_cursor(app))
         .block(Block::default()
             .borders(Borders::ALL)
             .title("Note")
-            .style(Style::default().fg(Color::Yellow)))
+            .style(Style::default().fg(app.theme.selected)))
+        .wrap(Wrap { trim: false });
+    f.render_widget(input, chunks[1]);
+
+    // Instructions (or the latest snippet message)
+    let instructions = match &app.flash_message {
+        Some(message) => vec![Span::styled(message.as_str(), Style::default().fg(app.theme.accent))],
+        None => {
+            let prompt = match app.staged_status {
+                Some((id, day, status)) if app.needs_skip_note(id, day, status) => {
+                    "A note is required to save this skip. "
+                }
+                _ if app.skip_reason_for.is_some() => "Type a reason (saved as \"Skipped: ...\"; Esc leaves it blank). ",
+                _ => "Type your note. ",
+            };
+            vec![
+                Span::raw(prompt),
+                Span::styled("Enter", Style::default().fg(app.theme.done)),
+                Span::raw(" to save, "),
+                Span::styled("Esc", Style::default().fg(app.theme.skipped)),
+                Span::raw(" to cancel, "),
+                Span::styled("Tab", Style::default().fg(app.theme.header)),
+                Span::raw(" snippets, "),
+                Span::styled("Ctrl+S", Style::default().fg(app.theme.header)),
+                Span::raw(" save as snippet."),
+            ]
+        }
+    };
+    let instructions_widget = Paragraph::new(Line::from(instructions))
+        .block(Block::default().borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    f.render_widget(instructions_widget, chunks[2]);
+
+    // Snippet picker
+    if let Some(selected) = app.snippet_picker {
+        let lines: Vec<Line> = app.storage.settings().snippets.iter()
+            .enumerate()
+            .map(|(idx, snippet)| {
+                if idx == selected {
+                    Line::styled(format!("► {}", snippet), Style::default().fg(app.theme.selected).add_modifier(Modifier::BOLD))
+                } else {
+                    Line::from(format!("  {}", snippet))
+                }
+            })
+            .collect();
+        let picker = Paragraph::new(lines)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("Snippets (↑↓ choose, Enter insert, Esc close)"));
+        f.render_widget(Clear, chunks[3]);
+        f.render_widget(picker, chunks[3]);
+    }
+}
+
+/// Draw the week reflection input view
+fn draw_week_note_input(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Header
+            Constraint::Length(7),  // Input box
+            Constraint::Length(3),  // Instructions
+            Constraint::Min(0),     // Spacer
+        ])
+        .split(f.area());
+
+    // Header
+    let header_text = format!("Reflection for the week of {}", app.current_week.format());
+    let header = Paragraph::new(header_text)
+        .block(Block::default().borders(Borders::ALL))
+        .style(Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD));
+    f.render_widget(header, chunks[0]);
+
+    // Input box
+    let input = Paragraph::new(input_with_cursor(app))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title("Reflection")
+            .style(Style::default().fg(app.theme.selected)))
         .wrap(Wrap { trim: false });
     f.render_widget(input, chunks[1]);
 
     // Instructions
     let instructions = vec![
-        Span::raw("Type your note. "),
-        Span::styled("Enter", Style::default().fg(Color::Green)),
+        Span::raw("Write a short reflection on your week (←→ Home End move the cursor). "),
+        Span::styled("Enter", Style::default().fg(app.theme.done)),
         Span::raw(" to save, "),
-        Span::styled("Esc", Style::default().fg(Color::Red)),
+        Span::styled("Esc", Style::default().fg(app.theme.skipped)),
         Span::raw(" to cancel."),
     ];
     let instructions_widget = Paragraph::new(Line::from(instructions))
@@ -180,29 +531,269 @@ fn draw_note_input(f: &mut Frame, app: &App) {
 
 /// Draw the export confirmation view
 fn draw_export_confirmation(f: &mut Frame, app: &App) {
-    let file_path = app.last_export_path.as_ref()
-        .map(|p| p.to_string_lossy().to_string())
-        .unwrap_or_else(|| "Unknown".to_string());
+    let mut text = vec![
+        Line::from(Span::styled("Export Successful!", Style::default().fg(app.theme.done).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+    ];
 
-    let text = vec![
-        Line::from(Span::styled("Export Successful!", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))),
+    match &app.last_export {
+        Some(export) => {
+            text.push(Line::from(export.message.clone()));
+            if let Some(path) = &export.path {
+                text.push(Line::from(""));
+                text.push(Line::from(Span::styled(
+                    path.to_string_lossy().to_string(),
+                    Style::default().fg(app.theme.header),
+                )));
+            }
+        }
+        None => text.push(Line::from("Your weekly habit report has been exported.")),
+    }
+
+    text.extend([
         Line::from(""),
-        Line::from("Your weekly habit report has been exported to:"),
+        Line::from("You can share this report with your therapist or use it for personal reflection."),
         Line::from(""),
-        Line::from(Span::styled(file_path, Style::default().fg(Color::Cyan))),
         Line::from(""),
-        Line::from("You can share this report with your therapist or use it for personal reflection."),
+        Line::from(Span::styled("Press any key to return...", Style::default().fg(app.theme.selected))),
+    ]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Export Complete")
+        .style(Style::default());
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, f.area());
+}
+
+/// Draw the warning shown when another running copy holds the data file's lock
+fn draw_lock_warning(f: &mut Frame, app: &App) {
+    let pid = app.lock_warning.as_ref().map_or(0, |(pid, _)| *pid);
+    let text = vec![
+        Line::from(Span::styled("Already Running?", Style::default().fg(app.theme.skipped).add_modifier(Modifier::BOLD))),
         Line::from(""),
+        Line::from(format!("Another copy of Healing-Habits (process {}) is using this data file.", pid)),
+        Line::from("If both save, the last one to write wins and the other's changes are lost."),
         Line::from(""),
-        Line::from(Span::styled("Press any key to return...", Style::default().fg(Color::Yellow))),
+        Line::from(vec![
+            Span::styled("r", Style::default().fg(app.theme.done)),
+            Span::raw(" Open read-only  "),
+            Span::styled("f", Style::default().fg(app.theme.skipped)),
+            Span::raw(" Continue anyway  "),
+            Span::styled("q", Style::default().fg(app.theme.selected)),
+            Span::raw(" Quit"),
+        ]),
     ];
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .title("Export Complete")
+        .title("Data File In Use")
+        .style(Style::default());
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, f.area());
+}
+
+/// Draw the reset week confirmation prompt
+fn draw_reset_week_confirmation(f: &mut Frame, app: &App) {
+    let text = vec![
+        Line::from(Span::styled("Reset Week?", Style::default().fg(app.theme.skipped).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(format!("This will clear every status and note for the week of {}.", app.current_week.format())),
+        Line::from(format!(
+            "Other weeks are not affected. Press {} afterwards to undo.",
+            app.keymap.label(Action::Undo, app.storage.settings().vim_keys)
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Press "),
+            Span::styled("y", Style::default().fg(app.theme.skipped)),
+            Span::raw(" to confirm, any other key to cancel."),
+        ]),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Confirm Reset")
         .style(Style::default());
     let paragraph = Paragraph::new(text)
         .block(block)
         .wrap(Wrap { trim: true });
     f.render_widget(paragraph, f.area());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_faulty_view_is_contained() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let app = App::new(temp_file.path()).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+
+        // A deliberately faulty view: the panic is caught and reported
+        let mut error = None;
+        terminal.draw(|f| {
+            error = draw_guarded(f, |_| panic!("faulty view"));
+            if let Some(message) = &error {
+                draw_error_panel(f, &Theme::default(), message);
+            }
+        }).unwrap();
+        assert_eq!(error, Some("Display error: faulty view".to_string()));
+
+        // The terminal and app are still usable for the next frame
+        let mut error = Some(String::new());
+        terminal.draw(|f| error = draw(f, &app)).unwrap();
+        assert_eq!(error, None);
+    }
+
+    #[test]
+    fn test_main_view_click_targets() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let app = App::new(temp_file.path()).unwrap();
+        let area = Rect::new(0, 0, 80, 30);
+        let habit_count = app.habits_for_date(app.selected_date()).len() as u16;
+
+        // The week strip's day row sits inside its border, under the 3-line week header
+        assert_eq!(main_view_click(area, &app, 2, 4), Some(ClickTarget::Day(0)));
+        assert_eq!(main_view_click(area, &app, 77, 4), Some(ClickTarget::Day(6)));
+        assert_eq!(main_view_click(area, &app, 2, 3), None);
+
+        // Habit rows start inside the list's border, under the date header
+        assert_eq!(main_view_click(area, &app, 5, 10), Some(ClickTarget::Habit(0)));
+        assert_eq!(main_view_click(area, &app, 5, 9 + habit_count), Some(ClickTarget::Habit(habit_count as usize - 1)));
+        assert_eq!(main_view_click(area, &app, 5, 10 + habit_count), None);
+        assert_eq!(main_view_click(area, &app, 0, 10), None);
+        assert_eq!(main_view_click(area, &app, 5, 29), None);
+    }
+
+    #[test]
+    fn test_header_shows_now_and_marks_today() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        app.set_clock(|| chrono::NaiveDate::from_ymd_opt(2025, 10, 15).unwrap().and_hms_opt(9, 5, 0).unwrap());
+        app.go_to_today();
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let mut render = |app: &App| -> String {
+            terminal.draw(|f| { draw(f, app); }).unwrap();
+            terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+        };
+
+        let screen = render(&app);
+        assert!(screen.contains("Today: Wed Oct 15, 09:05"));
+        assert!(screen.contains("Wed*["));
+        assert!(!screen.contains("Tue*["));
+
+        // Another week keeps the clock but has no today to mark
+        app.current_week = app.current_week.prev();
+        let screen = render(&app);
+        assert!(screen.contains("Today: Wed Oct 15, 09:05"));
+        assert!(!screen.contains("*["));
+    }
+
+    #[test]
+    fn test_wide_terminals_split_week_and_day() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let app = App::new(temp_file.path()).unwrap();
+        let render = |width: u16| -> Vec<String> {
+            let mut terminal = Terminal::new(TestBackend::new(width, 30)).unwrap();
+            terminal.draw(|f| { draw(f, &app); }).unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..30).map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect()).collect()
+        };
+
+        // Narrow: the day list runs the full width, with no week summary
+        let narrow = render(WIDE_LAYOUT_MIN_WIDTH - 1);
+        assert!(!narrow.iter().any(|row| row.contains("This Week")));
+
+        // Wide: the week summary on the left, the day on the right
+        let wide = render(WIDE_LAYOUT_MIN_WIDTH);
+        let day_left = (WIDE_LAYOUT_MIN_WIDTH * 45 / 100) as usize;
+        let halves = |row: &String| {
+            let chars: Vec<char> = row.chars().collect();
+            (chars[..day_left].iter().collect::<String>(), chars[day_left..].iter().collect::<String>())
+        };
+        assert!(wide.iter().any(|row| halves(row).0.contains("This Week")));
+        assert!(wide.iter().any(|row| halves(row).0.contains("Shower") && halves(row).0.contains("0/")));
+        assert!(wide.iter().any(|row| halves(row).1.contains("► Shower")));
+
+        // Clicks follow the panes
+        let area = Rect::new(0, 0, WIDE_LAYOUT_MIN_WIDTH, 30);
+        assert_eq!(main_view_click(area, &app, 2, 4), Some(ClickTarget::Day(0)));
+        assert_eq!(main_view_click(area, &app, 5, 10), None);
+        assert_eq!(main_view_click(area, &app, day_left as u16 + 5, 7), Some(ClickTarget::Habit(0)));
+    }
+
+    #[test]
+    fn test_long_habit_lists_scroll() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        app.show_all_habits = true;
+        for n in 1..=30 {
+            app.storage.add_habit(format!("Habit {:02}", n)).unwrap();
+        }
+        let count = app.habits_for_date(app.selected_date()).len();
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let mut render = |app: &App| -> String {
+            terminal.draw(|f| { draw(f, app); }).unwrap();
+            terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+        };
+
+        // Wrapping from the top shows the bottom of the list
+        assert!(render(&app).contains("Shower"));
+        app.prev_habit().unwrap();
+        assert_eq!(app.selected_habit_idx, count - 1);
+        let screen = render(&app);
+        assert!(screen.contains("Habit 30") && !screen.contains("Shower"));
+        let offset = app.habit_list_scroll.offset();
+        assert!(offset > 0);
+
+        // Clicks land on the scrolled rows
+        let area = Rect::new(0, 0, 80, 24);
+        assert_eq!(main_view_click(area, &app, 5, 10), Some(ClickTarget::Habit(offset)));
+
+        // ...and wrapping back to the top shows it again
+        app.next_habit().unwrap();
+        assert!(render(&app).contains("Shower"));
+        assert_eq!(app.habit_list_scroll.offset(), 0);
+
+        // Paging moves a screenful at a time, stopping at the ends
+        let rows = app.habit_list_scroll.rows();
+        app.page_down_habits().unwrap();
+        assert_eq!(app.selected_habit_idx, rows);
+        for _ in 0..count / rows + 1 {
+            app.page_down_habits().unwrap();
+        }
+        assert_eq!(app.selected_habit_idx, count - 1);
+        app.page_up_habits().unwrap();
+        assert_eq!(app.selected_habit_idx, count - 1 - rows);
+    }
+
+    #[test]
+    fn test_footer_save_indicator() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        let render = |terminal: &mut Terminal<TestBackend>, app: &App| -> String {
+            terminal.draw(|f| { draw(f, app); }).unwrap();
+            terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+        };
+
+        assert!(render(&mut terminal, &app).contains("✓ saved"));
+
+        // A staged status hasn't been saved yet
+        app.toggle_habit_status();
+        let screen = render(&mut terminal, &app);
+        assert!(screen.contains("●"));
+        assert!(!screen.contains("✓ saved"));
+
+        app.commit_staged_status().unwrap();
+        assert!(render(&mut terminal, &app).contains("✓ saved"));
+    }
+}
@@ -0,0 +1,65 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Draw every note for the selected habit and day, oldest first
+pub fn draw(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),     // Notes
+            Constraint::Length(3),  // Instructions
+        ])
+        .split(f.area());
+
+    let habit_name = app.selected_habit()
+        .map(|h| h.display_name())
+        .unwrap_or_else(|| "Unknown".to_string());
+    let notes = app.current_notes();
+
+    let mut lines = Vec::new();
+    if notes.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No notes for this habit and day.",
+            Style::default().fg(app.theme.unmarked),
+        )));
+    }
+    for (at, text) in &notes {
+        lines.push(Line::from(Span::styled(
+            at.format("%b %d, %Y %H:%M").to_string(),
+            Style::default().fg(app.theme.selected).add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(text.lines().map(|line| Line::from(line.to_string())));
+        lines.push(Line::from(""));
+    }
+
+    let title = format!(
+        "Notes - {} on {} ({})",
+        habit_name,
+        app.selected_date().format("%a %b %d"),
+        notes.len()
+    );
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .wrap(Wrap { trim: false })
+        .scroll((app.notes_scroll, 0));
+    f.render_widget(paragraph, chunks[0]);
+
+    let instructions = vec![
+        Span::styled("↑↓", Style::default().fg(app.theme.selected)),
+        Span::raw(" Scroll  "),
+        Span::styled("n", Style::default().fg(app.theme.header)),
+        Span::raw(" Add note  "),
+        Span::styled("q/Esc", Style::default().fg(app.theme.done)),
+        Span::raw(" Back"),
+    ];
+    let instructions_widget = Paragraph::new(Line::from(instructions))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(instructions_widget, chunks[1]);
+}
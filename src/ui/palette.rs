@@ -0,0 +1,65 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Draw the command palette: query input above the filtered command list
+pub fn draw(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Query input
+            Constraint::Min(5),     // Matching commands
+            Constraint::Length(3),  // Instructions
+        ])
+        .split(f.area());
+
+    // Query input
+    let input = Paragraph::new(format!(": {}", app.input_buffer))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title("Command Palette")
+            .style(Style::default().fg(app.theme.selected)));
+    f.render_widget(input, chunks[0]);
+
+    // Matching commands
+    let matches = app.palette_matches();
+    let items: Vec<ListItem> = if matches.is_empty() {
+        vec![ListItem::new("No matching commands.").style(Style::default().fg(app.theme.unmarked))]
+    } else {
+        matches
+            .iter()
+            .enumerate()
+            .map(|(idx, cmd)| {
+                if idx == app.palette_selected_idx {
+                    ListItem::new(format!("► {}", cmd.label()))
+                        .style(Style::default().fg(app.theme.selected).add_modifier(Modifier::BOLD))
+                } else {
+                    ListItem::new(format!("  {}", cmd.label()))
+                }
+            })
+            .collect()
+    };
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(format!("Commands ({})", matches.len())));
+    f.render_widget(list, chunks[1]);
+
+    // Instructions
+    let instructions = vec![
+        Span::raw("Type to filter. "),
+        Span::styled("↑↓", Style::default().fg(app.theme.selected)),
+        Span::raw(" select, "),
+        Span::styled("Enter", Style::default().fg(app.theme.done)),
+        Span::raw(" to run, "),
+        Span::styled("Esc", Style::default().fg(app.theme.skipped)),
+        Span::raw(" to cancel."),
+    ];
+    let instructions_widget = Paragraph::new(Line::from(instructions))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(instructions_widget, chunks[2]);
+}
@@ -0,0 +1,56 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::{App, AppView};
+
+/// Draw a scrollable preview of the export, with the range prompt under it while one is typed
+pub fn draw(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),     // Preview
+            Constraint::Length(3),  // Instructions
+        ])
+        .split(f.area());
+
+    // Preview
+    let preview = Paragraph::new(app.export_preview.as_str())
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Export Preview ({}) - {}", app.export_kind.description(), app.export_period())))
+        .wrap(Wrap { trim: false })
+        .scroll((app.preview_scroll, 0));
+    f.render_widget(preview, chunks[0]);
+
+    if app.view == AppView::ExportRange {
+        let input = Paragraph::new(super::input_with_cursor(app))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("Days to export (YYYY-MM-DD to YYYY-MM-DD, blank for the week) - Enter to apply, Esc to cancel")
+                .style(Style::default().fg(app.theme.selected)));
+        f.render_widget(input, chunks[1]);
+        return;
+    }
+
+    // Instructions
+    let instructions = vec![
+        Span::styled("↑↓", Style::default().fg(app.theme.selected)),
+        Span::raw(" Scroll  "),
+        Span::styled("Tab", Style::default().fg(app.theme.header)),
+        Span::raw(" Format  "),
+        Span::styled("r", Style::default().fg(app.theme.header)),
+        Span::raw(" Date range  "),
+        Span::styled("Enter", Style::default().fg(app.theme.done)),
+        Span::raw(" Write file  "),
+        Span::styled("Esc", Style::default().fg(app.theme.skipped)),
+        Span::raw(" Cancel"),
+    ];
+    let instructions_widget = Paragraph::new(Line::from(instructions))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(instructions_widget, chunks[1]);
+}
@@ -0,0 +1,64 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::models::Week;
+
+/// Draw the guided review of the week's unmarked past days
+pub fn draw(f: &mut Frame, app: &App) {
+    let total = app.review.pairs.len();
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("Review - {}", app.current_week.format()),
+            Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    match app.review_current() {
+        Some((date, habit_id)) => {
+            let position = app.review.pairs.iter()
+                .position(|&p| p == (date, habit_id))
+                .map_or(total, |idx| idx + 1);
+            let habit_name = app.storage.get_habit(habit_id)
+                .map(|h| h.display_name())
+                .unwrap_or_else(|| "Unknown".to_string());
+
+            lines.push(Line::from(format!("{} of {}", position, total)));
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{}, {}", Week::full_weekday_name(date), date.format("%B %d")),
+                    Style::default().fg(app.theme.selected),
+                ),
+            ]));
+            lines.push(Line::from(Span::styled(
+                habit_name,
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+        }
+        None => lines.push(Line::from("Nothing left to review.")),
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("d", Style::default().fg(app.theme.done)),
+        Span::raw(" Done  "),
+        Span::styled("p", Style::default().fg(app.theme.partial)),
+        Span::raw(" Partial  "),
+        Span::styled("s", Style::default().fg(app.theme.skipped)),
+        Span::raw(" Skipped  "),
+        Span::styled("Space/→", Style::default().fg(app.theme.selected)),
+        Span::raw(" Leave for now  "),
+        Span::styled("q/Esc", Style::default().fg(app.theme.done)),
+        Span::raw(" Finish"),
+    ]));
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Review Unmarked Days"));
+    f.render_widget(paragraph, f.area());
+}
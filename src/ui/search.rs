@@ -0,0 +1,118 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Characters of a note shown in each result
+const SNIPPET_LEN: usize = 60;
+
+/// One line of a note around the first match of `query`, with an ellipsis where it was cut
+fn snippet(note: &str, query: &str) -> String {
+    let chars: Vec<char> = note.split_whitespace().collect::<Vec<_>>().join(" ").chars().collect();
+    let lower: String = chars.iter().collect::<String>().to_lowercase();
+    let query = query.trim().to_lowercase();
+    // Start a little before the match so it has some context
+    let found = lower.find(&query).map_or(0, |byte| lower[..byte].chars().count());
+    let start = found.saturating_sub(SNIPPET_LEN / 4);
+    let end = (start + SNIPPET_LEN).min(chars.len());
+    let start = end.saturating_sub(SNIPPET_LEN);
+
+    let mut text: String = chars[start..end].iter().collect();
+    if start > 0 {
+        text.insert(0, '…');
+    }
+    if end < chars.len() {
+        text.push('…');
+    }
+    text
+}
+
+/// Draw note search: query input above the matching notes
+pub fn draw(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Query input
+            Constraint::Min(5),     // Results
+            Constraint::Length(3),  // Instructions
+        ])
+        .split(f.area());
+
+    // Query input
+    let input = Paragraph::new(format!("/ {}", app.input_buffer))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title("Search Notes")
+            .style(Style::default().fg(app.theme.selected)));
+    f.render_widget(input, chunks[0]);
+
+    // Results
+    let (items, title) = match &app.search_results {
+        None => (
+            vec![ListItem::new("Press Enter to search.").style(Style::default().fg(app.theme.unmarked))],
+            "Results".to_string(),
+        ),
+        Some(results) if results.is_empty() => (
+            vec![ListItem::new("No notes match.").style(Style::default().fg(app.theme.unmarked))],
+            "Results (0)".to_string(),
+        ),
+        Some(results) => {
+            let items = results.iter().enumerate().map(|(idx, (habit_id, date, note))| {
+                let name = app.storage.get_habit(*habit_id)
+                    .map(|h| h.display_name())
+                    .unwrap_or_else(|| "Unknown".to_string());
+                let selected = idx == app.search_selected_idx;
+                let marker = if selected { "► " } else { "  " };
+                let line = Line::from(vec![
+                    Span::raw(marker),
+                    Span::styled(format!("{:<20}", name), Style::default().fg(app.theme.header)),
+                    Span::styled(format!(" {}  ", date.format("%a %b %d, %Y")), Style::default().fg(app.theme.muted)),
+                    Span::raw(snippet(note, &app.input_buffer)),
+                ]);
+                let item = ListItem::new(line);
+                if selected {
+                    item.style(Style::default().fg(app.theme.selected).add_modifier(Modifier::BOLD))
+                } else {
+                    item
+                }
+            }).collect();
+            (items, format!("Results ({})", results.len()))
+        }
+    };
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(list, chunks[1]);
+
+    // Instructions
+    let instructions = vec![
+        Span::raw("Type to search. "),
+        Span::styled("↑↓", Style::default().fg(app.theme.selected)),
+        Span::raw(" select, "),
+        Span::styled("Enter", Style::default().fg(app.theme.done)),
+        Span::raw(" to search / go to the day, "),
+        Span::styled("Esc", Style::default().fg(app.theme.skipped)),
+        Span::raw(" to cancel."),
+    ];
+    let instructions_widget = Paragraph::new(Line::from(instructions))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(instructions_widget, chunks[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet_centers_on_match() {
+        assert_eq!(snippet("Bad\nsleep", "sleep"), "Bad sleep");
+        let long = format!("{} bad sleep {}", "x".repeat(80), "y".repeat(80));
+        let text = snippet(&long, "BAD SLEEP");
+        assert!(text.starts_with('…') && text.ends_with('…'));
+        assert!(text.contains("bad sleep"));
+        assert_eq!(text.chars().count(), SNIPPET_LEN + 2);
+    }
+}
@@ -1,61 +1,344 @@
 use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols::bar,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Sparkline},
     Frame,
 };
 
-use crate::app::App;
+use crate::app::{App, TREND_WEEKS};
+use crate::storage::round_rate;
+use crate::theme::Theme;
 
-/// Draw the weekly stats view
+/// Narrowest a completion bar gets, however small the terminal
+const MIN_BAR_WIDTH: usize = 5;
+
+/// Sparkline bars for terminals without Unicode block characters
+const ASCII_BARS: bar::Set = bar::Set {
+    full: "#",
+    seven_eighths: "#",
+    three_quarters: "#",
+    five_eighths: "=",
+    half: "=",
+    three_eighths: "-",
+    one_quarter: "-",
+    one_eighth: ".",
+    empty: " ",
+};
+
+/// Bar color for a completion rate: the theme's done color from 80%, selected from 50%, skipped below
+fn rate_color(theme: &Theme, pct: usize) -> Color {
+    match pct {
+        80.. => theme.done,
+        50.. => theme.selected,
+        _ => theme.skipped,
+    }
+}
+
+/// Draw the stats view for the chosen range (week, month or all time)
 pub fn draw(f: &mut Frame, app: &App) {
-    let stats = app.storage.get_stats(app.current_week.start, app.current_week.end());
-    let habits = app.habits();
+    let bounds = app.stats_bounds();
+    let habits = app.filtered_habits();
 
     let mut items = vec![
         ListItem::new(Line::from(Span::styled(
-            format!("Weekly Stats - {}", app.current_week.format()),
-            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            app.stats_title(),
+            Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD),
         ))),
         ListItem::new(Line::from("")),
     ];
 
     if habits.is_empty() {
         items.push(ListItem::new("No habits tracked yet."));
+    } else if let Some((start, end)) = bounds {
+        let stats = app.storage.get_stats(start, end);
+        let rows: Vec<(String, usize, String)> = habits.iter()
+            .filter_map(|habit| {
+                let stats = stats.get(&habit.id)?;
+                let total = stats.trackable();
+                let completion_pct = round_rate(stats.done, total);
+                let summary = format!(
+                    " Done: {}/{} ({}%)  Partial: {}  Skipped: {}  Unmarked: {}  Of marked days: {}%",
+                    stats.done, total, completion_pct, stats.partial, stats.skipped, stats.unmarked,
+                    round_rate(stats.done, stats.marked())
+                );
+                Some((habit.display_name(), completion_pct, summary))
+            })
+            .collect();
+
+        // The bars share whatever width is left after the name and the longest summary
+        let summary_width = rows.iter().map(|(_, _, s)| s.chars().count()).max().unwrap_or(0);
+        let inner_width = f.area().width.saturating_sub(2) as usize;
+        let bar_width = inner_width.saturating_sub(20 + 1 + summary_width + 1).max(MIN_BAR_WIDTH);
+
+        let (full, empty) = if app.storage.settings().ascii_glyphs { ("#", ".") } else { ("█", "░") };
+        for (name, completion_pct, summary) in rows {
+            let filled = (completion_pct * bar_width + 50) / 100;
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<20}", name), Style::default().fg(app.theme.selected)),
+                Span::raw(" "),
+                Span::styled(full.repeat(filled), Style::default().fg(rate_color(&app.theme, completion_pct))),
+                Span::styled(empty.repeat(bar_width - filled), Style::default().fg(app.theme.muted)),
+                Span::raw(format!("{:<width$}", summary, width = summary_width)),
+            ])));
+        }
     } else {
-        for habit in habits {
-            if let Some((done, skipped, unmarked)) = stats.get(&habit.id) {
-                let total = done + skipped + unmarked;
-                let completion_pct = if total > 0 {
-                    (done * 100) / total
-                } else {
-                    0
-                };
-
-                items.push(ListItem::new(Line::from(vec![
-                    Span::styled(
-                        format!("{:<20}", habit.name),
-                        Style::default().fg(Color::Yellow),
-                    ),
-                    Span::raw(format!(
-                        " Done: {}/7 ({}%)  Skipped: {}  Unmarked: {}",
-                        done, completion_pct, skipped, unmarked
-                    )),
-                ])));
-            }
+        items.push(ListItem::new("Nothing recorded yet."));
+    }
+
+    if !habits.is_empty() {
+        items.push(ListItem::new(Line::from("")));
+        items.push(ListItem::new(Line::from(Span::styled(
+            "All-time Insights",
+            Style::default().fg(app.theme.header).add_modifier(Modifier::BOLD),
+        ))));
+        for habit in &habits {
+            let best_week = match app.storage.best_week(habit.id) {
+                Some((week, done)) => format!("{} ({} done)", week.format(), done),
+                None => "-".to_string(),
+            };
+            let best_streak = match app.storage.best_streak(habit.id) {
+                Some((start, len)) => format!("{} days from {}", len, start.format("%b %d, %Y")),
+                None => "-".to_string(),
+            };
+            let rank = match app.storage.week_rank(habit.id, app.current_week) {
+                Some(rank) => format!("#{}", rank),
+                None => "-".to_string(),
+            };
+
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<20}", habit.display_name()),
+                    Style::default().fg(app.theme.selected),
+                ),
+                Span::raw(format!(
+                    " Best week: {}  Best streak: {}  This week: {}",
+                    best_week, best_streak, rank
+                )),
+            ])));
         }
     }
 
     items.push(ListItem::new(Line::from("")));
     items.push(ListItem::new(Line::from(Span::styled(
-        "Press 'q' or Esc to return",
-        Style::default().fg(Color::Green),
+        "Press Tab to switch between week, month and all time; 'q' or Esc to return",
+        Style::default().fg(app.theme.done),
     ))));
 
+    // The trend gets a row per habit under the list, leaving the list at least half the screen
+    let trend_height = if habits.is_empty() { 0 } else { (habits.len() as u16 + 2).min(f.area().height / 2) };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),                 // Stats list
+            Constraint::Length(trend_height),   // Weekly trend
+        ])
+        .split(f.area());
+
     let block = Block::default()
         .borders(Borders::ALL)
-        .title("Weekly Statistics")
+        .title("Statistics")
         .style(Style::default());
     let list = List::new(items).block(block);
-    f.render_widget(list, f.area());
+    f.render_widget(list, chunks[0]);
+
+    if trend_height > 0 {
+        draw_trend(f, chunks[1], app);
+    }
+}
+
+/// Draw each habit's completion rate over the last `TREND_WEEKS` weeks as a sparkline
+fn draw_trend(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Trend - {} weeks to {}", TREND_WEEKS, app.current_week.format()));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let bar_set = if app.storage.settings().ascii_glyphs { ASCII_BARS } else { bar::NINE_LEVELS };
+    for (row, habit) in app.filtered_habits().iter().enumerate().take(inner.height as usize) {
+        let line = Rect { y: inner.y + row as u16, height: 1, ..inner };
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(21),                  // Name
+                Constraint::Length(TREND_WEEKS as u16),  // Sparkline
+                Constraint::Min(0),                      // Latest rate
+            ])
+            .split(line);
+
+        let series = app.storage.weekly_completion_series(habit.id, TREND_WEEKS, app.current_week.start);
+        let data: Vec<u64> = series.iter().map(|&(_, rate)| (rate * 100.0).round() as u64).collect();
+        let latest = data.last().copied().unwrap_or(0);
+
+        f.render_widget(
+            Paragraph::new(format!("{:<20}", habit.display_name())).style(Style::default().fg(app.theme.selected)),
+            columns[0],
+        );
+        f.render_widget(
+            Sparkline::default()
+                .data(&data)
+                .max(100)
+                .bar_set(bar_set.clone())
+                .style(Style::default().fg(rate_color(&app.theme, latest as usize))),
+            columns[1],
+        );
+        f.render_widget(Paragraph::new(format!(" {}% this week", latest)), columns[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{HabitStatus, Week};
+    use chrono::NaiveDate;
+    use ratatui::{backend::TestBackend, Terminal};
+    use tempfile::NamedTempFile;
+
+    /// Render the stats view and return its text
+    fn render(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(160, 30)).unwrap();
+        terminal.draw(|f| draw(f, app)).unwrap();
+        terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+    }
+
+    #[test]
+    fn test_rate_color_thresholds() {
+        let theme = Theme::default();
+        assert_eq!(rate_color(&theme, 100), Color::Green);
+        assert_eq!(rate_color(&theme, 80), Color::Green);
+        assert_eq!(rate_color(&theme, 79), Color::Yellow);
+        assert_eq!(rate_color(&theme, 50), Color::Yellow);
+        assert_eq!(rate_color(&theme, 49), Color::Red);
+        assert_eq!(rate_color(&theme, 0), Color::Red);
+    }
+
+    #[test]
+    fn test_stats_follow_tag_filter() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        app.current_week = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
+        let days = app.current_week.days();
+        let mut shower = app.habits()[0].clone();
+        shower.set_created_at(days[0]);
+        shower.tags = vec!["hygiene".to_string()];
+        app.storage.update_habit(shower.clone()).unwrap();
+        app.storage.update_log_status(shower.id, days[0], HabitStatus::Done).unwrap();
+
+        app.cycle_tag_filter().unwrap();
+        let screen = render(&app);
+        assert!(screen.contains("Shower"));
+        assert!(!screen.contains("Meds"));
+        assert!(screen.contains("Done: 1/7 (14%)"));
+    }
+
+    #[test]
+    fn test_trend_sparkline_per_habit() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        app.current_week = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
+        let days = app.current_week.days();
+        let mut shower = app.habits()[0].clone();
+        shower.set_created_at(days[0]);
+        app.storage.update_habit(shower.clone()).unwrap();
+        for &day in &days {
+            app.storage.update_log_status(shower.id, day, HabitStatus::Done).unwrap();
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(160, 30)).unwrap();
+        terminal.draw(|f| draw(f, &app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let row = (0..30).map(|y| (0..160).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+            .find(|row| row.contains("Shower") && row.contains("this week"))
+            .unwrap();
+        // Only the last of the 12 weeks is full
+        assert_eq!(row.matches('█').count(), 1);
+        assert!(row.contains("100% this week"));
+        assert!(render(&app).contains("Trend - 12 weeks"));
+    }
+
+    #[test]
+    fn test_bars_fill_in_proportion_to_width() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        app.current_week = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
+        let days = app.current_week.days();
+        for mut habit in app.habits().into_iter().cloned().collect::<Vec<_>>() {
+            habit.set_created_at(days[0]);
+            app.storage.update_habit(habit).unwrap();
+        }
+        let shower = app.habits()[0].id;
+        for &day in &days {
+            app.storage.update_log_status(shower, day, HabitStatus::Done).unwrap();
+        }
+
+        // One row of the screen per line, to find each habit's bar
+        let bar_cells = |width: u16, name: &str| {
+            let mut terminal = Terminal::new(TestBackend::new(width, 30)).unwrap();
+            terminal.draw(|f| draw(f, &app)).unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..30).map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+                .find(|row| row.contains(name) && row.contains("Done:"))
+                .map(|row| (row.matches('█').count(), row.matches('░').count()))
+                .unwrap()
+        };
+
+        // Full bar for 100%, empty for 0%, and wider terminals get wider bars
+        let (filled, empty) = bar_cells(160, "Shower");
+        assert_eq!(empty, 0);
+        let (none, rest) = bar_cells(160, "Meds");
+        assert_eq!((none, rest), (0, filled));
+        let (narrow, _) = bar_cells(100, "Shower");
+        assert!(narrow < filled);
+        assert!(narrow >= MIN_BAR_WIDTH);
+    }
+
+    #[test]
+    fn test_stats_and_export_rates_agree() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut app = App::new(temp_file.path()).unwrap();
+        app.current_week = Week::containing(NaiveDate::from_ymd_opt(2025, 10, 13).unwrap());
+        let days = app.current_week.days();
+        for mut habit in app.habits().into_iter().cloned().collect::<Vec<_>>() {
+            habit.set_created_at(days[0]);
+            app.storage.update_habit(habit).unwrap();
+        }
+        let shower = app.habits()[0].id;
+        let meds = app.habits()[3].id;
+
+        // Shower: 5 of 7 done, the rest skipped (71.4%)
+        for (idx, &day) in days.iter().enumerate() {
+            let status = if idx < 5 { HabitStatus::Done } else { HabitStatus::Skipped };
+            app.storage.update_log_status(shower, day, status).unwrap();
+        }
+        let stats = render(&app);
+        let export = app.export_week_to_markdown();
+        assert!(stats.contains("Done: 5/7 (71%)"));
+        assert!(export.contains("| Shower | 5 | 0 | 2 | 0 | 71% | 71% |"));
+
+        // Meds: 1 done, 2 skipped, 4 unmarked; both count all 7 trackable days (14.3%)
+        app.storage.update_log_status(meds, days[0], HabitStatus::Done).unwrap();
+        app.storage.update_log_status(meds, days[1], HabitStatus::Skipped).unwrap();
+        app.storage.update_log_status(meds, days[2], HabitStatus::Skipped).unwrap();
+        let stats = render(&app);
+        let export = app.export_week_to_markdown();
+        assert!(stats.contains("Done: 1/7 (14%)"));
+        assert!(export.contains("| Meds | 1 | 0 | 2 | 4 | 14% | 33% |"));
+
+        // Brush teeth: marked Done on one day only; unmarked days count against completion
+        // but not against the rate over marked days
+        let brush = app.habits()[1].id;
+        app.storage.update_log_status(brush, days[3], HabitStatus::Done).unwrap();
+        let stats = render(&app);
+        let export = app.export_week_to_markdown();
+        assert!(stats.contains("Done: 1/7 (14%)  Partial: 0  Skipped: 0  Unmarked: 6  Of marked days: 100%"));
+        assert!(export.contains("| Brush teeth | 1 | 0 | 0 | 6 | 14% | 100% |"));
+
+        // All time runs from the first to the last logged day (Oct 13-19 here)
+        app.stats_range = crate::app::StatsRange::AllTime;
+        let stats = render(&app);
+        assert!(stats.contains("All-time Stats - Oct 13, 2025 to Oct 19, 2025"));
+        assert!(stats.contains("Done: 5/7 (71%)"));
+    }
 }
@@ -1,22 +1,125 @@
+use std::rc::Rc;
+
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
 use crate::app::App;
-use crate::models::Week;
+use crate::models::Settings;
+use crate::theme::habit_color;
+
+/// Symbols used to draw the week strip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphSet {
+    /// ✓ ◐ ✗ ~ ∅
+    Unicode,
+    /// + o x ~ / for terminals that can't show the Unicode symbols
+    Ascii,
+}
+
+impl GlyphSet {
+    /// The glyph set the user picked
+    pub fn from_settings(settings: &Settings) -> Self {
+        if settings.ascii_glyphs {
+            GlyphSet::Ascii
+        } else {
+            GlyphSet::Unicode
+        }
+    }
+
+    /// Translate a status symbol from the app into this glyph set
+    pub fn symbol(&self, symbol: char) -> char {
+        match (self, symbol) {
+            (GlyphSet::Ascii, '✓') => '+',
+            (GlyphSet::Ascii, '◐') => 'o',
+            (GlyphSet::Ascii, '✗') => 'x',
+            (GlyphSet::Ascii, '∅') => '/',
+            (GlyphSet::Ascii, '·') => '.',
+            _ => symbol,
+        }
+    }
+
+    /// One-line key to the strip, for day totals or for a single followed habit
+    pub fn legend(&self, follows_habit: bool) -> String {
+        let entries: &[(char, &str)] = if follows_habit {
+            &[('✓', "done"), ('◐', "partial"), ('✗', "skipped"), ('∅', "n/a"), ('-', "not scheduled"), ('·', "vacation"), (' ', "unmarked")]
+        } else {
+            &[('✓', "all done"), ('~', "partial"), ('✗', "skipped"), ('·', "vacation"), (' ', "none")]
+        };
+        entries.iter()
+            .map(|(symbol, meaning)| format!("[{}] {}", self.symbol(*symbol), meaning))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+}
 
 /// Draw the week strip showing 7 days with status symbols
 pub fn draw(f: &mut Frame, area: Rect, app: &App) {
-    let block = Block::default()
+    let mut block = Block::default()
         .borders(Borders::ALL)
         .style(Style::default());
+    if let Some(habit) = app.selected_habit().filter(|_| app.strip_follows_habit) {
+        block = block.title(format!(" {} ", habit.display_name()));
+    }
+    let day_chunks = day_areas(area);
+
+    f.render_widget(block, area);
+
+    // Draw each day
+    for day_idx in 0..7 {
+        draw_day(f, day_chunks[day_idx], app, day_idx);
+    }
+}
+
+/// Draw each habit's week at a glance: a symbol per day and its Done count
+/// Shown under the strip when the main view is wide enough to put the day beside it.
+pub fn draw_summary(f: &mut Frame, area: Rect, app: &App) {
+    let glyphs = GlyphSet::from_settings(app.storage.settings());
+    let stats = app.storage.get_stats(app.current_week.start, app.current_week.end());
 
+    // Weekday initials over the symbol columns
+    let initials: Vec<String> = (0..7)
+        .map(|idx| app.current_week.weekday_name(idx).chars().take(1).collect())
+        .collect();
+    let mut lines = vec![Line::from(Span::styled(
+        format!("{:<21}{}", "", initials.join(" ")),
+        Style::default().fg(app.theme.muted),
+    ))];
+    for habit in app.filtered_habits() {
+        // Unmarked days get an underscore so the columns stay readable
+        let days: Vec<String> = (0..7)
+            .map(|idx| match glyphs.symbol(app.get_habit_day_status(habit.id, idx)) {
+                ' ' => '_'.to_string(),
+                symbol => symbol.to_string(),
+            })
+            .collect();
+        let habit_stats = stats.get(&habit.id).copied().unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:<20} ", habit.display_name()),
+                Style::default().fg(habit_color(habit).unwrap_or(app.theme.selected)),
+            ),
+            Span::raw(days.join(" ")),
+            Span::styled(
+                format!("  {}/{}", habit_stats.done, habit_stats.trackable()),
+                Style::default().fg(app.theme.muted),
+            ),
+        ]));
+    }
+
+    let summary = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("This Week"));
+    f.render_widget(summary, area);
+}
+
+/// Where each day's column sits inside a strip drawn in `area`, starting with the week's first day
+pub fn day_areas(area: Rect) -> Rc<[Rect]> {
     // Split into 7 equal columns for each day
-    let day_chunks = Layout::default()
+    Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
             Constraint::Percentage(14),
@@ -27,35 +130,38 @@ pub fn draw(f: &mut Frame, area: Rect, app: &App) {
             Constraint::Percentage(14),
             Constraint::Percentage(15),
         ])
-        .split(block.inner(area));
-
-    f.render_widget(block, area);
-
-    // Draw each day
-    for day_idx in 0..7 {
-        draw_day(f, day_chunks[day_idx], app, day_idx);
-    }
+        .split(Block::default().borders(Borders::ALL).inner(area))
 }
 
 /// Draw a single day in the week strip
 fn draw_day(f: &mut Frame, area: Rect, app: &App, day_idx: usize) {
-    let day_name = Week::weekday_name(day_idx);
-    let status_symbol = app.get_day_status(day_idx);
+    let day_name = app.current_week.weekday_name(day_idx);
+    let glyphs = GlyphSet::from_settings(app.storage.settings());
+    let status_symbol = glyphs.symbol(app.week_strip_status(day_idx));
 
     // Highlight if this is the selected day
     let is_selected = day_idx == app.selected_day_idx;
     let style = if is_selected {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(app.theme.selected)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
     };
 
+    // Today is starred and underlined, wherever the selection is
+    let is_today = app.today_index() == Some(day_idx);
+    let (marker, name_style) = if is_today {
+        ("*", style.add_modifier(Modifier::UNDERLINED))
+    } else {
+        (" ", style)
+    };
+
     // Combine day name and status symbol on the same line
     let text = vec![
         Line::from(vec![
-            Span::styled(format!("{} ", day_name), style),
+            Span::styled(day_name.to_string(), name_style),
+            Span::styled(marker, style),
             Span::styled(format!("[{}]", status_symbol), style),
         ]),
     ];
@@ -63,3 +169,26 @@ fn draw_day(f: &mut Frame, area: Rect, app: &App, day_idx: usize) {
     let paragraph = Paragraph::new(text);
     f.render_widget(paragraph, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legend_follows_glyph_set() {
+        assert_eq!(
+            GlyphSet::Unicode.legend(false),
+            "[✓] all done  [~] partial  [✗] skipped  [·] vacation  [ ] none"
+        );
+        assert_eq!(
+            GlyphSet::Ascii.legend(false),
+            "[+] all done  [~] partial  [x] skipped  [.] vacation  [ ] none"
+        );
+        assert!(GlyphSet::Ascii.legend(true).contains("[/] n/a"));
+        assert!(GlyphSet::Ascii.legend(true).is_ascii());
+
+        let settings = Settings { ascii_glyphs: true, ..Settings::default() };
+        assert_eq!(GlyphSet::from_settings(&settings), GlyphSet::Ascii);
+        assert_eq!(GlyphSet::from_settings(&Settings::default()), GlyphSet::Unicode);
+    }
+}
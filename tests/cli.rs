@@ -0,0 +1,62 @@
+use std::process::Command;
+
+#[test]
+fn test_version_flag_prints_version_and_exits() {
+    let output = Command::new(env!("CARGO_BIN_EXE_healing-habits"))
+        .arg("--version")
+        .output()
+        .expect("failed to run healing-habits");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let first_line = stdout.lines().next().unwrap();
+    let version = first_line.strip_prefix("healing-habits ").unwrap();
+    let parts: Vec<&str> = version.split('.').collect();
+    assert_eq!(parts.len(), 3);
+    assert!(parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit())));
+    assert!(stdout.contains("habits.json"));
+}
+
+#[test]
+fn test_help_flag_lists_options() {
+    let output = Command::new(env!("CARGO_BIN_EXE_healing-habits"))
+        .arg("--help")
+        .output()
+        .expect("failed to run healing-habits");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    for flag in ["--data", "--template", "--import", "--no-mouse", "--version", "--help"] {
+        assert!(stdout.contains(flag), "help is missing {}", flag);
+    }
+}
+
+#[test]
+fn test_data_flag_overrides_data_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let data = dir.path().join("profiles").join("work.json");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_healing-habits"))
+        .args(["--data", data.to_str().unwrap(), "--version"])
+        .output()
+        .expect("failed to run healing-habits");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&format!("Data file: {}", data.display())));
+}
+
+#[test]
+fn test_data_flag_rejects_directory() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_healing-habits"))
+        .args(["-d", dir.path().to_str().unwrap()])
+        .output()
+        .expect("failed to run healing-habits");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("is a directory"));
+}